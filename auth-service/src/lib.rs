@@ -1,4 +1,4 @@
-mod config;
-mod domain;
-mod infrastructure;
-mod presentation;
+pub mod config;
+pub mod domain;
+pub mod infrastructure;
+pub mod presentation;
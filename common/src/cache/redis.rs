@@ -7,12 +7,25 @@ use crate::cache::traits::Cache;
 #[derive(Debug)]
 pub struct RedisCache {
     client: redis::Client,
+    prefix: String,
 }
 
 impl RedisCache {
     pub fn new(url: &str) -> Result<Self, redis::RedisError> {
         let client = redis::Client::open(url)?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            prefix: String::new(),
+        })
+    }
+
+    pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
     }
 
     async fn get_conn(&self) -> Option<redis::aio::MultiplexedConnection> {
@@ -24,24 +37,39 @@ impl RedisCache {
 impl Cache for RedisCache {
     async fn get_str(&self, key: &str) -> Option<String> {
         let mut conn = self.get_conn().await?;
-        conn.get(key).await.ok()
+        conn.get(self.key(key)).await.ok()
     }
 
     async fn set_str(&self, key: &str, value: &str, ttl: Duration) {
         if let Some(mut conn) = self.get_conn().await {
-            let _: Result<(), _> = conn.set_ex(key, value, ttl.as_secs()).await;
+            let _: Result<(), _> = conn.set_ex(self.key(key), value, ttl.as_secs()).await;
         }
     }
 
     async fn delete_str(&self, key: &str) {
         if let Some(mut conn) = self.get_conn().await {
-            let _: Result<(), _> = conn.del(key).await;
+            let _: Result<(), _> = conn.del(self.key(key)).await;
+        }
+    }
+
+    async fn get_many_str(&self, keys: &[String]) -> Vec<Option<String>> {
+        if keys.is_empty() {
+            return Vec::new();
         }
+        let Some(mut conn) = self.get_conn().await else {
+            return vec![None; keys.len()];
+        };
+        let keys: Vec<String> = keys.iter().map(|key| self.key(key)).collect();
+        redis::cmd("MGET")
+            .arg(&keys)
+            .query_async(&mut conn)
+            .await
+            .unwrap_or_else(|_| vec![None; keys.len()])
     }
 
     async fn exists_str(&self, key: &str) -> bool {
         if let Some(mut conn) = self.get_conn().await {
-            conn.exists(key).await.unwrap_or(false)
+            conn.exists(self.key(key)).await.unwrap_or(false)
         } else {
             false
         }
@@ -52,6 +52,40 @@ impl Cache for TieredCache {
         None
     }
 
+    async fn get_shared_str(&self, key: &str) -> Option<String> {
+        match self.l2 {
+            Some(ref l2) => l2.get_str(key).await,
+            None => self.l1.get_str(key).await,
+        }
+    }
+
+    async fn get_many_str(&self, keys: &[String]) -> Vec<Option<String>> {
+        let mut values = self.l1.get_many_str(keys).await;
+
+        let Some(ref l2) = self.l2 else {
+            return values;
+        };
+        let missing: Vec<usize> = (0..keys.len()).filter(|&i| values[i].is_none()).collect();
+        if missing.is_empty() {
+            return values;
+        }
+
+        let missing_keys: Vec<String> = missing.iter().map(|&i| keys[i].clone()).collect();
+        let found = l2.get_many_str(&missing_keys).await;
+        for (i, value) in missing.into_iter().zip(found) {
+            if let Some(value) = value {
+                self.l1.set_str(&keys[i], &value, self.l1_ttl).await;
+                values[i] = Some(value);
+            }
+        }
+        tracing::debug!(
+            keys = keys.len(),
+            misses = values.iter().filter(|v| v.is_none()).count(),
+            "Cache multi-get"
+        );
+        values
+    }
+
     async fn set_str(&self, key: &str, value: &str, ttl: Duration) {
         self.l1.set_str(key, value, self.l1_ttl).await;
 
@@ -1,14 +1,58 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use serde::{Serialize, de::DeserializeOwned};
 
+use crate::etag::Versioned;
+
 #[async_trait]
 pub trait Cache: Send + Sync + 'static {
     async fn get_str(&self, key: &str) -> Option<String>;
     async fn set_str(&self, key: &str, value: &str, ttl: Duration);
     async fn delete_str(&self, key: &str);
     async fn exists_str(&self, key: &str) -> bool;
+
+    /// Reads `key` from the tier every instance shares, skipping any copy
+    /// kept in this process. Single-tier caches just read it.
+    async fn get_shared_str(&self, key: &str) -> Option<String> {
+        self.get_str(key).await
+    }
+
+    /// Looks up several keys at once; the result lines up with `keys`.
+    async fn get_many_str(&self, keys: &[String]) -> Vec<Option<String>> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.get_str(key).await);
+        }
+        values
+    }
+}
+
+#[async_trait]
+impl<C: Cache + ?Sized> Cache for Arc<C> {
+    async fn get_str(&self, key: &str) -> Option<String> {
+        (**self).get_str(key).await
+    }
+
+    async fn set_str(&self, key: &str, value: &str, ttl: Duration) {
+        (**self).set_str(key, value, ttl).await
+    }
+
+    async fn delete_str(&self, key: &str) {
+        (**self).delete_str(key).await
+    }
+
+    async fn exists_str(&self, key: &str) -> bool {
+        (**self).exists_str(key).await
+    }
+
+    async fn get_shared_str(&self, key: &str) -> Option<String> {
+        (**self).get_shared_str(key).await
+    }
+
+    async fn get_many_str(&self, keys: &[String]) -> Vec<Option<String>> {
+        (**self).get_many_str(keys).await
+    }
 }
 
 #[async_trait]
@@ -23,6 +67,17 @@ pub trait CacheExt: Cache {
             .and_then(|json| serde_json::from_str(&json).ok())
     }
 
+    async fn get_many<V>(&self, keys: &[String]) -> Vec<Option<V>>
+    where
+        V: DeserializeOwned + Send,
+    {
+        self.get_many_str(keys)
+            .await
+            .into_iter()
+            .map(|json| json.and_then(|json| serde_json::from_str(&json).ok()))
+            .collect()
+    }
+
     async fn set<K, V>(&self, key: K, value: V, ttl: Duration)
     where
         K: AsRef<str> + Send,
@@ -33,6 +88,22 @@ pub trait CacheExt: Cache {
         }
     }
 
+    /// Stores `value` unless the cache already holds a newer version of it, so
+    /// a slow writer cannot overwrite a fresher entry with a stale one.
+    async fn set_versioned<K, V>(&self, key: K, value: &V, ttl: Duration)
+    where
+        K: AsRef<str> + Send,
+        V: Versioned + Serialize + DeserializeOwned + Send + Sync,
+    {
+        if let Some(cached) = self.get::<_, V>(key.as_ref()).await
+            && cached.version() > value.version()
+        {
+            tracing::debug!(key = %key.as_ref(), "Skipping stale cache write");
+            return;
+        }
+        self.set(key, value, ttl).await;
+    }
+
     async fn delete<K>(&self, key: K)
     where
         K: AsRef<str> + Send,
@@ -40,6 +111,37 @@ pub trait CacheExt: Cache {
         self.delete_str(key.as_ref()).await
     }
 
+    /// Current generation of a family of entries, such as the pages of a
+    /// list, starting one if there is none. Keying the entries by it lets a
+    /// single [`bump_generation`](Self::bump_generation) orphan all of them,
+    /// leaving them to expire.
+    ///
+    /// Read it before loading the data to cache: a write that lands in
+    /// between bumps the generation, so the stale entry is never looked up.
+    /// It is always read from the shared tier, since a bump made by another
+    /// instance would not reach a per-process copy.
+    async fn generation<K>(&self, key: K, ttl: Duration) -> String
+    where
+        K: AsRef<str> + Send,
+    {
+        match self.get_shared_str(key.as_ref()).await {
+            Some(generation) => generation,
+            None => self.bump_generation(key, ttl).await,
+        }
+    }
+
+    /// Starts a new generation for the entries behind `key`. Generations are
+    /// random rather than counted, so concurrent bumps never agree on a value
+    /// a reader may already hold.
+    async fn bump_generation<K>(&self, key: K, ttl: Duration) -> String
+    where
+        K: AsRef<str> + Send,
+    {
+        let generation = uuid::Uuid::new_v4().simple().to_string();
+        self.set_str(key.as_ref(), &generation, ttl).await;
+        generation
+    }
+
     async fn exists<K>(&self, key: K) -> bool
     where
         K: AsRef<str> + Send,
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use config::ConfigError;
 use serde::{Deserialize, Serialize};
@@ -9,6 +9,20 @@ pub struct Settings {
     pub database: DatabaseSettings,
     pub cache: CacheSettings,
     pub pubsub: PubSubSettings,
+    #[serde(default)]
+    pub search: SearchSettings,
+    #[serde(default)]
+    pub trash: TrashSettings,
+    #[serde(default)]
+    pub feed: FeedSettings,
+    #[serde(default)]
+    pub erasure: ErasureSettings,
+    #[serde(default)]
+    pub export: ExportSettings,
+    #[serde(default)]
+    pub suspension: SuspensionSettings,
+    #[serde(default)]
+    pub mail: MailSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +83,10 @@ pub struct RedisSettings {
     pub password: Option<String>,
     #[serde(default)]
     pub database: Option<u8>,
+    /// Prepended to every cache key, so deployments sharing a Redis keep
+    /// their entries apart.
+    #[serde(default)]
+    pub key_prefix: String,
 }
 
 impl RedisSettings {
@@ -182,3 +200,273 @@ pub struct PubSubSettings {
 fn default_emulator_host() -> String {
     "localhost:8085".to_string()
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSettings {
+    #[serde(default = "default_index_path")]
+    pub index_path: String,
+    #[serde(default = "default_writer_memory_mb")]
+    pub writer_memory_mb: usize,
+    /// Pub/Sub subscription the indexer pulls from; indexing from events is disabled when unset.
+    /// Each instance suffixes it with its host name, see [`SearchSettings::instance_subscription`].
+    #[serde(default)]
+    pub subscription: Option<String>,
+}
+
+fn default_index_path() -> String {
+    "data/search-index".to_string()
+}
+fn default_writer_memory_mb() -> usize {
+    50
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            index_path: default_index_path(),
+            writer_memory_mb: default_writer_memory_mb(),
+            subscription: None,
+        }
+    }
+}
+
+impl SearchSettings {
+    /// The subscription this instance indexes from.
+    ///
+    /// Every replica keeps its own index on local disk, so every replica must
+    /// see every event. Sharing one subscription would split the events across
+    /// replicas, so the configured name is suffixed with the host name to give
+    /// each instance its own. A new subscription only receives events published
+    /// after it was created, which the startup bootstrap covers. Subscriptions
+    /// left behind by replaced hosts expire under Pub/Sub's inactivity policy.
+    pub fn instance_subscription(&self) -> Option<String> {
+        let subscription = self.subscription.as_ref()?;
+        let host = std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty());
+
+        Some(match host {
+            Some(host) => {
+                let host: String = host
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                    .collect();
+                format!("{}-{}", subscription, host)
+            }
+            None => subscription.clone(),
+        })
+    }
+}
+
+/// How long soft-deleted rows stay restorable before the purge job removes them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashSettings {
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u64,
+    #[serde(default = "default_purge_interval_secs")]
+    pub purge_interval_secs: u64,
+}
+
+fn default_retention_days() -> u64 {
+    30
+}
+fn default_purge_interval_secs() -> u64 {
+    3600
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: default_retention_days(),
+            purge_interval_secs: default_purge_interval_secs(),
+        }
+    }
+}
+
+impl TrashSettings {
+    pub fn retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.retention_days as i64)
+    }
+
+    pub fn purge_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.purge_interval_secs)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedSettings {
+    /// Pub/Sub subscription the feed fan-out pulls from; fan-out is disabled when unset.
+    #[serde(default)]
+    pub subscription: Option<String>,
+    /// Authors with at least this many subscribers are merged into feeds at read
+    /// time instead of being pushed into every subscriber's timeline.
+    #[serde(default = "default_hot_author_threshold")]
+    pub hot_author_threshold: u64,
+}
+
+fn default_hot_author_threshold() -> u64 {
+    10_000
+}
+
+impl Default for FeedSettings {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            hot_author_threshold: default_hot_author_threshold(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureSettings {
+    /// Pub/Sub subscription erasure acknowledgements are pulled from; tracking
+    /// is disabled when unset.
+    #[serde(default)]
+    pub subscription: Option<String>,
+    /// How long the other services get to acknowledge an erasure before it is
+    /// marked as timed out.
+    #[serde(default = "default_erasure_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_erasure_check_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+fn default_erasure_timeout_secs() -> u64 {
+    24 * 3600
+}
+fn default_erasure_check_interval_secs() -> u64 {
+    60
+}
+
+impl Default for ErasureSettings {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            timeout_secs: default_erasure_timeout_secs(),
+            check_interval_secs: default_erasure_check_interval_secs(),
+        }
+    }
+}
+
+impl ErasureSettings {
+    pub fn timeout(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.timeout_secs as i64)
+    }
+
+    pub fn check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.check_interval_secs)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSettings {
+    /// Pub/Sub subscription export requests or parts are pulled from; exports
+    /// are not processed when unset.
+    #[serde(default)]
+    pub subscription: Option<String>,
+    /// Directory finished export archives are written to.
+    #[serde(default = "default_export_dir")]
+    pub dir: String,
+    /// Base URL of the service each export part is fetched from, keyed by
+    /// part name.
+    #[serde(default)]
+    pub sources: BTreeMap<String, String>,
+    /// How long the other services get to send their parts before the export
+    /// is marked as failed.
+    #[serde(default = "default_export_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_export_check_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+fn default_export_dir() -> String {
+    "data/exports".to_string()
+}
+fn default_export_timeout_secs() -> u64 {
+    3600
+}
+fn default_export_check_interval_secs() -> u64 {
+    60
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            dir: default_export_dir(),
+            sources: BTreeMap::new(),
+            timeout_secs: default_export_timeout_secs(),
+            check_interval_secs: default_export_check_interval_secs(),
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn timeout(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.timeout_secs as i64)
+    }
+
+    pub fn check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.check_interval_secs)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspensionSettings {
+    /// Pub/Sub subscription user status changes are pulled from; they are
+    /// not followed when unset.
+    #[serde(default)]
+    pub subscription: Option<String>,
+    /// How often lapsed suspensions are looked for and lifted.
+    #[serde(default = "default_suspension_check_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+fn default_suspension_check_interval_secs() -> u64 {
+    60
+}
+
+impl Default for SuspensionSettings {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            check_interval_secs: default_suspension_check_interval_secs(),
+        }
+    }
+}
+
+impl SuspensionSettings {
+    pub fn check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.check_interval_secs)
+    }
+}
+
+/// Where outgoing email is handed off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailSettings {
+    /// Base URL of the mail relay, which accepts messages on Mailpit's
+    /// `/api/v1/send` API.
+    #[serde(default = "default_mail_url")]
+    pub url: String,
+    /// Address email is sent from.
+    #[serde(default = "default_mail_sender")]
+    pub sender: String,
+}
+
+fn default_mail_url() -> String {
+    "http://localhost:8025".to_string()
+}
+fn default_mail_sender() -> String {
+    "no-reply@blog.local".to_string()
+}
+
+impl Default for MailSettings {
+    fn default() -> Self {
+        Self {
+            url: default_mail_url(),
+            sender: default_mail_sender(),
+        }
+    }
+}
@@ -4,6 +4,15 @@ use crate::config::DatabaseSettings;
 
 use super::error::Result;
 
+/// Escapes `LIKE` wildcards, so user input only ever matches literally. They
+/// are common in usernames (`_`) and titles (`%`).
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 #[async_trait]
 pub trait Database: Send + Sync {
     type Pool;
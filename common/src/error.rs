@@ -1,10 +1,14 @@
+use std::collections::BTreeMap;
+
 use axum::{
     Json,
+    extract::rejection::JsonRejection,
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use validator::{ValidationErrors, ValidationErrorsKind};
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
@@ -16,12 +20,27 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Validation error: {0}")]
+    FieldValidationError(#[from] ValidationErrors),
+
+    #[error("Invalid request body: {0}")]
+    JsonRejection(#[from] JsonRejection),
+
     #[error("Conflict: {0}")]
     ConflictError(String),
 
+    #[error("Precondition failed: {0}")]
+    PreconditionFailedError(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequiredError(String),
+
     #[error("Unauthorized: {0}")]
     UnauthorizedError(String),
 
+    #[error("Forbidden: {0}")]
+    ForbiddenError(String),
+
     #[error("Internal server error: {0}")]
     InternalServerError(#[from] anyhow::Error),
 
@@ -57,15 +76,67 @@ impl From<sea_orm::DbErr> for AppError {
 pub struct ErrorResponse {
     pub error: String,
     pub message: String,
+    /// Messages per offending field, keyed by its path (`tags[0]`, `author.name`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// Flattens nested validation errors into messages keyed by field path.
+fn field_messages(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    fn collect(prefix: &str, errors: &ValidationErrors, out: &mut BTreeMap<String, Vec<String>>) {
+        for (field, kind) in errors.errors() {
+            let path = if prefix.is_empty() {
+                field.to_string()
+            } else {
+                format!("{}.{}", prefix, field)
+            };
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    out.entry(path).or_default().extend(errors.iter().map(|e| {
+                        e.message
+                            .as_deref()
+                            .map_or_else(|| format!("Invalid value ({})", e.code), str::to_string)
+                    }));
+                }
+                ValidationErrorsKind::Struct(errors) => collect(&path, errors, out),
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        collect(&format!("{}[{}]", path, index), errors, out);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    collect("", errors, &mut out);
+    out
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let fields = match &self {
+            AppError::FieldValidationError(e) => Some(field_messages(e)),
+            _ => None,
+        };
+
         let (status, message) = match &self {
             AppError::NotFoundError(e) => (StatusCode::NOT_FOUND, e.to_string()),
             AppError::ValidationError(e) => (StatusCode::BAD_REQUEST, e.to_string()),
+            AppError::FieldValidationError(_) => (
+                StatusCode::BAD_REQUEST,
+                "Request failed validation".to_string(),
+            ),
+            AppError::JsonRejection(e) => (e.status(), e.body_text()),
             AppError::ConflictError(e) => (StatusCode::CONFLICT, e.to_string()),
+            AppError::PreconditionFailedError(e) => {
+                (StatusCode::PRECONDITION_FAILED, e.to_string())
+            }
+            AppError::PreconditionRequiredError(e) => {
+                (StatusCode::PRECONDITION_REQUIRED, e.to_string())
+            }
             AppError::UnauthorizedError(e) => (StatusCode::UNAUTHORIZED, e.to_string()),
+            AppError::ForbiddenError(e) => (StatusCode::FORBIDDEN, e.to_string()),
             AppError::InternalServerError(e) => {
                 tracing::error!("Internal server error: {:?}", e);
                 (
@@ -103,6 +174,7 @@ impl IntoResponse for AppError {
         let body = Json(ErrorResponse {
             error: status.to_string(),
             message,
+            fields,
         });
 
         (status, body).into_response()
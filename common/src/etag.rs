@@ -0,0 +1,86 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderName, header, request::Parts},
+};
+
+use crate::error::AppError;
+
+/// Response header pair carrying the strong entity tag of a resource.
+pub type ETagHeader = [(HeaderName, String); 1];
+
+/// Versioned resources expose their row version as a strong `ETag`.
+pub trait Versioned {
+    fn version(&self) -> i32;
+}
+
+pub fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+pub fn header(version: i32) -> ETagHeader {
+    [(header::ETAG, etag(version))]
+}
+
+/// Parsed `If-Match` request header.
+///
+/// Handlers that take this extractor reject requests without the header
+/// with `428 Precondition Required`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    Any,
+    Versions(Vec<i32>),
+}
+
+impl IfMatch {
+    pub fn parse(value: &str) -> Self {
+        if value.trim() == "*" {
+            return IfMatch::Any;
+        }
+
+        // If-Match uses strong comparison, so weak tags never match
+        let versions = value
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.starts_with("W/"))
+            .filter_map(|tag| tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+            .collect();
+
+        IfMatch::Versions(versions)
+    }
+
+    pub fn matches(&self, version: i32) -> bool {
+        match self {
+            IfMatch::Any => true,
+            IfMatch::Versions(versions) => versions.contains(&version),
+        }
+    }
+
+    /// Fails with `412 Precondition Failed` if `version` is not matched.
+    pub fn check(&self, version: i32) -> Result<(), AppError> {
+        if self.matches(version) {
+            Ok(())
+        } else {
+            Err(AppError::PreconditionFailedError(format!(
+                "Resource has been modified, current ETag is {}",
+                etag(version)
+            )))
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(header::IF_MATCH)
+            .ok_or_else(|| {
+                AppError::PreconditionRequiredError("If-Match header is required".to_string())
+            })?
+            .to_str()
+            .map_err(|_| AppError::ValidationError("Invalid If-Match header".to_string()))?;
+
+        Ok(IfMatch::parse(value))
+    }
+}
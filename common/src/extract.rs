@@ -0,0 +1,41 @@
+use std::borrow::Cow;
+
+use axum::{
+    Json,
+    extract::{FromRequest, Request},
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError};
+
+use crate::error::AppError;
+
+/// JSON body extractor that also runs the payload's `Validate` rules.
+///
+/// Malformed bodies are rejected the same way `Json` rejects them; bodies
+/// that fail validation get `400 Bad Request` with a `fields` map listing
+/// every offending field.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        value.validate()?;
+        Ok(ValidatedJson(value))
+    }
+}
+
+/// Rejects strings that are empty or only whitespace, for use with
+/// `#[validate(custom(function = "common::extract::not_blank"))]`.
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message(Cow::Borrowed("Cannot be blank")));
+    }
+    Ok(())
+}
@@ -2,6 +2,8 @@ pub mod cache;
 pub mod config;
 pub mod db;
 pub mod error;
+pub mod etag;
+pub mod extract;
 pub mod macros;
 pub mod outbox;
 pub mod pagination;
@@ -23,7 +23,16 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary")]
     pub payload: serde_json::Value,
     pub created_at: DateTimeWithTimeZone,
-    pub send_at: Option<DateTimeWithTimeZone>,
+    pub sent_at: Option<DateTimeWithTimeZone>,
+    /// Failed publish attempts so far. Delivery bookkeeping, so it stays out of
+    /// the published message like the fields below.
+    #[serde(skip)]
+    pub attempts: i32,
+    #[serde(skip)]
+    pub last_error: Option<String>,
+    /// Set once the event is given up on; the poller no longer picks it up.
+    #[serde(skip)]
+    pub dead_lettered_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -33,6 +42,14 @@ impl ActiveModelBehavior for ActiveModel {}
 
 pub type OutBoxEvent = Model;
 
+/// Pub/Sub rejects messages larger than this, so such events are dead-lettered
+/// without trying.
+pub const MAX_MESSAGE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Publish attempts before an event is dead-lettered instead of holding up the
+/// events behind it.
+pub const MAX_PUBLISH_ATTEMPTS: i32 = 10;
+
 pub async fn insert_outbox_event(
     tx: &DatabaseTransaction,
     aggregate_type: &str,
@@ -47,7 +64,10 @@ pub async fn insert_outbox_event(
         event_type: Set(event_type.to_string()),
         payload: Set(payload),
         created_at: Set(Utc::now().into()),
-        send_at: Set(None),
+        sent_at: Set(None),
+        attempts: Set(0),
+        last_error: Set(None),
+        dead_lettered_at: Set(None),
     };
     Ok(event.insert(tx).await?)
 }
@@ -88,7 +108,8 @@ impl OutboxPoller {
 
     async fn poll_and_publish(&self) -> crate::error::Result<()> {
         let events: Vec<OutBoxEvent> = Entity::find()
-            .filter(Column::SendAt.is_null())
+            .filter(Column::SentAt.is_null())
+            .filter(Column::DeadLetteredAt.is_null())
             .order_by_asc(Column::CreatedAt)
             .limit(self.batch_size)
             .all(&self.conn)
@@ -98,16 +119,34 @@ impl OutboxPoller {
             return Ok(());
         }
 
-        let count = events.len();
-        tracing::debug!("Outbox poller found {} unsend events", count);
+        tracing::debug!("Outbox poller found {} unsend events", events.len());
 
+        let mut count = 0;
         for event in events {
             let message = serde_json::to_string(&event)?;
-            self.publisher.publish(message).await?;
+            if message.len() > MAX_MESSAGE_BYTES {
+                let error = format!(
+                    "Message of {} bytes exceeds the {} byte limit",
+                    message.len(),
+                    MAX_MESSAGE_BYTES
+                );
+                self.record_failure(event, error, true).await?;
+                continue;
+            }
+
+            if let Err(e) = self.publisher.publish(message).await {
+                // Most failures are transient, so the batch waits for the next
+                // poll to keep events in order, unless this one keeps failing
+                if !self.record_failure(event, e.to_string(), false).await? {
+                    break;
+                }
+                continue;
+            }
 
             let mut active: ActiveModel = event.into();
-            active.send_at = Set(Some(Utc::now().into()));
+            active.sent_at = Set(Some(Utc::now().into()));
             active.update(&self.conn).await?;
+            count += 1;
 
             tracing::debug!("Published outbox event");
         }
@@ -115,4 +154,41 @@ impl OutboxPoller {
         tracing::info!("Outbox poller published {} events", count);
         Ok(())
     }
+
+    /// Records a failed attempt and dead-letters the event when `give_up` is
+    /// set or it has used up its attempts. Returns whether it was dead-lettered.
+    async fn record_failure(
+        &self,
+        event: OutBoxEvent,
+        error: String,
+        give_up: bool,
+    ) -> crate::error::Result<bool> {
+        let attempts = event.attempts + 1;
+        let dead = give_up || attempts >= MAX_PUBLISH_ATTEMPTS;
+        if dead {
+            tracing::error!(
+                "Dead-lettering outbox event {} ({}) after {} attempts: {}",
+                event.id,
+                event.event_type,
+                attempts,
+                error
+            );
+        } else {
+            tracing::warn!(
+                "Failed to publish outbox event {} (attempt {}): {}",
+                event.id,
+                attempts,
+                error
+            );
+        }
+
+        let mut active: ActiveModel = event.into();
+        active.attempts = Set(attempts);
+        active.last_error = Set(Some(error));
+        if dead {
+            active.dead_lettered_at = Set(Some(Utc::now().into()));
+        }
+        active.update(&self.conn).await?;
+        Ok(dead)
+    }
 }
@@ -32,6 +32,23 @@ impl Pagination {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl From<SortOrder> for sea_orm::Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => sea_orm::Order::Asc,
+            SortOrder::Desc => sea_orm::Order::Desc,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
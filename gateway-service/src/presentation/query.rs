@@ -34,12 +34,13 @@ impl QueryRoot {
             )));
         }
 
-        let (posts, users_response) = tokio::try_join!(
-            post_response.json::<Vec<RawPost>>(),
+        let (posts_response, users_response) = tokio::try_join!(
+            post_response.json::<PaginatedResponse<RawPost>>(),
             user_response.json::<PaginatedResponse<RawUser>>()
         )
         .map_err(|e| AppError::InternalServerError(e.into()))?;
 
+        let posts = posts_response.data;
         let users = users_response.data;
 
         let user_map: std::collections::HashMap<_, _> =
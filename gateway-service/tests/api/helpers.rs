@@ -47,6 +47,7 @@ impl TestGateway {
         assert!(status.success(), "docker compose up failed");
     }
 
+    #[allow(dead_code)]
     pub fn docker_compose_down(profile: Option<&str>) {
         let mut cmd = Command::new("docker");
         cmd.arg("compose")
@@ -2,6 +2,9 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_notifications;
 mod m20220102_000002_create_outbox;
+mod m20220103_000003_create_notification_preferences;
+mod m20220104_000004_add_outbox_dead_letters;
+mod m20220105_000005_create_notification_exports;
 
 pub struct Migrator;
 
@@ -11,6 +14,9 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20220101_000001_create_notifications::Migration),
             Box::new(m20220102_000002_create_outbox::Migration),
+            Box::new(m20220103_000003_create_notification_preferences::Migration),
+            Box::new(m20220104_000004_add_outbox_dead_letters::Migration),
+            Box::new(m20220105_000005_create_notification_exports::Migration),
         ]
     }
 }
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationExport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationExport::ExportId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(NotificationExport::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(NotificationExport::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationExport::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Erasing a user removes their exports too
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_exports_user_id")
+                    .table(NotificationExport::Table)
+                    .col(NotificationExport::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NotificationExport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationExport {
+    #[sea_orm(iden = "notification_exports")]
+    Table,
+    ExportId,
+    UserId,
+    Data,
+    CreatedAt,
+}
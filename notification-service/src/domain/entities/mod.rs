@@ -1 +1,3 @@
 pub mod notification;
+pub mod notification_export;
+pub mod notification_preference;
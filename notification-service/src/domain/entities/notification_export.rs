@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+/// The `notifications` part of a user's data export, kept here until the
+/// users service fetches it.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_exports")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub export_id: Uuid,
+    pub user_id: Uuid,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub data: Json,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
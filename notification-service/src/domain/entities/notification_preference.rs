@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The channel opt-ins a user last saved in the users service. Users without
+/// a row get every notification.
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub channels: ChannelOptIns,
+    /// Version of the preferences in the users service; older events are ignored.
+    pub version: i32,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Channel switches keyed by notification kind, then by channel name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct ChannelOptIns(pub BTreeMap<String, BTreeMap<String, bool>>);
+
+impl ChannelOptIns {
+    /// Kinds and channels the user never decided on are allowed.
+    pub fn allows(&self, kind: &str, channel: &str) -> bool {
+        self.0
+            .get(kind)
+            .and_then(|channels| channels.get(channel))
+            .copied()
+            .unwrap_or(true)
+    }
+}
+
+pub type NotificationPreference = Model;
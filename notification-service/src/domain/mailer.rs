@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use common::error::Result;
+use std::{fmt::Debug, sync::Arc};
+
+/// A plain-text email to a single recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync + Debug {
+    /// Hands the email off for delivery; an error means it was not accepted.
+    async fn send(&self, email: Email) -> Result<()>;
+}
+
+pub type DynMailer = Arc<dyn Mailer>;
@@ -1,2 +1,3 @@
 pub mod entities;
+pub mod mailer;
 pub mod repository;
@@ -3,7 +3,7 @@ use common::{error::Result, pagination::Pagination};
 use std::{fmt::Debug, sync::Arc};
 use uuid::Uuid;
 
-use crate::domain::entities::notification::Notification;
+use crate::domain::entities::{notification::Notification, notification_preference::ChannelOptIns};
 
 #[async_trait]
 pub trait NotificationRepository: Send + Sync + Debug {
@@ -16,6 +16,30 @@ pub trait NotificationRepository: Send + Sync + Debug {
     ) -> Result<(Vec<Notification>, u64)>;
     async fn mark_as_read(&self, id: Uuid) -> Result<()>;
     async fn delete_notification(&self, id: Uuid) -> Result<()>;
+    /// Removes every notification of a user, along with their preferences and
+    /// exports, and returns how many notifications there were. Erasure
+    /// `erasure_id` is acknowledged to the users service in the same
+    /// transaction.
+    async fn delete_notifications_for_user(&self, user_id: Uuid, erasure_id: Uuid) -> Result<u64>;
+    /// Stores every notification of a user as the `notifications` part of
+    /// export `export_id` and tells the users service where to fetch it.
+    async fn export_notifications_for_user(
+        &self,
+        user_id: Uuid,
+        export_id: Uuid,
+    ) -> Result<Vec<Notification>>;
+    /// The stored part of export `export_id`, if there is one.
+    async fn get_export(&self, export_id: Uuid) -> Result<Option<serde_json::Value>>;
+    /// Keeps the channel opt-ins from the user's preferences at `version`,
+    /// unless newer ones are already stored. Returns whether they were kept.
+    async fn store_preferences(
+        &self,
+        user_id: Uuid,
+        channels: ChannelOptIns,
+        version: i32,
+    ) -> Result<bool>;
+    /// Whether the user wants `kind` notifications delivered in the app.
+    async fn wants_in_app(&self, user_id: Uuid, kind: &str) -> Result<bool>;
 }
 
 pub type DynNotificationRepository = Arc<dyn NotificationRepository>;
@@ -1,16 +1,21 @@
 use uuid::Uuid;
 
 use async_trait::async_trait;
-use common::{error::Result, pagination::Pagination};
+use common::{error::Result, outbox, pagination::Pagination};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{Set, Unchanged},
     ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter,
-    QueryOrder,
+    QueryOrder, QuerySelect, TransactionTrait,
+    sea_query::OnConflict,
 };
 
 use crate::domain::{
-    entities::{self, notification::Notification},
+    entities::{
+        self,
+        notification::Notification,
+        notification_preference::{self, ChannelOptIns},
+    },
     repository::NotificationRepository,
 };
 
@@ -99,4 +104,136 @@ impl NotificationRepository for SeaOrmNotificationRepository {
         notification.unwrap().delete(&self.conn).await?;
         Ok(())
     }
+
+    async fn delete_notifications_for_user(&self, user_id: Uuid, erasure_id: Uuid) -> Result<u64> {
+        let tx = self.conn.begin().await?;
+
+        let deleted = entities::notification::Entity::delete_many()
+            .filter(entities::notification::Column::UserId.eq(user_id))
+            .exec(&tx)
+            .await?
+            .rows_affected;
+        notification_preference::Entity::delete_by_id(user_id)
+            .exec(&tx)
+            .await?;
+        entities::notification_export::Entity::delete_many()
+            .filter(entities::notification_export::Column::UserId.eq(user_id))
+            .exec(&tx)
+            .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user_id,
+            "user_erasure_step_completed",
+            serde_json::json!({
+                "erasure_id": erasure_id,
+                "user_id": user_id,
+                "step": "notifications",
+                "notifications_deleted": deleted,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn export_notifications_for_user(
+        &self,
+        user_id: Uuid,
+        export_id: Uuid,
+    ) -> Result<Vec<Notification>> {
+        let tx = self.conn.begin().await?;
+
+        let notifications = entities::notification::Entity::find()
+            .filter(entities::notification::Column::UserId.eq(user_id))
+            .order_by_asc(entities::notification::Column::CreatedAt)
+            .all(&tx)
+            .await?;
+
+        // The event only points at the part: it can outgrow a Pub/Sub message,
+        // and every subscriber would see it
+        entities::notification_export::Entity::insert(entities::notification_export::ActiveModel {
+            export_id: Set(export_id),
+            user_id: Set(user_id),
+            data: Set(serde_json::json!({ "notifications": notifications })),
+            created_at: Set(chrono::Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::column(entities::notification_export::Column::ExportId)
+                .update_columns([
+                    entities::notification_export::Column::Data,
+                    entities::notification_export::Column::CreatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(&tx)
+        .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user_id,
+            "user_export_part_ready",
+            serde_json::json!({
+                "export_id": export_id,
+                "user_id": user_id,
+                "part": "notifications",
+                "path": format!("/exports/{}", export_id),
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(notifications)
+    }
+
+    async fn get_export(&self, export_id: Uuid) -> Result<Option<serde_json::Value>> {
+        Ok(entities::notification_export::Entity::find_by_id(export_id)
+            .one(&self.conn)
+            .await?
+            .map(|export| export.data))
+    }
+
+    async fn store_preferences(
+        &self,
+        user_id: Uuid,
+        channels: ChannelOptIns,
+        version: i32,
+    ) -> Result<bool> {
+        let tx = self.conn.begin().await?;
+
+        let current = notification_preference::Entity::find_by_id(user_id)
+            .lock_exclusive()
+            .one(&tx)
+            .await?;
+        if current
+            .as_ref()
+            .is_some_and(|current| current.version >= version)
+        {
+            return Ok(false);
+        }
+
+        let preference = notification_preference::ActiveModel {
+            user_id: Set(user_id),
+            channels: Set(channels),
+            version: Set(version),
+            updated_at: Set(chrono::Utc::now().into()),
+        };
+        match current {
+            Some(_) => preference.update(&tx).await?,
+            None => preference.insert(&tx).await?,
+        };
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn wants_in_app(&self, user_id: Uuid, kind: &str) -> Result<bool> {
+        let preference = notification_preference::Entity::find_by_id(user_id)
+            .one(&self.conn)
+            .await?;
+        Ok(preference.is_none_or(|preference| preference.channels.allows(kind, "in_app")))
+    }
 }
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use axum::Router;
 
 use crate::presentation::{
-    routes::{health::health_check_router, notification::notifications_router},
+    routes::{
+        exports::exports_router, health::health_check_router, notification::notifications_router,
+    },
     state::AppState,
 };
 
@@ -11,4 +13,5 @@ pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(health_check_router(state.clone()))
         .nest("/notifications", notifications_router(state.clone()))
+        .nest("/exports", exports_router(state.clone()))
 }
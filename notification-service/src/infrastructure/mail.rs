@@ -0,0 +1,42 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use common::{config::MailSettings, error::Result};
+
+use crate::domain::mailer::{Email, Mailer};
+
+/// Sends email through a relay speaking Mailpit's send API.
+#[derive(Debug, Clone)]
+pub struct HttpMailer {
+    url: String,
+    sender: String,
+    client: reqwest::Client,
+}
+
+impl HttpMailer {
+    pub fn new(settings: &MailSettings) -> Self {
+        Self {
+            url: format!("{}/api/v1/send", settings.url.trim_end_matches('/')),
+            sender: settings.sender.clone(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for HttpMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(&serde_json::json!({
+                "From": { "Email": self.sender },
+                "To": [{ "Email": email.to }],
+                "Subject": email.subject,
+                "Text": email.body,
+            }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to send email through {}", self.url))?;
+        Ok(())
+    }
+}
@@ -1,2 +1,3 @@
 pub mod database;
 pub mod http;
+pub mod mail;
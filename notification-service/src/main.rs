@@ -7,8 +7,12 @@ use common::{
 };
 use notification_service::{
     infrastructure::{
-        database::{bootstrap::bootstrap_db, factory::RepoProvider},
+        database::{
+            bootstrap::{bootstrap_db, bootstrap_outbox},
+            factory::RepoProvider,
+        },
         http::create_router,
+        mail::HttpMailer,
     },
     presentation::state::AppState,
 };
@@ -32,8 +36,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     .expect("Failed to bind to port");
 
     let conn = bootstrap_db(&config.database).await?;
-    let repo_provider = RepoProvider::from_connection(conn).await?;
-    let state = AppState::new(repo_provider);
+    let repo_provider = RepoProvider::from_connection(conn.clone()).await?;
+
+    let outbox_poller = bootstrap_outbox(conn, &config.pubsub).await?;
+    outbox_poller.spawn();
+
+    let state = AppState::new(repo_provider, Arc::new(HttpMailer::new(&config.mail)));
     let state_arc = Arc::new(state);
 
     let pubsub_subscriber = PubSubSubscriber::new(&config.pubsub).await?;
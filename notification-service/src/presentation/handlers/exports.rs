@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use common::error::{AppError, Result};
+use uuid::Uuid;
+
+use crate::presentation::state::AppState;
+
+/// The `notifications` part of an export, fetched by the users service once
+/// the `user_export_part_ready` event tells it the part is there.
+pub async fn get_export_part(
+    State(state): State<Arc<AppState>>,
+    Path(export_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let data = state
+        .repos
+        .notifications
+        .get_export(export_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Export not found".into()))?;
+    Ok(Json(data))
+}
@@ -1,3 +1,4 @@
+pub mod exports;
 pub mod health;
 pub mod notification;
 pub mod types;
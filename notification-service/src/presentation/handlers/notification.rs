@@ -6,6 +6,7 @@ use axum::{
 };
 use common::{
     error::{AppError, Result},
+    extract::ValidatedJson,
     pagination::{PaginatedResponse, Pagination},
 };
 use uuid::Uuid;
@@ -21,7 +22,7 @@ use crate::{
 
 pub async fn create_notification(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateNotificationRequest>,
+    ValidatedJson(payload): ValidatedJson<CreateNotificationRequest>,
 ) -> Result<Json<NotificationResponse>> {
     let notification = Notification {
         id: Uuid::new_v4(),
@@ -1,14 +1,27 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::domain::entities::notification::Notification;
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Validate)]
 pub struct CreateNotificationRequest {
     pub user_id: Uuid,
+    #[validate(
+        length(max = 50, message = "Kind cannot exceed 50 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
     pub kind: String,
+    #[validate(
+        length(max = 200, message = "Title cannot exceed 200 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
     pub title: String,
+    #[validate(
+        length(max = 2000, message = "Message cannot exceed 2000 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
     pub message: String,
 }
 
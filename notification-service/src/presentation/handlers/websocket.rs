@@ -39,7 +39,9 @@ async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>, user_id: Uui
                match msg {
                 Some(Ok(Message::Close(_))) | None => break,
                 Some(Ok(Message::Ping(msg))) => {
-                    if socket.send(Message::Pong(msg)).await.is_err() {
+                    // Bound first: the pong takes the payload, which a match guard cannot move
+                    let pong = socket.send(Message::Pong(msg)).await;
+                    if pong.is_err() {
                         break;
                     }
                 }
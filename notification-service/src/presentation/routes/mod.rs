@@ -1,2 +1,3 @@
+pub mod exports;
 pub mod health;
 pub mod notification;
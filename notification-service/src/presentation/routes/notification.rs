@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use axum::{
     Router,
-    routing::{delete, get, put},
+    routing::{get, put},
 };
 
 use crate::presentation::{
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
-use crate::infrastructure::database::factory::RepoProvider;
+use crate::{domain::mailer::DynMailer, infrastructure::database::factory::RepoProvider};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationEvent {
@@ -15,12 +15,13 @@ pub struct NotificationEvent {
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub repos: RepoProvider,
+    pub mailer: DynMailer,
     pub tx: broadcast::Sender<NotificationEvent>,
 }
 
 impl AppState {
-    pub fn new(repos: RepoProvider) -> Self {
+    pub fn new(repos: RepoProvider, mailer: DynMailer) -> Self {
         let (tx, _) = broadcast::channel(1024);
-        Self { repos, tx }
+        Self { repos, mailer, tx }
     }
 }
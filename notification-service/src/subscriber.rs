@@ -1,16 +1,157 @@
 use std::sync::Arc;
 
-use common::{outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
 use uuid::Uuid;
 
 use crate::{
-    domain::entities::notification::Notification,
+    domain::{
+        entities::{notification::Notification, notification_preference::ChannelOptIns},
+        mailer::Email,
+    },
     presentation::state::{AppState, NotificationEvent},
 };
 
-async fn process_event(state: &Arc<AppState>, event: OutBoxEvent) {
+/// Drops the notifications of a user being erased, and acknowledges the
+/// erasure once done. A plain deletion can still be undone by restoring the
+/// user, so it leaves them alone. Failures are returned so the event is
+/// redelivered; otherwise the erasure could only time out.
+async fn forget_user(state: &Arc<AppState>, event: &OutBoxEvent) -> Result<()> {
+    let ids = ["id", "erasure_id"].map(|field| {
+        event.payload[field]
+            .as_str()
+            .and_then(|s| s.parse::<Uuid>().ok())
+    });
+    let [Some(user_id), Some(erasure_id)] = ids else {
+        return Ok(());
+    };
+
+    let count = state
+        .repos
+        .notifications
+        .delete_notifications_for_user(user_id, erasure_id)
+        .await?;
+    tracing::info!("Deleted {} notifications of user: {}", count, user_id);
+    Ok(())
+}
+
+/// Sends the user's notifications for a data export they asked for. Failures
+/// are returned so the event is redelivered; otherwise the export could only
+/// time out.
+async fn export_user(state: &Arc<AppState>, event: &OutBoxEvent) -> Result<()> {
+    let ids = ["user_id", "export_id"].map(|field| {
+        event.payload[field]
+            .as_str()
+            .and_then(|s| s.parse::<Uuid>().ok())
+    });
+    let [Some(user_id), Some(export_id)] = ids else {
+        return Ok(());
+    };
+
+    let notifications = state
+        .repos
+        .notifications
+        .export_notifications_for_user(user_id, export_id)
+        .await?;
+    tracing::info!(
+        "Exported {} notifications of user: {}",
+        notifications.len(),
+        user_id
+    );
+    Ok(())
+}
+
+/// Mails the confirmation token of an email change to the new address, the
+/// only place it may go: confirming proves the user owns that address.
+/// Failures are returned so the event is redelivered; otherwise the user
+/// could never confirm.
+async fn send_email_change_token(state: &Arc<AppState>, event: &OutBoxEvent) -> Result<()> {
+    let (Some(new_email), Some(token)) = (
+        event.payload["new_email"].as_str(),
+        event.payload["token"].as_str(),
+    ) else {
+        tracing::warn!("Ignoring malformed email change event: {}", event.id);
+        return Ok(());
+    };
+    let username = event.payload["username"].as_str().unwrap_or("there");
+    let expires_at = event.payload["expires_at"].as_str().unwrap_or("soon");
+
+    state
+        .mailer
+        .send(Email {
+            to: new_email.to_string(),
+            subject: "Confirm Your New Email Address".to_string(),
+            body: format!(
+                "Hello {},\n\nUse this token to confirm {} as the email address of your \
+                 account: {}\n\nIt expires at {}. If you did not ask for this change, \
+                 ignore this email.",
+                username, new_email, token, expires_at
+            ),
+        })
+        .await?;
+    tracing::info!("Sent email change token of user: {}", event.aggregate_id);
+    Ok(())
+}
+
+/// Keeps the user's channel opt-ins, so notifications they turned off are
+/// dropped here without asking the users service.
+async fn remember_preferences(state: &Arc<AppState>, event: &OutBoxEvent) {
+    let user_id = event.payload["id"]
+        .as_str()
+        .and_then(|s| s.parse::<Uuid>().ok());
+    let version = event.payload["version"]
+        .as_i64()
+        .and_then(|v| i32::try_from(v).ok());
+    let channels = serde_json::from_value::<ChannelOptIns>(
+        event.payload["preferences"]["notifications"].clone(),
+    );
+    let (Some(user_id), Some(version), Ok(channels)) = (user_id, version, channels) else {
+        tracing::warn!("Ignoring malformed preferences event: {}", event.id);
+        return;
+    };
+
+    match state
+        .repos
+        .notifications
+        .store_preferences(user_id, channels, version)
+        .await
+    {
+        Ok(true) => tracing::info!("Stored notification preferences of user: {}", user_id),
+        Ok(false) => tracing::info!(
+            "Skipped outdated notification preferences of user: {}",
+            user_id
+        ),
+        Err(e) => {
+            tracing::error!(
+                "Failed to store notification preferences of user {}: {}",
+                user_id,
+                e
+            );
+        }
+    }
+}
+
+/// Handles one event from the stream. Erasure, export and email failures are
+/// returned so the event is retried; notification errors are only logged.
+pub async fn process_event(state: &Arc<AppState>, event: OutBoxEvent) -> Result<()> {
+    match event.event_type.as_str() {
+        "user_deleted" => return forget_user(state, &event).await,
+        "user_export_requested" => return export_user(state, &event).await,
+        "user_email_change_requested" => return send_email_change_token(state, &event).await,
+        "user_preferences_updated" => {
+            remember_preferences(state, &event).await;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    notify(state, event).await;
+    Ok(())
+}
+
+/// Stores and pushes the notification an event calls for, if any.
+async fn notify(state: &Arc<AppState>, event: OutBoxEvent) {
     let (user_id, kind, title, message) = match event.event_type.as_str() {
-        "post_created" => {
+        "post_published" => {
             let author_id = event.payload["author_id"]
                 .as_str()
                 .and_then(|s| s.parse().ok());
@@ -22,17 +163,144 @@ async fn process_event(state: &Arc<AppState>, event: OutBoxEvent) {
             match author_id {
                 Some(uid) => (
                     uid,
-                    "post_created".to_string(),
+                    "post_published".to_string(),
                     "New Post Published".to_string(),
                     format!("Your post '{}' has been published!", post_title),
                 ),
                 None => return,
             }
         }
-        "user_registered" => {
+        "comment_created" => {
+            let post_author_id: Option<Uuid> = event.payload["post_author_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+            let commenter_id: Option<Uuid> = event.payload["author_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+            let post_title = event.payload["post_title"]
+                .as_str()
+                .map(|s| s.to_string())
+                .unwrap_or("Untitled".to_string());
+
+            match post_author_id {
+                // Authors are not notified about their own comments
+                Some(uid) if Some(uid) != commenter_id => (
+                    uid,
+                    "comment_created".to_string(),
+                    "New Comment".to_string(),
+                    format!("Someone commented on your post '{}'", post_title),
+                ),
+                _ => return,
+            }
+        }
+        "post_liked" => {
+            let post_author_id: Option<Uuid> = event.payload["post_author_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+            let user_id: Option<Uuid> = event.payload["user_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+            let post_title = event.payload["post_title"]
+                .as_str()
+                .map(|s| s.to_string())
+                .unwrap_or("Untitled".to_string());
+            let reaction = event.payload["reaction"].as_str().unwrap_or("like");
+
+            match post_author_id {
+                // Authors are not notified about their own reactions
+                Some(uid) if Some(uid) != user_id => (
+                    uid,
+                    "post_liked".to_string(),
+                    "New Reaction".to_string(),
+                    match reaction {
+                        "like" => format!("Someone liked your post '{}'", post_title),
+                        _ => format!(
+                            "Someone reacted with '{}' to your post '{}'",
+                            reaction, post_title
+                        ),
+                    },
+                ),
+                _ => return,
+            }
+        }
+        "user_followed" => {
+            let followee_id = event.payload["followee_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+            let follower = event.payload["follower_username"]
+                .as_str()
+                .unwrap_or("Someone");
+
+            match followee_id {
+                Some(uid) => (
+                    uid,
+                    "user_followed".to_string(),
+                    "New Follower".to_string(),
+                    format!("{} started following you", follower),
+                ),
+                None => return,
+            }
+        }
+        "user_updated" => {
+            let user_id = event.payload["id"].as_str().and_then(|s| s.parse().ok());
+            let changes = &event.payload["changes"];
+
+            // Account details changing is worth a heads-up; profile edits are not
+            let change = if changes.get("email").is_some() {
+                Some((
+                    "Email Changed",
+                    "The email address of your account was changed".to_string(),
+                ))
+            } else {
+                changes["username"]["after"].as_str().map(|username| {
+                    (
+                        "Username Changed",
+                        format!("Your username is now '{}'", username),
+                    )
+                })
+            };
+
+            match (user_id, change) {
+                (Some(uid), Some((title, message))) => {
+                    (uid, "user_updated".to_string(), title.to_string(), message)
+                }
+                _ => return,
+            }
+        }
+        "user_export_ready" => {
             let user_id = event.payload["user_id"]
                 .as_str()
                 .and_then(|s| s.parse().ok());
+            let download_path = event.payload["download_path"].as_str().unwrap_or_default();
+
+            match user_id {
+                Some(uid) => (
+                    uid,
+                    "user_export_ready".to_string(),
+                    "Your Data Export Is Ready".to_string(),
+                    format!("Your data export can be downloaded at {}", download_path),
+                ),
+                None => return,
+            }
+        }
+        "user_export_failed" => {
+            let user_id = event.payload["user_id"]
+                .as_str()
+                .and_then(|s| s.parse().ok());
+
+            match user_id {
+                Some(uid) => (
+                    uid,
+                    "user_export_failed".to_string(),
+                    "Your Data Export Failed".to_string(),
+                    "Your data export could not be completed. Please request a new one."
+                        .to_string(),
+                ),
+                None => return,
+            }
+        }
+        "user_registered" => {
+            let user_id = event.payload["id"].as_str().and_then(|s| s.parse().ok());
             let username = event.payload["username"]
                 .as_str()
                 .map(|s| s.to_string())
@@ -54,6 +322,16 @@ async fn process_event(state: &Arc<AppState>, event: OutBoxEvent) {
         _ => return,
     };
 
+    match state.repos.notifications.wants_in_app(user_id, &kind).await {
+        Ok(true) => {}
+        Ok(false) => {
+            tracing::info!("User {} opted out of {} notifications", user_id, kind);
+            return;
+        }
+        // Better an unwanted notification than a lost one
+        Err(e) => tracing::error!("Failed to load preferences of user {}: {}", user_id, e),
+    }
+
     let notification = Notification {
         id: Uuid::new_v4(),
         user_id,
@@ -95,11 +373,25 @@ pub fn spawn_subscriber(state: Arc<AppState>, subscriber: PubSubSubscriber) {
                 let state = state.clone();
                 async move {
                     let data = String::from_utf8_lossy(&msg.message.data);
-                    match serde_json::from_str::<OutBoxEvent>(&data) {
-                        Ok(event) => process_event(&state, event).await,
-                        Err(e) => tracing::error!("Failed to parse event: {}", e),
+                    let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                        Ok(event) => event,
+                        Err(e) => {
+                            tracing::error!("Failed to parse event: {}", e);
+                            let _ = msg.ack().await;
+                            return;
+                        }
+                    };
+
+                    let event_id = event.id;
+                    match process_event(&state, event).await {
+                        Ok(()) => {
+                            let _ = msg.ack().await;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to process event {}: {:?}", event_id, e);
+                            let _ = msg.nack().await;
+                        }
                     }
-                    let _ = msg.ack().await;
                 }
             })
             .await
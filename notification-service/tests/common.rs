@@ -0,0 +1,254 @@
+#![allow(dead_code)]
+
+use std::sync::{
+    Arc, LazyLock, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use common::{error::Result, outbox::OutBoxEvent, telemetry};
+use notification_service::{
+    domain::mailer::{Email, Mailer},
+    infrastructure::{
+        database::{RepoProvider, bootstrap::bootstrap_db, build_db_url},
+        http::create_router,
+    },
+    presentation::state::AppState,
+    subscriber::process_event,
+};
+use tracing::info;
+use uuid::Uuid;
+
+static TRACING: LazyLock<()> = LazyLock::new(|| {
+    let subscriber = telemetry::get_subscriber("test".into(), "info".into(), std::io::stdout);
+    telemetry::init_subscriber(subscriber);
+});
+
+/// Keeps the emails it is handed instead of delivering them.
+#[derive(Debug, Default)]
+pub struct RecordingMailer {
+    sent: Mutex<Vec<Email>>,
+    failing: AtomicBool,
+}
+
+impl RecordingMailer {
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Makes every following send fail, as if the relay were down.
+    pub fn fail(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("Mail relay unavailable").into());
+        }
+        self.sent.lock().unwrap().push(email);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestApp {
+    pub address: String,
+    pub state: Arc<AppState>,
+    pub mailer: Arc<RecordingMailer>,
+    pub db_name: String,
+    pub db_config: common::config::DatabaseSettings,
+    pub api_client: reqwest::Client,
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let db_config = self.db_config.clone();
+        let db_name = self.db_name.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                delete_database(&db_config, &db_name).await;
+            });
+        })
+        .join()
+        .expect("failed to join cleanup thread");
+    }
+}
+
+pub async fn spawn_app() -> TestApp {
+    LazyLock::force(&TRACING);
+
+    // Make integration tests use `config/test.yaml`
+    unsafe {
+        std::env::set_var("APP_ENVIRONMENT", "test");
+    }
+
+    let mut config =
+        common::config::get_configuration::<common::config::Settings>("config").unwrap();
+    // Randomize database name
+    config.database.database_name = Uuid::new_v4().to_string();
+
+    configure_database(&config.database).await;
+
+    let listener = tokio::net::TcpListener::bind(format!("{}:0", config.application.host))
+        .await
+        .unwrap();
+
+    info!("Bound to port: {}", listener.local_addr().unwrap().port());
+
+    let addr = listener.local_addr().unwrap();
+
+    let conn = bootstrap_db(&config.database).await.unwrap();
+    let repo_provider = RepoProvider::from_connection(conn).await.unwrap();
+    let mailer = Arc::new(RecordingMailer::default());
+    let state = Arc::new(AppState::new(repo_provider, mailer.clone()));
+    let router = create_router(state.clone());
+
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    TestApp {
+        address: addr.to_string(),
+        state,
+        mailer,
+        db_name: config.database.database_name.clone(),
+        db_config: config.database.clone(),
+        api_client: reqwest::Client::new(),
+    }
+}
+
+async fn configure_database(config: &common::config::DatabaseSettings) {
+    use sea_orm::{ConnectionTrait, Database};
+
+    let mut maintenance_config = config.clone();
+    maintenance_config.database_name = "postgres".to_string();
+
+    let db_url = build_db_url(&maintenance_config)
+        .await
+        .context(format!(
+            "Failed to build database url for maintenance_config: {:?}",
+            maintenance_config
+        ))
+        .unwrap();
+
+    let db = Database::connect(&db_url)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to connect to Postgres for tests at db_url: {db_url}\n\
+\n\
+Hint: start the test database with:\n\
+  docker compose --profile test up -d notification-db-test\n"
+            )
+        })
+        .unwrap();
+
+    db.execute_unprepared(&format!("CREATE DATABASE \"{}\";", config.database_name))
+        .await
+        .context(format!("Failed to create database for db_url: {}", &db_url))
+        .unwrap();
+}
+
+async fn delete_database(config: &common::config::DatabaseSettings, db_name: &str) {
+    use sea_orm::{ConnectionTrait, Database};
+
+    let mut maintenance_config = config.clone();
+    maintenance_config.database_name = "postgres".to_string();
+
+    let db_url = build_db_url(&maintenance_config)
+        .await
+        .context(format!(
+            "Failed to build database url for maintenance_config: {:?}",
+            maintenance_config
+        ))
+        .unwrap();
+
+    let db = Database::connect(&db_url)
+        .await
+        .context(format!(
+            "Failed to connect to database for db_url: {}",
+            &db_url
+        ))
+        .unwrap();
+
+    let _ = db
+        .execute_unprepared(&format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}';",
+            db_name
+        ))
+        .await
+        .context(format!(
+            "Failed to terminate connections for db_name: {}",
+            db_name
+        ))
+        .unwrap();
+
+    db.execute_unprepared(&format!("DROP DATABASE \"{}\";", db_name))
+        .await
+        .context(format!("Failed to drop database for db_name: {}", db_name))
+        .unwrap();
+}
+
+/// An event as it arrives from the stream.
+pub fn event(event_type: &str, aggregate_id: Uuid, payload: serde_json::Value) -> OutBoxEvent {
+    OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id,
+        event_type: event_type.to_string(),
+        payload,
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    }
+}
+
+impl TestApp {
+    /// Feeds an event through the subscriber, as if it came from Pub/Sub.
+    pub async fn process(&self, event: OutBoxEvent) {
+        process_event(&self.state, event).await.unwrap();
+    }
+
+    pub async fn post_notification(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/notifications", self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_user_notifications(&self, user_id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/notifications/user/{}",
+                self.address, user_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn outbox_payloads(&self, event_type: &str) -> Vec<serde_json::Value> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        common::outbox::Entity::find()
+            .filter(common::outbox::Column::EventType.eq(event_type))
+            .order_by_asc(common::outbox::Column::CreatedAt)
+            .all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.payload)
+            .collect()
+    }
+}
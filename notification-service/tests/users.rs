@@ -0,0 +1,99 @@
+mod common;
+
+use common::TestApp;
+use notification_service::subscriber::process_event;
+use uuid::Uuid;
+
+async fn notify(app: &TestApp, user_id: Uuid) {
+    let response = app
+        .post_notification(&serde_json::json!({
+            "user_id": user_id,
+            "kind": "post_liked",
+            "title": "Liked",
+            "message": "Someone liked your post",
+        }))
+        .await;
+    assert_eq!(response.status(), 200);
+}
+
+async fn notification_count(app: &TestApp, user_id: Uuid) -> u64 {
+    let response = app.list_user_notifications(user_id).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["total"].as_u64().unwrap()
+}
+
+#[tokio::test]
+async fn soft_deleting_a_user_keeps_their_notifications() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    notify(&app, user).await;
+
+    app.process(common::event(
+        "user_deleted",
+        user,
+        serde_json::json!({ "id": user, "erasure_id": null }),
+    ))
+    .await;
+
+    assert_eq!(notification_count(&app, user).await, 1);
+    assert!(
+        app.outbox_payloads("user_erasure_step_completed")
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn erasing_a_user_deletes_their_notifications() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    notify(&app, user).await;
+    notify(&app, other).await;
+    let erasure_id = Uuid::new_v4();
+
+    app.process(common::event(
+        "user_deleted",
+        user,
+        serde_json::json!({ "id": user, "erasure_id": erasure_id }),
+    ))
+    .await;
+
+    assert_eq!(notification_count(&app, user).await, 0);
+    assert_eq!(notification_count(&app, other).await, 1);
+    let acks = app.outbox_payloads("user_erasure_step_completed").await;
+    assert_eq!(acks.len(), 1);
+    assert_eq!(acks[0]["erasure_id"], erasure_id.to_string());
+    assert_eq!(acks[0]["notifications_deleted"], 1);
+}
+
+#[tokio::test]
+async fn email_change_tokens_are_mailed_to_the_new_address() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    let change = common::event(
+        "user_email_change_requested",
+        user,
+        serde_json::json!({
+            "id": user,
+            "username": "alice",
+            "new_email": "alice@new.example",
+            "token": "change-token",
+            "expires_at": "2030-01-01T00:00:00Z",
+        }),
+    );
+
+    app.mailer.fail(true);
+    assert!(process_event(&app.state, change.clone()).await.is_err());
+    assert!(app.mailer.sent().is_empty());
+
+    app.mailer.fail(false);
+    app.process(change).await;
+
+    let sent = app.mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "alice@new.example");
+    assert!(sent[0].body.contains("change-token"));
+    assert_eq!(notification_count(&app, user).await, 0);
+}
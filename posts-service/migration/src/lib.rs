@@ -2,6 +2,21 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
 mod m20220102_000002_create_outbox;
+mod m20220103_000003_add_post_status;
+mod m20220104_000004_create_post_revisions;
+mod m20220105_000005_add_post_version;
+mod m20220106_000006_create_tags;
+mod m20220107_000007_add_post_slugs;
+mod m20220108_000008_add_post_search;
+mod m20220109_000009_add_post_content_format;
+mod m20220110_000010_create_comments;
+mod m20220111_000011_create_post_reactions;
+mod m20220112_000012_add_post_deleted_at;
+mod m20220113_000013_create_feed;
+mod m20220114_000014_create_author_statuses;
+mod m20220115_000015_add_outbox_dead_letters;
+mod m20220116_000016_create_author_exports;
+mod m20220117_000017_add_post_excerpts;
 
 pub struct Migrator;
 
@@ -11,6 +26,21 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20220101_000001_create_table::Migration),
             Box::new(m20220102_000002_create_outbox::Migration),
+            Box::new(m20220103_000003_add_post_status::Migration),
+            Box::new(m20220104_000004_create_post_revisions::Migration),
+            Box::new(m20220105_000005_add_post_version::Migration),
+            Box::new(m20220106_000006_create_tags::Migration),
+            Box::new(m20220107_000007_add_post_slugs::Migration),
+            Box::new(m20220108_000008_add_post_search::Migration),
+            Box::new(m20220109_000009_add_post_content_format::Migration),
+            Box::new(m20220110_000010_create_comments::Migration),
+            Box::new(m20220111_000011_create_post_reactions::Migration),
+            Box::new(m20220112_000012_add_post_deleted_at::Migration),
+            Box::new(m20220113_000013_create_feed::Migration),
+            Box::new(m20220114_000014_create_author_statuses::Migration),
+            Box::new(m20220115_000015_add_outbox_dead_letters::Migration),
+            Box::new(m20220116_000016_create_author_exports::Migration),
+            Box::new(m20220117_000017_add_post_excerpts::Migration),
         ]
     }
 }
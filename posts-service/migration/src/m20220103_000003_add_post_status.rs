@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(
+                        ColumnDef::new(Post::Status)
+                            .string()
+                            .not_null()
+                            .default("draft"),
+                    )
+                    .add_column(
+                        ColumnDef::new(Post::PublishAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Post::PublishedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Posts created before the lifecycle existed were already public.
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Post::Table)
+                    .value(Post::Status, "published")
+                    .value(Post::PublishedAt, Expr::col(Post::CreatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index for the scheduler looking up due posts
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_status_publish_at")
+                    .table(Post::Table)
+                    .col(Post::Status)
+                    .col(Post::PublishAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_posts_status_publish_at")
+                    .table(Post::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::Status)
+                    .drop_column(Post::PublishAt)
+                    .drop_column(Post::PublishedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Status,
+    PublishAt,
+    PublishedAt,
+    CreatedAt,
+}
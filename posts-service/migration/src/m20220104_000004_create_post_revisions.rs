@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostRevision::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostRevision::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostRevision::Revision).integer().not_null())
+                    .col(ColumnDef::new(PostRevision::Title).string().not_null())
+                    .col(ColumnDef::new(PostRevision::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(PostRevision::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_revisions_post_id")
+                            .from(PostRevision::Table, PostRevision::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_revisions_post_id_revision")
+                    .table(PostRevision::Table)
+                    .col(PostRevision::PostId)
+                    .col(PostRevision::Revision)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing posts start their history from their current content
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO post_revisions (id, post_id, revision, title, content, created_at) \
+                 SELECT gen_random_uuid(), id, 1, title, content, updated_at FROM posts",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostRevision::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostRevision {
+    #[sea_orm(iden = "post_revisions")]
+    Table,
+    Id,
+    PostId,
+    Revision,
+    Title,
+    Content,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Tag::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Tag::Name).text().not_null().unique_key())
+                    .col(
+                        ColumnDef::new(Tag::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostTag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostTag::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostTag::TagId).uuid().not_null())
+                    .primary_key(Index::create().col(PostTag::PostId).col(PostTag::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_tags_post_id")
+                            .from(PostTag::Table, PostTag::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_tags_tag_id")
+                            .from(PostTag::Table, PostTag::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The primary key covers lookups by post, this one covers lookups by tag
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_tags_tag_id")
+                    .table(PostTag::Table)
+                    .col(PostTag::TagId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostTag::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Tag::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    #[sea_orm(iden = "tags")]
+    Table,
+    Id,
+    Name,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostTag {
+    #[sea_orm(iden = "post_tags")]
+    Table,
+    PostId,
+    TagId,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(ColumnDef::new(Post::Slug).text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostSlug::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostSlug::Slug)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostSlug::PostId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(PostSlug::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_slugs_post_id")
+                            .from(PostSlug::Table, PostSlug::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_slugs_post_id")
+                    .table(PostSlug::Table)
+                    .col(PostSlug::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        backfill_slugs(manager).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .modify_column(ColumnDef::new(Post::Slug).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_slug")
+                    .table(Post::Table)
+                    .col(Post::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostSlug::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::Slug)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Gives every existing post a slug, oldest posts first so they keep the
+/// unsuffixed form. Mirrors `slugify` in the service as of this migration.
+async fn backfill_slugs(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let rows = conn
+        .query_all_raw(Statement::from_string(
+            backend,
+            "SELECT id::text AS id, title FROM posts ORDER BY created_at, id",
+        ))
+        .await?;
+
+    let mut taken = HashSet::new();
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let title: String = row.try_get("", "title")?;
+
+        let base = slugify(&title);
+        let slug = (1..)
+            .map(|n| {
+                if n == 1 {
+                    base.clone()
+                } else {
+                    format!("{}-{}", base, n)
+                }
+            })
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap();
+        taken.insert(slug.clone());
+
+        conn.execute_raw(Statement::from_sql_and_values(
+            backend,
+            "UPDATE posts SET slug = $1 WHERE id = $2::uuid",
+            [slug.into(), id.into()],
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in deunicode::deunicode(title).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > 80 {
+        slug.truncate(80);
+        if let Some(idx) = slug.rfind('-') {
+            slug.truncate(idx);
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "post".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+    Slug,
+}
+
+#[derive(DeriveIden)]
+enum PostSlug {
+    #[sea_orm(iden = "post_slugs")]
+    Table,
+    Slug,
+    PostId,
+    CreatedAt,
+}
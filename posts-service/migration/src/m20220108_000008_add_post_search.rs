@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(ColumnDef::new(Post::SearchVector).custom(TsVector).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+
+        // Titles weigh more than content when ranking
+        conn.execute_unprepared(
+            r#"
+            CREATE OR REPLACE FUNCTION posts_search_vector_update() RETURNS trigger AS $$
+            BEGIN
+                NEW.search_vector :=
+                    setweight(to_tsvector('english', coalesce(NEW.title, '')), 'A') ||
+                    setweight(to_tsvector('english', coalesce(NEW.content, '')), 'B');
+                RETURN NEW;
+            END
+            $$ LANGUAGE plpgsql;
+
+            CREATE TRIGGER posts_search_vector_trigger
+                BEFORE INSERT OR UPDATE OF title, content ON posts
+                FOR EACH ROW EXECUTE FUNCTION posts_search_vector_update();
+
+            UPDATE posts SET search_vector =
+                setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+                setweight(to_tsvector('english', coalesce(content, '')), 'B');
+            "#,
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_search_vector")
+                    .table(Post::Table)
+                    .col(Post::SearchVector)
+                    .index_type(IndexType::Custom(SeaRc::new(Gin)))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS posts_search_vector_trigger ON posts;
+                DROP FUNCTION IF EXISTS posts_search_vector_update();
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::SearchVector)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    SearchVector,
+}
+
+#[derive(DeriveIden)]
+#[sea_orm(iden = "tsvector")]
+struct TsVector;
+
+#[derive(DeriveIden)]
+#[sea_orm(iden = "gin")]
+struct Gin;
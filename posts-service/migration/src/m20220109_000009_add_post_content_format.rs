@@ -0,0 +1,127 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing content was stored and served raw, so it is treated as plain text.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(
+                        ColumnDef::new(Post::ContentFormat)
+                            .text()
+                            .not_null()
+                            .default("plain"),
+                    )
+                    .add_column(ColumnDef::new(Post::ContentHtml).text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .modify_column(
+                        ColumnDef::new(Post::ContentFormat)
+                            .text()
+                            .not_null()
+                            .default("markdown"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        backfill_content_html(manager).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .modify_column(ColumnDef::new(Post::ContentHtml).text().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::ContentFormat)
+                    .drop_column(Post::ContentHtml)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Renders the existing plain-text posts. Mirrors plain-text rendering in the
+/// service as of this migration.
+async fn backfill_content_html(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let rows = conn
+        .query_all_raw(Statement::from_string(
+            backend,
+            "SELECT id::text AS id, content FROM posts",
+        ))
+        .await?;
+
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let content: String = row.try_get("", "content")?;
+
+        conn.execute_raw(Statement::from_sql_and_values(
+            backend,
+            "UPDATE posts SET content_html = $1 WHERE id = $2::uuid",
+            [render_plain(&content).into(), id.into()],
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn render_plain(content: &str) -> String {
+    content
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let lines: Vec<String> = paragraph.lines().map(escape).collect();
+            format!("<p>{}</p>\n", lines.join("<br>\n"))
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    ContentFormat,
+    ContentHtml,
+}
@@ -0,0 +1,128 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comment::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Comment::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Comment::PostId).uuid().not_null())
+                    .col(ColumnDef::new(Comment::ParentId).uuid().null())
+                    .col(ColumnDef::new(Comment::RootId).uuid().not_null())
+                    .col(ColumnDef::new(Comment::Depth).integer().not_null())
+                    .col(ColumnDef::new(Comment::AuthorId).uuid().not_null())
+                    .col(ColumnDef::new(Comment::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(Comment::Status)
+                            .text()
+                            .not_null()
+                            .default("approved"),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::EditedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::DeletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_post_id")
+                            .from(Comment::Table, Comment::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_parent_id")
+                            .from(Comment::Table, Comment::ParentId)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Threads are paged by their root comment, oldest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_post_id_created_at")
+                    .table(Comment::Table)
+                    .col(Comment::PostId)
+                    .col(Comment::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_root_id")
+                    .table(Comment::Table)
+                    .col(Comment::RootId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_parent_id")
+                    .table(Comment::Table)
+                    .col(Comment::ParentId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Comment::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Comment {
+    #[sea_orm(iden = "comments")]
+    Table,
+    Id,
+    PostId,
+    ParentId,
+    RootId,
+    Depth,
+    AuthorId,
+    Content,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+    EditedAt,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostReaction::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostReaction::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostReaction::UserId).uuid().not_null())
+                    .col(ColumnDef::new(PostReaction::Kind).text().not_null())
+                    .col(
+                        ColumnDef::new(PostReaction::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(PostReaction::PostId)
+                            .col(PostReaction::UserId)
+                            .col(PostReaction::Kind),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_reactions_post_id")
+                            .from(PostReaction::Table, PostReaction::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Counts are derived from post_reactions and written back periodically,
+        // so reads never have to aggregate the reactions table.
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostReactionCount::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostReactionCount::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostReactionCount::Kind).text().not_null())
+                    .col(
+                        ColumnDef::new(PostReactionCount::Count)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(PostReactionCount::PostId)
+                            .col(PostReactionCount::Kind),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_reaction_counts_post_id")
+                            .from(PostReactionCount::Table, PostReactionCount::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostReactionCount::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PostReaction::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostReaction {
+    #[sea_orm(iden = "post_reactions")]
+    Table,
+    PostId,
+    UserId,
+    Kind,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostReactionCount {
+    #[sea_orm(iden = "post_reaction_counts")]
+    Table,
+    PostId,
+    Kind,
+    Count,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+}
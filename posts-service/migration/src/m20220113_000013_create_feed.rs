@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Replica of the users-service follow graph, kept in step from its events
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorSubscription::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuthorSubscription::SubscriberId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AuthorSubscription::AuthorId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AuthorSubscription::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(AuthorSubscription::SubscriberId)
+                            .col(AuthorSubscription::AuthorId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_author_subscriptions_author_id")
+                    .table(AuthorSubscription::Table)
+                    .col(AuthorSubscription::AuthorId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Durable copy of every timeline; Redis, when configured, serves the hot part
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimelineEntry::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TimelineEntry::UserId).uuid().not_null())
+                    .col(ColumnDef::new(TimelineEntry::PostId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(TimelineEntry::PublishedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TimelineEntry::UserId)
+                            .col(TimelineEntry::PostId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_post_id")
+                            .from(TimelineEntry::Table, TimelineEntry::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_timeline_entries_user_id_published_at")
+                    .table(TimelineEntry::Table)
+                    .col(TimelineEntry::UserId)
+                    .col((TimelineEntry::PublishedAt, IndexOrder::Desc))
+                    .col((TimelineEntry::PostId, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TimelineEntry::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AuthorSubscription::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuthorSubscription {
+    #[sea_orm(iden = "author_subscriptions")]
+    Table,
+    SubscriberId,
+    AuthorId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TimelineEntry {
+    #[sea_orm(iden = "timeline_entries")]
+    Table,
+    UserId,
+    PostId,
+    PublishedAt,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Id,
+}
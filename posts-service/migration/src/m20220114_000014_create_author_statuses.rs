@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorStatus::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuthorStatus::AuthorId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuthorStatus::Status).text().not_null())
+                    .col(ColumnDef::new(AuthorStatus::Version).integer().not_null())
+                    .col(
+                        ColumnDef::new(AuthorStatus::ChangedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Reads only ever look for the authors that are not active
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_author_statuses_restricted ON author_statuses (author_id) \
+                 WHERE status <> 'active'",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuthorStatus::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuthorStatus {
+    #[sea_orm(iden = "author_statuses")]
+    Table,
+    AuthorId,
+    Status,
+    Version,
+    ChangedAt,
+}
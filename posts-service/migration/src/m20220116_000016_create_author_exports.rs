@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorExport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuthorExport::ExportId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuthorExport::AuthorId).uuid().not_null())
+                    .col(ColumnDef::new(AuthorExport::Data).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(AuthorExport::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Erasing an author removes their exports too
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_author_exports_author_id")
+                    .table(AuthorExport::Table)
+                    .col(AuthorExport::AuthorId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuthorExport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuthorExport {
+    #[sea_orm(iden = "author_exports")]
+    Table,
+    ExportId,
+    AuthorId,
+    Data,
+    CreatedAt,
+}
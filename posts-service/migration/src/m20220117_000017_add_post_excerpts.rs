@@ -0,0 +1,145 @@
+use pulldown_cmark::{Event, Options, Parser, TagEnd};
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, Statement},
+};
+
+const EXCERPT_LENGTH: usize = 200;
+const WORDS_PER_MINUTE: usize = 200;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .add_column(ColumnDef::new(Post::Excerpt).text().null())
+                    .add_column(ColumnDef::new(Post::ReadingTimeMinutes).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        backfill_excerpts(manager).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .modify_column(ColumnDef::new(Post::Excerpt).text().not_null())
+                    .modify_column(
+                        ColumnDef::new(Post::ReadingTimeMinutes)
+                            .integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Post::Table)
+                    .drop_column(Post::Excerpt)
+                    .drop_column(Post::ReadingTimeMinutes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Derives the excerpt and reading time of existing posts. Mirrors the
+/// service's plain-text extraction as of this migration.
+async fn backfill_excerpts(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let backend = manager.get_database_backend();
+
+    let rows = conn
+        .query_all_raw(Statement::from_string(
+            backend,
+            "SELECT id::text AS id, content, content_format FROM posts",
+        ))
+        .await?;
+
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let content: String = row.try_get("", "content")?;
+        let format: String = row.try_get("", "content_format")?;
+
+        let text = plain_text(&format, &content);
+        let reading_time = text
+            .split_whitespace()
+            .count()
+            .div_ceil(WORDS_PER_MINUTE)
+            .max(1);
+        conn.execute_raw(Statement::from_sql_and_values(
+            backend,
+            "UPDATE posts SET excerpt = $1, reading_time_minutes = $2 WHERE id = $3::uuid",
+            [
+                excerpt(&text).into(),
+                (reading_time as i32).into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn plain_text(format: &str, content: &str) -> String {
+    let text = match format {
+        "markdown" => markdown_text(content),
+        "html" => {
+            let text = ammonia::Builder::empty().clean(content).to_string();
+            html_escape::decode_html_entities(&text).into_owned()
+        }
+        _ => content.to_string(),
+    };
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn excerpt(text: &str) -> String {
+    if text.chars().count() <= EXCERPT_LENGTH {
+        return text.to_string();
+    }
+
+    let mut excerpt: String = text.chars().take(EXCERPT_LENGTH).collect();
+    if let Some(idx) = excerpt.rfind(' ') {
+        excerpt.truncate(idx);
+    }
+    excerpt.push('…');
+    excerpt
+}
+
+fn markdown_text(content: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let mut text = String::with_capacity(content.len());
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::Item) => text.push(' '),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::TableCell,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    #[sea_orm(iden = "posts")]
+    Table,
+    Excerpt,
+    ReadingTimeMinutes,
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{Comment, CommentStatus};
+
+/// Maximum comment length in characters.
+pub const MAX_COMMENT_LENGTH: u64 = 10_000;
+/// Replies nested deeper than this are rejected; top-level comments have depth 0.
+pub const MAX_COMMENT_DEPTH: i32 = 8;
+
+/// A comment with its visible replies, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentThread {
+    pub comment: Comment,
+    pub replies: Vec<CommentThread>,
+}
+
+impl Comment {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+/// Assembles threads for public display from root comments and the rest of
+/// their threads, which must be ordered oldest first.
+///
+/// Comments that are not approved hide their whole subtree, and deleted
+/// comments are only kept as placeholders while they still have visible replies.
+pub fn build_threads(roots: Vec<Comment>, descendants: Vec<Comment>) -> Vec<CommentThread> {
+    let mut children: HashMap<Uuid, Vec<Comment>> = HashMap::new();
+    for comment in descendants {
+        if let Some(parent_id) = comment.parent_id {
+            children.entry(parent_id).or_default().push(comment);
+        }
+    }
+
+    roots
+        .into_iter()
+        .filter_map(|root| build_thread(root, &mut children))
+        .collect()
+}
+
+fn build_thread(
+    comment: Comment,
+    children: &mut HashMap<Uuid, Vec<Comment>>,
+) -> Option<CommentThread> {
+    if comment.status != CommentStatus::Approved {
+        return None;
+    }
+
+    let replies: Vec<CommentThread> = children
+        .remove(&comment.id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|reply| build_thread(reply, children))
+        .collect();
+
+    if comment.is_deleted() && replies.is_empty() {
+        return None;
+    }
+
+    Some(CommentThread { comment, replies })
+}
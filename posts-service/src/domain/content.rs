@@ -0,0 +1,229 @@
+use std::{collections::HashSet, sync::LazyLock};
+
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+use crate::domain::{ContentFormat, slug};
+
+pub const EXCERPT_LENGTH: usize = 200;
+pub const WORDS_PER_MINUTE: usize = 200;
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Allows heading ids for anchors and `class` on code spans for highlighting;
+/// everything else follows ammonia's defaults.
+static SANITIZER: LazyLock<ammonia::Builder<'static>> = LazyLock::new(|| {
+    let mut builder = ammonia::Builder::default();
+    for heading in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+        builder.add_tag_attributes(heading, ["id"]);
+    }
+    builder
+        .add_tag_attributes("code", ["class"])
+        .add_tag_attributes("span", ["class"]);
+    builder
+});
+
+/// Renders post content to sanitized HTML.
+pub fn render_html(format: ContentFormat, content: &str) -> String {
+    match format {
+        ContentFormat::Markdown => SANITIZER.clean(&render_markdown(content)).to_string(),
+        ContentFormat::Html => SANITIZER.clean(content).to_string(),
+        ContentFormat::Plain => render_plain(content),
+    }
+}
+
+/// The readable text of the content, with markup removed and whitespace collapsed.
+pub fn plain_text(format: ContentFormat, content: &str) -> String {
+    let text = match format {
+        ContentFormat::Markdown => markdown_text(content),
+        ContentFormat::Html => {
+            let text = ammonia::Builder::empty().clean(content).to_string();
+            html_escape::decode_html_entities(&text).into_owned()
+        }
+        ContentFormat::Plain => content.to_string(),
+    };
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The first `EXCERPT_LENGTH` characters of the plain text, cut at a word boundary.
+pub fn excerpt(format: ContentFormat, content: &str) -> String {
+    let text = plain_text(format, content);
+    if text.chars().count() <= EXCERPT_LENGTH {
+        return text;
+    }
+
+    let mut excerpt: String = text.chars().take(EXCERPT_LENGTH).collect();
+    if let Some(idx) = excerpt.rfind(' ') {
+        excerpt.truncate(idx);
+    }
+    excerpt.push('…');
+    excerpt
+}
+
+/// Estimated reading time, rounded up and never below a minute.
+pub fn reading_time_minutes(format: ContentFormat, content: &str) -> i32 {
+    let words = plain_text(format, content).split_whitespace().count();
+    words.div_ceil(WORDS_PER_MINUTE).max(1) as i32
+}
+
+fn render_plain(content: &str) -> String {
+    content
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let lines: Vec<_> = paragraph.lines().map(html_escape::encode_text).collect();
+            format!("<p>{}</p>\n", lines.join("<br>\n"))
+        })
+        .collect()
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES
+}
+
+/// Renders Markdown to (unsanitized) HTML, giving headings slug ids and
+/// highlighting fenced code blocks whose language is known.
+fn render_markdown(content: &str) -> String {
+    let events: Vec<Event> = Parser::new_ext(content, markdown_options()).collect();
+    let mut output = Vec::with_capacity(events.len());
+    let mut anchors = HashSet::new();
+
+    let mut i = 0;
+    while i < events.len() {
+        match &events[i] {
+            Event::Start(Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            }) => {
+                let end = find_end(&events, i);
+                let text = inline_text(&events[i + 1..end]);
+                let id = match id {
+                    Some(id) => id.to_string(),
+                    None => heading_anchor(&text),
+                };
+                let id = unique_anchor(&mut anchors, &id);
+                output.push(Event::Start(Tag::Heading {
+                    level: *level,
+                    id: Some(CowStr::from(id)),
+                    classes: classes.clone(),
+                    attrs: attrs.clone(),
+                }));
+                output.extend_from_slice(&events[i + 1..=end]);
+                i = end + 1;
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                let end = find_end(&events, i);
+                let code = inline_text(&events[i + 1..end]);
+                match highlight(lang, &code) {
+                    Some(html) => output.push(Event::Html(CowStr::from(html))),
+                    None => output.extend_from_slice(&events[i..=end]),
+                }
+                i = end + 1;
+            }
+            event => {
+                output.push(event.clone());
+                i += 1;
+            }
+        }
+    }
+
+    let mut html = String::with_capacity(content.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, output.into_iter());
+    html
+}
+
+/// Index of the event closing the element opened at `start`.
+fn find_end(events: &[Event], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, event) in events.iter().enumerate().skip(start) {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    events.len() - 1
+}
+
+fn inline_text(events: &[Event]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn markdown_text(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    for event in Parser::new_ext(content, markdown_options()) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::Item) => text.push(' '),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::TableCell,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn heading_anchor(text: &str) -> String {
+    let anchor = slug::slugify(text);
+    if text.chars().any(char::is_alphanumeric) {
+        anchor
+    } else {
+        "section".to_string()
+    }
+}
+
+fn unique_anchor(anchors: &mut HashSet<String>, base: &str) -> String {
+    let anchor = (1..)
+        .map(|n| slug::with_suffix(base, n))
+        .find(|candidate| !anchors.contains(candidate))
+        .unwrap();
+    anchors.insert(anchor.clone());
+    anchor
+}
+
+fn highlight(lang: &str, code: &str) -> Option<String> {
+    let token = lang.split([' ', ',']).next().unwrap_or_default();
+    if token.is_empty() {
+        return None;
+    }
+    let syntax = SYNTAX_SET.find_syntax_by_token(token)?;
+
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+    for line in LinesWithEndings::from(code) {
+        generator
+            .parse_html_for_line_which_includes_newline(line)
+            .ok()?;
+    }
+
+    Some(format!(
+        "<pre><code class=\"language-{}\">{}</code></pre>\n",
+        html_escape::encode_double_quoted_attribute(token),
+        generator.finalize()
+    ))
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+/// Account status of a user, as announced by the users service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    #[sea_orm(string_value = "active")]
+    Active,
+    #[sea_orm(string_value = "deactivated")]
+    Deactivated,
+    #[sea_orm(string_value = "suspended")]
+    Suspended,
+    #[sea_orm(string_value = "banned")]
+    Banned,
+}
+
+/// Latest known status of an author. Posts by authors who are not active are
+/// hidden, and their writes rejected, until they are active again.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "author_statuses")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub author_id: Uuid,
+    pub status: AccountStatus,
+    /// The user's version when the status changed; older events are ignored.
+    pub version: i32,
+    pub changed_at: DateTimeWithTimeZone,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
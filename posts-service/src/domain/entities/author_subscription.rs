@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+/// `subscriber_id` gets `author_id`'s posts in their feed.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "author_subscriptions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub subscriber_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub author_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum CommentStatus {
+    #[default]
+    #[sea_orm(string_value = "approved")]
+    Approved,
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "rejected")]
+    Rejected,
+}
+
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "comments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    /// Top-level comment of the thread; a top-level comment is its own root.
+    pub root_id: Uuid,
+    pub depth: i32,
+    pub author_id: Uuid,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub status: CommentStatus,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub edited_at: Option<DateTimeWithTimeZone>,
+    /// Set when a comment with replies is deleted; its content is cleared but it
+    /// stays in place so the replies keep their parent.
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub type Comment = Model;
@@ -1 +1,12 @@
+pub mod author_export;
+pub mod author_status;
+pub mod author_subscription;
+pub mod comment;
 pub mod post;
+pub mod post_reaction;
+pub mod post_reaction_count;
+pub mod post_revision;
+pub mod post_slug;
+pub mod post_tag;
+pub mod tag;
+pub mod timeline_entry;
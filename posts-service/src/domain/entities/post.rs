@@ -1,23 +1,111 @@
+use common::etag::Versioned;
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 use uuid::Uuid;
+use validator::Validate;
 
 use sea_orm::entity::prelude::*;
 
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    #[default]
+    #[sea_orm(string_value = "draft")]
+    Draft,
+    #[sea_orm(string_value = "scheduled")]
+    Scheduled,
+    #[sea_orm(string_value = "published")]
+    Published,
+    #[sea_orm(string_value = "archived")]
+    Archived,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum ContentFormat {
+    #[default]
+    #[sea_orm(string_value = "markdown")]
+    Markdown,
+    #[sea_orm(string_value = "plain")]
+    Plain,
+    #[sea_orm(string_value = "html")]
+    Html,
+}
+
 #[sea_orm::model]
-#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, TypedBuilder)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, TypedBuilder, Validate,
+)]
 #[sea_orm(table_name = "posts")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
+    #[validate(
+        length(min = 1, max = 200, message = "Title must be between 1-200 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
     pub title: String,
+    #[sea_orm(column_type = "Text", unique)]
+    #[serde(default)]
+    #[builder(default)]
+    pub slug: String,
     pub author_id: Uuid,
     #[sea_orm(column_type = "Text")]
+    #[validate(length(min = 1, message = "Content cannot be empty"))]
     pub content: String,
+    #[serde(default)]
+    #[builder(default)]
+    pub content_format: ContentFormat,
+    /// Sanitized rendering of `content`, refreshed by the repository on every write.
+    #[sea_orm(column_type = "Text")]
+    #[serde(default)]
+    #[builder(default)]
+    pub content_html: String,
+    /// Plain-text summary of `content`, refreshed together with `content_html`.
+    #[sea_orm(column_type = "Text")]
+    #[serde(default)]
+    #[builder(default)]
+    pub excerpt: String,
+    #[serde(default)]
+    #[builder(default)]
+    pub reading_time_minutes: i32,
+    #[serde(default)]
+    #[builder(default)]
+    pub status: PostStatus,
+    #[serde(default)]
+    #[builder(default)]
+    pub publish_at: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    #[builder(default)]
+    pub published_at: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    #[builder(default = 1)]
+    pub version: i32,
+    /// Tag names, kept in `post_tags` and loaded by the repository.
+    #[sea_orm(ignore)]
+    #[serde(default)]
+    #[builder(default)]
+    pub tags: Vec<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    /// Set while the post is in the trash; repository reads skip such posts.
+    #[serde(default)]
+    #[builder(default)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
+
 pub type Post = Model;
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "post_tags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub post_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tag_id: Uuid,
+    #[sea_orm(belongs_to, from = "post_id", to = "id")]
+    pub post: HasOne<super::post::Entity>,
+    #[sea_orm(belongs_to, from = "tag_id", to = "id")]
+    pub tag: HasOne<super::tag::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+use crate::domain::PostId;
+
+/// What was removed when erasing a user's content, reported back to the
+/// users service with the acknowledgement.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuthorErasure {
+    /// Posts written by the user, all of them deleted for good.
+    pub post_ids: Vec<PostId>,
+    /// Comments emptied and left as placeholders so replies keep their thread.
+    pub comments_cleared: u64,
+    /// Posts the user had reacted to, whose counts need recomputing.
+    pub reacted_post_ids: Vec<PostId>,
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::{Comment, Post, entities::post_reaction};
+
+/// Where the users service fetches the `posts` part of export `export_id`
+/// from.
+pub fn part_path(export_id: Uuid) -> String {
+    format!("/exports/{}", export_id)
+}
+
+/// Everything a user wrote or did here, sent as the `posts` part of their
+/// data export. Trashed posts and deleted comments are included since they
+/// are still stored.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuthorExport {
+    pub posts: Vec<Post>,
+    pub comments: Vec<Comment>,
+    pub reactions: Vec<post_reaction::Model>,
+}
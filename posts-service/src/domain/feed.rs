@@ -0,0 +1,96 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use common::error::{AppError, Result};
+use uuid::Uuid;
+
+use crate::domain::Post;
+
+/// Timelines are trimmed to this many entries in Redis; older ones stay in Postgres.
+pub const MAX_TIMELINE_LEN: usize = 800;
+/// How many of an author's latest posts a new subscriber gets in their timeline.
+pub const SUBSCRIPTION_BACKFILL: u64 = 20;
+
+/// A post in someone's timeline, ordered by `published_at` and then `post_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineEntry {
+    pub post_id: Uuid,
+    pub published_at: DateTime<Utc>,
+}
+
+impl TimelineEntry {
+    /// Returns `None` for posts that were never published.
+    pub fn for_post(post: &Post) -> Option<Self> {
+        Some(Self {
+            post_id: post.id,
+            published_at: post.published_at?.into(),
+        })
+    }
+
+    pub fn cursor(&self) -> FeedCursor {
+        FeedCursor {
+            published_at: self.published_at,
+            post_id: self.post_id,
+        }
+    }
+
+    /// Sort score used for Redis sorted sets: microseconds since the epoch,
+    /// exact in an `f64` for any realistic date.
+    pub fn score(&self) -> f64 {
+        self.published_at.timestamp_micros() as f64
+    }
+
+    pub fn from_score(post_id: Uuid, score: f64) -> Option<Self> {
+        Some(Self {
+            post_id,
+            published_at: DateTime::from_timestamp_micros(score as i64)?,
+        })
+    }
+
+    /// Whether this entry comes after `cursor` in newest-first order.
+    pub fn is_after(&self, cursor: &FeedCursor) -> bool {
+        (self.published_at, self.post_id) < (cursor.published_at, cursor.post_id)
+    }
+
+    /// Newest first, ties broken by descending post id.
+    pub fn newest_first(a: &Self, b: &Self) -> Ordering {
+        (b.published_at, b.post_id).cmp(&(a.published_at, a.post_id))
+    }
+}
+
+/// Position in a feed; the next page starts after this entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedCursor {
+    pub published_at: DateTime<Utc>,
+    pub post_id: Uuid,
+}
+
+impl FeedCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.published_at.timestamp_micros(), self.post_id)
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || AppError::ValidationError("Invalid feed cursor".to_string());
+
+        let (micros, post_id) = value.split_once('_').ok_or_else(invalid)?;
+        let published_at = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let post_id = post_id.parse().map_err(|_| invalid())?;
+
+        Ok(Self {
+            published_at,
+            post_id,
+        })
+    }
+}
+
+/// One page of a reader's feed.
+#[derive(Debug, Clone)]
+pub struct FeedPage {
+    pub posts: Vec<Post>,
+    pub next_cursor: Option<FeedCursor>,
+}
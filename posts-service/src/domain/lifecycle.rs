@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use common::error::{AppError, Result};
+
+use crate::domain::{Post, PostStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostTransition {
+    Publish,
+    Unpublish,
+    Schedule(DateTime<Utc>),
+    Archive,
+}
+
+impl PostTransition {
+    pub fn target(&self) -> PostStatus {
+        match self {
+            Self::Publish => PostStatus::Published,
+            Self::Unpublish => PostStatus::Draft,
+            Self::Schedule(_) => PostStatus::Scheduled,
+            Self::Archive => PostStatus::Archived,
+        }
+    }
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Scheduled => "scheduled",
+            Self::Published => "published",
+            Self::Archived => "archived",
+        }
+    }
+
+    pub fn can_transition_to(self, next: PostStatus) -> bool {
+        use PostStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, Scheduled | Published | Archived)
+                | (Scheduled, Draft | Scheduled | Published | Archived)
+                | (Published, Draft | Archived)
+                | (Archived, Draft)
+        )
+    }
+}
+
+impl Post {
+    pub fn apply_transition(
+        mut self,
+        transition: PostTransition,
+        now: DateTime<Utc>,
+    ) -> Result<Post> {
+        let next = transition.target();
+
+        if !self.status.can_transition_to(next) {
+            return Err(AppError::ConflictError(format!(
+                "Cannot move a {} post to {}",
+                self.status.as_str(),
+                next.as_str()
+            )));
+        }
+
+        match transition {
+            PostTransition::Publish => {
+                self.publish_at = None;
+                self.published_at = Some(now.into());
+            }
+            PostTransition::Unpublish => {
+                self.publish_at = None;
+                self.published_at = None;
+            }
+            PostTransition::Schedule(publish_at) => {
+                if publish_at <= now {
+                    return Err(AppError::ValidationError(
+                        "publish_at must be in the future".to_string(),
+                    ));
+                }
+                self.publish_at = Some(publish_at.into());
+            }
+            PostTransition::Archive => {
+                self.publish_at = None;
+            }
+        }
+
+        self.status = next;
+        self.updated_at = now.into();
+        Ok(self)
+    }
+}
@@ -1,7 +1,41 @@
+pub(crate) mod comment;
+pub(crate) mod content;
 pub(crate) mod entities;
+pub(crate) mod erasure;
+pub(crate) mod export;
+pub(crate) mod feed;
+pub(crate) mod lifecycle;
+pub(crate) mod query;
+pub(crate) mod reaction;
 pub(crate) mod repository;
+pub(crate) mod revision;
+pub(crate) mod search;
+pub(crate) mod slug;
+pub(crate) mod tag;
 mod types;
+pub(crate) mod update;
 
-pub use entities::post::Post;
-pub use repository::{DynPostRepository, PostRepository};
-pub use types::{AuthorId, PostId};
+pub use comment::CommentThread;
+pub use entities::author_status::AccountStatus;
+pub use entities::comment::{Comment, CommentStatus};
+pub use entities::post::{ContentFormat, Post, PostStatus};
+pub use entities::post_reaction::ReactionKind;
+pub use entities::post_revision::PostRevision;
+pub use erasure::AuthorErasure;
+pub use export::AuthorExport;
+pub use feed::{FeedCursor, FeedPage, TimelineEntry};
+pub use lifecycle::PostTransition;
+pub use query::{PostFilter, PostSort, PostSortField};
+pub use reaction::{ReactionCounts, ReactionDeltas};
+pub use repository::{
+    CommentRepository, DynCommentRepository, DynFeedRepository, DynPostRepository,
+    DynReactionRepository, DynTimelineStore, FeedRepository, PostRepository, ReactionRepository,
+    TimelineStore,
+};
+pub use search::{
+    AuthorFacet, IndexedPostHit, PostSearchHit, SearchFacets, SearchIndexFilter,
+    SearchIndexResults, TagFacet,
+};
+pub use tag::TagCount;
+pub use types::{AuthorId, CommentId, PostId, UserId};
+pub use update::PostUpdate;
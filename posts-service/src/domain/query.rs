@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use common::pagination::SortOrder;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::PostStatus;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostFilter {
+    pub status: Option<PostStatus>,
+    pub author_id: Option<Uuid>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub title: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    PublishedAt,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PostSort {
+    #[serde(default)]
+    pub sort_by: PostSortField,
+    #[serde(default)]
+    pub order: SortOrder,
+}
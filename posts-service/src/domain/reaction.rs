@@ -0,0 +1,18 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::{PostId, ReactionKind};
+
+/// Reactions of each kind on a post; kinds nobody used are left out.
+pub type ReactionCounts = BTreeMap<ReactionKind, u64>;
+
+/// Signed changes to the reaction counts of each post.
+pub type ReactionDeltas = HashMap<PostId, HashMap<ReactionKind, i64>>;
+
+/// Applies a signed change to the count of `kind`, dropping it once it reaches zero.
+pub fn apply_delta(counts: &mut ReactionCounts, kind: ReactionKind, delta: i64) {
+    let count = counts.get(&kind).copied().unwrap_or_default();
+    match count.saturating_add_signed(delta) {
+        0 => counts.remove(&kind),
+        count => counts.insert(kind, count),
+    };
+}
@@ -1,20 +1,198 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use common::error::Result;
+use chrono::{DateTime, Utc};
+use common::{error::Result, etag::IfMatch, pagination::Pagination};
 
-use crate::domain::PostId;
+use crate::domain::{
+    AuthorErasure, AuthorExport, AuthorId, CommentId, CommentThread, FeedCursor, PostFilter,
+    PostId, PostSearchHit, PostSort, PostTransition, PostUpdate, ReactionCounts, ReactionDeltas,
+    ReactionKind, TagCount, TimelineEntry, UserId,
+};
 
-use super::entities::post::Post;
+use super::entities::{
+    author_status::AccountStatus,
+    comment::{Comment, CommentStatus},
+    post::Post,
+    post_revision::PostRevision,
+};
 
 #[async_trait]
 pub trait PostRepository: Send + Sync + Debug {
-    async fn create_post(&self, post: Post) -> Result<()>;
+    async fn create_post(&self, post: Post) -> Result<Post>;
     async fn get_post(&self, id: PostId) -> Result<Option<Post>>;
-    async fn update_post(&self, post: Post) -> Result<()>;
+    /// Finds the post owning `slug`, either as its current or a former slug.
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>>;
+    async fn update_post(&self, update: PostUpdate, if_match: &IfMatch) -> Result<Post>;
+    /// Moves a post to the trash, from where it can be restored until it is purged.
     async fn delete_post(&self, id: PostId) -> Result<()>;
-    async fn list_posts(&self) -> Result<Option<Vec<Post>>>;
+    /// Trashed posts, most recently deleted first.
+    async fn list_deleted_posts(
+        &self,
+        author_id: Option<AuthorId>,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)>;
+    async fn restore_post(&self, id: PostId) -> Result<Post>;
+    /// Permanently removes posts trashed before `before` and returns how many there were.
+    async fn purge_deleted_posts(&self, before: DateTime<Utc>) -> Result<u64>;
+    async fn list_posts(
+        &self,
+        filter: &PostFilter,
+        sort: &PostSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)>;
+    async fn search_posts(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostSearchHit>, u64)>;
+    async fn change_status(&self, id: PostId, transition: PostTransition) -> Result<Post>;
+    async fn publish_due_posts(&self, now: DateTime<Utc>) -> Result<Vec<Post>>;
+    async fn list_revisions(
+        &self,
+        id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostRevision>, u64)>;
+    async fn get_revision(&self, id: PostId, revision: i32) -> Result<Option<PostRevision>>;
+    async fn restore_revision(&self, id: PostId, revision: i32) -> Result<Post>;
+    async fn list_tags(&self, pagination: &Pagination) -> Result<(Vec<TagCount>, u64)>;
+    /// Deletes everything `author_id` wrote or did here and acknowledges
+    /// `erasure_id` to the users service in the same transaction. Running it
+    /// again finds nothing left and acknowledges again.
+    async fn erase_author(
+        &self,
+        author_id: AuthorId,
+        erasure_id: uuid::Uuid,
+    ) -> Result<AuthorErasure>;
+    /// Collects everything `author_id` wrote or did here, stores it as a part
+    /// of export `export_id` and tells the users service where to fetch it.
+    async fn export_author(
+        &self,
+        author_id: AuthorId,
+        export_id: uuid::Uuid,
+    ) -> Result<AuthorExport>;
+    /// The stored part of export `export_id`, if there is one.
+    async fn get_export(&self, export_id: uuid::Uuid) -> Result<Option<serde_json::Value>>;
+    /// Records the account status the users service announced for `author_id`
+    /// at the user's `version`, unless a later one is already recorded.
+    /// Returns the ids of the author's posts when their visibility changed,
+    /// each of which is announced as `post_visibility_changed`.
+    async fn set_author_status(
+        &self,
+        author_id: AuthorId,
+        status: AccountStatus,
+        version: i32,
+    ) -> Result<Vec<PostId>>;
 }
 
 pub type DynPostRepository = Arc<dyn PostRepository>;
+
+#[async_trait]
+pub trait CommentRepository: Send + Sync + Debug {
+    /// Adds a comment to a published post, threading it under `parent_id` when set.
+    async fn create_comment(&self, comment: Comment) -> Result<Comment>;
+    async fn get_comment(&self, id: CommentId) -> Result<Option<Comment>>;
+    /// Replaces the content of a comment; only its author may edit it.
+    async fn update_comment(
+        &self,
+        id: CommentId,
+        author_id: AuthorId,
+        content: String,
+    ) -> Result<Comment>;
+    /// Deletes a comment on behalf of its author. Comments with replies are kept
+    /// as placeholders until their last reply goes.
+    async fn delete_comment(&self, id: CommentId, author_id: AuthorId) -> Result<()>;
+    async fn set_comment_status(&self, id: CommentId, status: CommentStatus) -> Result<Comment>;
+    /// Visible threads of a post, paginated by top-level comment.
+    async fn list_threads(
+        &self,
+        post_id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<CommentThread>, u64)>;
+    /// Flat list of a post's comments in a moderation status, oldest first.
+    async fn list_comments_by_status(
+        &self,
+        post_id: PostId,
+        status: CommentStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Comment>, u64)>;
+}
+
+pub type DynCommentRepository = Arc<dyn CommentRepository>;
+
+#[async_trait]
+pub trait ReactionRepository: Send + Sync + Debug {
+    /// Adds a reaction to a published post. Returns `false` if the user had
+    /// already reacted this way.
+    async fn add_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool>;
+    /// Returns `false` if the user had not reacted this way.
+    async fn remove_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool>;
+    async fn user_reactions(&self, post_id: PostId, user_id: UserId) -> Result<Vec<ReactionKind>>;
+    /// Counts for each of `post_ids`; posts without reactions get empty counts.
+    async fn reaction_counts(&self, post_ids: &[PostId])
+    -> Result<HashMap<PostId, ReactionCounts>>;
+    /// Adds `deltas` to the stored counts. Additions commute, so instances
+    /// writing back at the same time do not overwrite each other.
+    async fn add_reaction_counts(&self, deltas: &ReactionDeltas) -> Result<()>;
+    /// Drops counts of `post_ids` a caching layer holds, after they were
+    /// changed elsewhere, e.g. by an erasure.
+    async fn invalidate_reaction_counts(&self, post_ids: &[PostId]) -> Result<()>;
+    /// Writes counts buffered by a caching layer back to the database and
+    /// returns how many posts were written.
+    async fn flush_reaction_counts(&self) -> Result<usize>;
+}
+
+pub type DynReactionRepository = Arc<dyn ReactionRepository>;
+
+/// Local replica of who follows whom, as far as feeds are concerned.
+#[async_trait]
+pub trait FeedRepository: Send + Sync + Debug {
+    /// Returns `false` if `subscriber_id` was already subscribed to `author_id`.
+    async fn subscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool>;
+    async fn unsubscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool>;
+    async fn is_subscribed(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool>;
+    async fn subscribers(&self, author_id: AuthorId) -> Result<Vec<UserId>>;
+    async fn subscriber_count(&self, author_id: AuthorId) -> Result<u64>;
+    /// Authors followed by `subscriber_id` that have at least `threshold` subscribers.
+    async fn hot_subscriptions(
+        &self,
+        subscriber_id: UserId,
+        threshold: u64,
+    ) -> Result<Vec<AuthorId>>;
+    /// Latest published posts of `author_ids`, newest first, starting after `before`.
+    async fn recent_posts(
+        &self,
+        author_ids: &[AuthorId],
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>>;
+}
+
+pub type DynFeedRepository = Arc<dyn FeedRepository>;
+
+/// Per-user lists of posts pushed to their followers' feeds.
+#[async_trait]
+pub trait TimelineStore: Send + Sync + Debug {
+    async fn push(&self, user_ids: &[UserId], entries: &[TimelineEntry]) -> Result<()>;
+    /// Entries of `user_id`'s timeline, newest first, starting after `before`.
+    async fn page(
+        &self,
+        user_id: UserId,
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>>;
+}
+
+pub type DynTimelineStore = Arc<dyn TimelineStore>;
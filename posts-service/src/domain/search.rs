@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::Post;
+
+pub const MAX_QUERY_LENGTH: usize = 200;
+
+/// A published post matching a full-text query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostSearchHit {
+    pub post: Post,
+    pub rank: f32,
+    /// Content excerpt with matches wrapped in `<mark>`; the rest is HTML-escaped.
+    pub snippet: String,
+}
+
+/// Drill-down filters applied on top of a search index query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndexFilter {
+    pub tag: Option<String>,
+    pub author_id: Option<Uuid>,
+}
+
+/// A published post as stored in the search index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedPostHit {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub author_id: Uuid,
+    pub tags: Vec<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub score: f32,
+    /// Content excerpt with matches wrapped in `<mark>`; empty when only fuzzy matches hit.
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagFacet {
+    pub tag: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorFacet {
+    pub author_id: Uuid,
+    /// Known once the author's `user_registered` or `user_updated` event has been indexed.
+    pub username: Option<String>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFacets {
+    pub tags: Vec<TagFacet>,
+    pub authors: Vec<AuthorFacet>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndexResults {
+    pub hits: Vec<IndexedPostHit>,
+    pub total: u64,
+    pub facets: SearchFacets,
+}
@@ -0,0 +1,41 @@
+use deunicode::deunicode;
+
+pub const MAX_SLUG_LENGTH: usize = 80;
+const FALLBACK_SLUG: &str = "post";
+
+/// Builds a URL-safe slug from a title: transliterated to ASCII, lowercase,
+/// with every run of other characters collapsed into a single `-`.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in deunicode(title).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    // Cut long slugs at a word boundary where possible
+    if slug.len() > MAX_SLUG_LENGTH {
+        slug.truncate(MAX_SLUG_LENGTH);
+        if let Some(idx) = slug.rfind('-') {
+            slug.truncate(idx);
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        FALLBACK_SLUG.to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Returns `base` for `n == 1`, otherwise `base-n`.
+pub fn with_suffix(base: &str, n: usize) -> String {
+    if n <= 1 {
+        base.to_string()
+    } else {
+        format!("{}-{}", base, n)
+    }
+}
@@ -0,0 +1,52 @@
+use common::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_TAGS_PER_POST: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// A tag together with the number of published posts carrying it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub name: String,
+    pub post_count: i64,
+}
+
+/// Canonical form of a tag name: trimmed, lowercase, inner whitespace as `-`.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Normalizes, deduplicates and sorts tags, matching the order they are loaded in.
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = normalize_tag(tag);
+        if tag.is_empty() {
+            return Err(AppError::ValidationError(
+                "Tags cannot be empty".to_string(),
+            ));
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Tags must be at most {} characters",
+                MAX_TAG_LENGTH
+            )));
+        }
+        normalized.push(tag);
+    }
+    normalized.sort();
+    normalized.dedup();
+
+    if normalized.len() > MAX_TAGS_PER_POST {
+        return Err(AppError::ValidationError(format!(
+            "A post can have at most {} tags",
+            MAX_TAGS_PER_POST
+        )));
+    }
+
+    Ok(normalized)
+}
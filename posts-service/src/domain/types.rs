@@ -2,3 +2,5 @@ use common::define_id;
 
 define_id!(Post);
 define_id!(Author);
+define_id!(Comment);
+define_id!(User);
@@ -0,0 +1,14 @@
+use crate::domain::{AuthorId, ContentFormat, PostId};
+
+/// A full-post edit. Title and content are always replaced; the optional
+/// fields keep their stored value when left out. `author_id` is only checked
+/// against the stored author, since posts never change hands.
+#[derive(Debug, Clone)]
+pub struct PostUpdate {
+    pub id: PostId,
+    pub title: String,
+    pub content: String,
+    pub author_id: Option<AuthorId>,
+    pub content_format: Option<ContentFormat>,
+    pub tags: Option<Vec<String>>,
+}
@@ -0,0 +1,38 @@
+use common::error::{AppError, Result};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+    sea_query::{Query, SelectStatement},
+};
+use uuid::Uuid;
+
+use crate::domain::entities::author_status::{self, AccountStatus};
+
+/// Ids of authors who are not active, whose posts are hidden from readers.
+pub(crate) fn restricted_author_ids() -> SelectStatement {
+    Query::select()
+        .column(author_status::Column::AuthorId)
+        .from(author_status::Entity)
+        .and_where(author_status::Column::Status.ne(AccountStatus::Active))
+        .to_owned()
+}
+
+/// Fails when `user_id`'s account is deactivated, suspended or banned.
+pub(crate) async fn ensure_active<C: ConnectionTrait>(conn: &C, user_id: Uuid) -> Result<()> {
+    let status = author_status::Entity::find_by_id(user_id)
+        .filter(author_status::Column::Status.ne(AccountStatus::Active))
+        .one(conn)
+        .await?;
+
+    match status.map(|status| status.status) {
+        None | Some(AccountStatus::Active) => Ok(()),
+        Some(AccountStatus::Deactivated) => Err(AppError::ForbiddenError(
+            "Account is deactivated".to_string(),
+        )),
+        Some(AccountStatus::Suspended) => {
+            Err(AppError::ForbiddenError("Account is suspended".to_string()))
+        }
+        Some(AccountStatus::Banned) => {
+            Err(AppError::ForbiddenError("Account is banned".to_string()))
+        }
+    }
+}
@@ -1,10 +1,25 @@
-use std::{fmt::Debug, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use common::cache::CacheExt;
-use common::error::Result;
+use common::error::{AppError, Result};
+use common::etag::IfMatch;
+use common::pagination::Pagination;
 
-use crate::domain::{Post, PostId, PostRepository};
+use crate::domain::{
+    AccountStatus, AuthorErasure, AuthorExport, AuthorId, Post, PostFilter, PostId, PostRepository,
+    PostRevision, PostSearchHit, PostSort, PostTransition, PostUpdate, ReactionCounts,
+    ReactionDeltas, ReactionKind, ReactionRepository, TagCount, UserId, reaction,
+};
 
 #[derive(Debug)]
 pub struct CachedPostRepository<C: CacheExt + Send + Sync + Debug> {
@@ -21,15 +36,46 @@ impl<C: CacheExt + Send + Sync + Debug> CachedPostRepository<C> {
     fn cache_key(id: &PostId) -> String {
         format!("post:{}", id)
     }
+
+    /// Maps a slug to its post id. A slug never moves to another post while
+    /// its owner exists, so only the `post:{id}` entry needs invalidation.
+    fn slug_key(slug: &str) -> String {
+        format!("post:slug:{}", slug)
+    }
+
+    /// Holds the generation of the cached `list_posts` pages.
+    const LIST_GENERATION_KEY: &str = "post:list:generation";
+
+    fn list_key(
+        generation: &str,
+        filter: &PostFilter,
+        sort: &PostSort,
+        pagination: &Pagination,
+    ) -> Result<String> {
+        let query = serde_json::to_string(&(filter, sort, pagination))?;
+        Ok(format!("post:list:{}:{}", generation, query))
+    }
+
+    /// Orphans every cached list page. Called after any write that can add,
+    /// remove, reorder or change a listed post.
+    async fn invalidate_lists(&self) {
+        self.cache
+            .bump_generation(Self::LIST_GENERATION_KEY, self.ttl)
+            .await;
+    }
 }
 
 #[async_trait]
 impl<C: CacheExt + Send + Sync + Debug + 'static> PostRepository for CachedPostRepository<C> {
-    async fn create_post(&self, post: Post) -> Result<()> {
+    async fn create_post(&self, post: Post) -> Result<Post> {
+        let post = self.inner.create_post(post).await?;
         let key = Self::cache_key(&post.id.into());
-        self.inner.create_post(post.clone()).await?;
-        self.cache.set(&key, &post, self.ttl).await;
-        Ok(())
+        self.cache.set_versioned(&key, &post, self.ttl).await;
+        self.cache
+            .set(Self::slug_key(&post.slug), post.id, self.ttl)
+            .await;
+        self.invalidate_lists().await;
+        Ok(post)
     }
 
     async fn get_post(&self, id: PostId) -> Result<Option<Post>> {
@@ -42,27 +88,401 @@ impl<C: CacheExt + Send + Sync + Debug + 'static> PostRepository for CachedPostR
         let post = self.inner.get_post(id).await?;
 
         if let Some(ref p) = post {
-            self.cache.set(&key, p, self.ttl).await;
+            self.cache.set_versioned(&key, p, self.ttl).await;
         }
 
         Ok(post)
     }
 
-    async fn update_post(&self, post: Post) -> Result<()> {
-        let key = Self::cache_key(&post.id.into());
-        self.inner.update_post(post.clone()).await?;
-        self.cache.set(&key, &post, self.ttl).await;
-        Ok(())
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>> {
+        let key = Self::slug_key(slug);
+
+        if let Some(id) = self.cache.get::<_, uuid::Uuid>(&key).await {
+            if let Some(post) = self.get_post(id.into()).await? {
+                return Ok(Some(post));
+            }
+            // The owning post was deleted and the slug may have been reused
+            self.cache.delete(&key).await;
+        }
+
+        let post = self.inner.get_post_by_slug(slug).await?;
+
+        if let Some(ref p) = post {
+            self.cache.set(&key, p.id, self.ttl).await;
+            self.cache
+                .set_versioned(Self::cache_key(&p.id.into()), p, self.ttl)
+                .await;
+        }
+
+        Ok(post)
+    }
+
+    async fn update_post(&self, update: PostUpdate, if_match: &IfMatch) -> Result<Post> {
+        let key = Self::cache_key(&update.id);
+        match self.inner.update_post(update, if_match).await {
+            Ok(post) => {
+                self.cache.set_versioned(&key, &post, self.ttl).await;
+                self.invalidate_lists().await;
+                Ok(post)
+            }
+            Err(e @ AppError::PreconditionFailedError(_)) => {
+                // The client saw a stale ETag, possibly served from here
+                self.cache.delete(&key).await;
+                Err(e)
+            }
+            Err(e) => Err(e),
+        }
     }
 
     async fn delete_post(&self, id: PostId) -> Result<()> {
         let key = Self::cache_key(&id);
         self.inner.delete_post(id).await?;
         self.cache.delete(&key).await;
+        self.invalidate_lists().await;
         Ok(())
     }
 
-    async fn list_posts(&self) -> Result<Option<Vec<Post>>> {
-        self.inner.list_posts().await
+    async fn list_deleted_posts(
+        &self,
+        author_id: Option<AuthorId>,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        self.inner.list_deleted_posts(author_id, pagination).await
+    }
+
+    async fn restore_post(&self, id: PostId) -> Result<Post> {
+        let key = Self::cache_key(&id);
+        let post = self.inner.restore_post(id).await?;
+        self.cache.set_versioned(&key, &post, self.ttl).await;
+        self.invalidate_lists().await;
+        Ok(post)
+    }
+
+    async fn purge_deleted_posts(&self, before: DateTime<Utc>) -> Result<u64> {
+        // Trashed posts are evicted and left out of list pages on deletion, so
+        // nothing cached can refer to them
+        self.inner.purge_deleted_posts(before).await
+    }
+
+    async fn erase_author(
+        &self,
+        author_id: AuthorId,
+        erasure_id: uuid::Uuid,
+    ) -> Result<AuthorErasure> {
+        let erasure = self.inner.erase_author(author_id, erasure_id).await?;
+        // Slug entries point at ids whose `post:{id}` entries are evicted here
+        for post_id in &erasure.post_ids {
+            self.cache.delete(Self::cache_key(post_id)).await;
+        }
+        if !erasure.post_ids.is_empty() {
+            self.invalidate_lists().await;
+        }
+        Ok(erasure)
+    }
+
+    async fn export_author(
+        &self,
+        author_id: AuthorId,
+        export_id: uuid::Uuid,
+    ) -> Result<AuthorExport> {
+        self.inner.export_author(author_id, export_id).await
+    }
+
+    async fn get_export(&self, export_id: uuid::Uuid) -> Result<Option<serde_json::Value>> {
+        self.inner.get_export(export_id).await
+    }
+
+    async fn set_author_status(
+        &self,
+        author_id: AuthorId,
+        status: AccountStatus,
+        version: i32,
+    ) -> Result<Vec<PostId>> {
+        let post_ids = self
+            .inner
+            .set_author_status(author_id, status, version)
+            .await?;
+        // Hidden posts must stop being served; slug entries resolve through these
+        for post_id in &post_ids {
+            self.cache.delete(Self::cache_key(post_id)).await;
+        }
+        if !post_ids.is_empty() {
+            self.invalidate_lists().await;
+        }
+        Ok(post_ids)
+    }
+
+    async fn list_posts(
+        &self,
+        filter: &PostFilter,
+        sort: &PostSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        // Read before the query, so a write landing in between orphans the page
+        let generation = self
+            .cache
+            .generation(Self::LIST_GENERATION_KEY, self.ttl)
+            .await;
+        let key = Self::list_key(&generation, filter, sort, pagination)?;
+
+        if let Some(page) = self.cache.get::<_, (Vec<Post>, u64)>(&key).await {
+            return Ok(page);
+        }
+
+        let page = self.inner.list_posts(filter, sort, pagination).await?;
+        self.cache.set(&key, &page, self.ttl).await;
+
+        Ok(page)
+    }
+
+    async fn search_posts(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostSearchHit>, u64)> {
+        self.inner.search_posts(query, pagination).await
+    }
+
+    async fn change_status(&self, id: PostId, transition: PostTransition) -> Result<Post> {
+        let key = Self::cache_key(&id);
+        let post = self.inner.change_status(id, transition).await?;
+        self.cache.set_versioned(&key, &post, self.ttl).await;
+        self.invalidate_lists().await;
+        Ok(post)
+    }
+
+    async fn publish_due_posts(&self, now: DateTime<Utc>) -> Result<Vec<Post>> {
+        let posts = self.inner.publish_due_posts(now).await?;
+        for post in &posts {
+            let key = Self::cache_key(&post.id.into());
+            self.cache.set_versioned(&key, post, self.ttl).await;
+        }
+        if !posts.is_empty() {
+            self.invalidate_lists().await;
+        }
+        Ok(posts)
+    }
+
+    async fn list_revisions(
+        &self,
+        id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostRevision>, u64)> {
+        self.inner.list_revisions(id, pagination).await
+    }
+
+    async fn get_revision(&self, id: PostId, revision: i32) -> Result<Option<PostRevision>> {
+        self.inner.get_revision(id, revision).await
+    }
+
+    async fn restore_revision(&self, id: PostId, revision: i32) -> Result<Post> {
+        let key = Self::cache_key(&id);
+        let post = self.inner.restore_revision(id, revision).await?;
+        self.cache.set_versioned(&key, &post, self.ttl).await;
+        self.invalidate_lists().await;
+        Ok(post)
+    }
+
+    async fn list_tags(&self, pagination: &Pagination) -> Result<(Vec<TagCount>, u64)> {
+        self.inner.list_tags(pagination).await
+    }
+}
+
+/// Serves reaction counts from the cache and buffers count changes in memory
+/// until `flush_reaction_counts` adds them to the stored counts.
+///
+/// The cache holds the counts as last written to the database; reads add the
+/// changes still pending in this process. Counts seen by other instances catch
+/// up at the next flush, when the written-back posts are evicted.
+///
+/// The pending lock is only held to touch the in-memory deltas. Cache and
+/// database I/O happen outside it, and `writes` tells reads that a write
+/// overlapped them: it is odd while a flush is writing and moves on with every
+/// write, so a read that saw it change retries instead of caching or returning
+/// counts from before the write.
+#[derive(Debug)]
+pub struct CachedReactionRepository<C: CacheExt + Send + Sync + Debug> {
+    inner: Arc<dyn ReactionRepository>,
+    cache: Arc<C>,
+    ttl: Duration,
+    pending: tokio::sync::Mutex<ReactionDeltas>,
+    /// Held for a whole flush, so two flushes never write the same deltas.
+    flushing: tokio::sync::Mutex<()>,
+    writes: AtomicU64,
+}
+
+impl<C: CacheExt + Send + Sync + Debug> CachedReactionRepository<C> {
+    pub fn new(inner: Arc<dyn ReactionRepository>, cache: Arc<C>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            cache,
+            ttl,
+            pending: Default::default(),
+            flushing: Default::default(),
+            writes: AtomicU64::new(0),
+        }
+    }
+
+    fn cache_key(id: &PostId) -> String {
+        format!("post:{}:reactions", id)
+    }
+
+    async fn record(&self, post_id: PostId, kind: ReactionKind, delta: i64) {
+        let mut pending = self.pending.lock().await;
+        *pending.entry(post_id).or_default().entry(kind).or_default() += delta;
+    }
+
+    /// Counts as stored, from the cache or else the database.
+    async fn stored_counts(&self, post_ids: &[PostId]) -> Result<HashMap<PostId, ReactionCounts>> {
+        let mut counts = HashMap::with_capacity(post_ids.len());
+        let mut misses = Vec::new();
+        for id in post_ids {
+            match self
+                .cache
+                .get::<_, ReactionCounts>(Self::cache_key(id))
+                .await
+            {
+                Some(cached) => {
+                    counts.insert(*id, cached);
+                }
+                None => misses.push(*id),
+            }
+        }
+
+        if !misses.is_empty() {
+            for (id, stored) in self.inner.reaction_counts(&misses).await? {
+                self.cache
+                    .set(Self::cache_key(&id), &stored, self.ttl)
+                    .await;
+                counts.insert(id, stored);
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn evict(&self, post_ids: impl IntoIterator<Item = &PostId>) {
+        for id in post_ids {
+            self.cache.delete(Self::cache_key(id)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<C: CacheExt + Send + Sync + Debug + 'static> ReactionRepository
+    for CachedReactionRepository<C>
+{
+    async fn add_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let added = self.inner.add_reaction(post_id, user_id, kind).await?;
+        if added {
+            self.record(post_id, kind, 1).await;
+        }
+        Ok(added)
+    }
+
+    async fn remove_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let removed = self.inner.remove_reaction(post_id, user_id, kind).await?;
+        if removed {
+            self.record(post_id, kind, -1).await;
+        }
+        Ok(removed)
+    }
+
+    async fn user_reactions(&self, post_id: PostId, user_id: UserId) -> Result<Vec<ReactionKind>> {
+        self.inner.user_reactions(post_id, user_id).await
+    }
+
+    async fn reaction_counts(
+        &self,
+        post_ids: &[PostId],
+    ) -> Result<HashMap<PostId, ReactionCounts>> {
+        loop {
+            let before = self.writes.load(Ordering::Acquire);
+            if before % 2 == 1 {
+                // A flush is writing; wait for it rather than spin
+                drop(self.flushing.lock().await);
+                continue;
+            }
+
+            let mut counts = self.stored_counts(post_ids).await?;
+            let pending: ReactionDeltas = {
+                let pending = self.pending.lock().await;
+                post_ids
+                    .iter()
+                    .filter_map(|id| Some((*id, pending.get(id)?.clone())))
+                    .collect()
+            };
+
+            if self.writes.load(Ordering::Acquire) != before {
+                // What was cached above may predate the write
+                self.evict(post_ids).await;
+                continue;
+            }
+
+            for (id, post_counts) in counts.iter_mut() {
+                for (kind, delta) in pending.get(id).into_iter().flatten() {
+                    reaction::apply_delta(post_counts, *kind, *delta);
+                }
+            }
+            return Ok(counts);
+        }
+    }
+
+    async fn add_reaction_counts(&self, deltas: &ReactionDeltas) -> Result<()> {
+        self.inner.add_reaction_counts(deltas).await?;
+        self.writes.fetch_add(2, Ordering::AcqRel);
+        self.evict(deltas.keys()).await;
+        Ok(())
+    }
+
+    async fn invalidate_reaction_counts(&self, post_ids: &[PostId]) -> Result<()> {
+        self.writes.fetch_add(2, Ordering::AcqRel);
+        self.evict(post_ids).await;
+        self.inner.invalidate_reaction_counts(post_ids).await
+    }
+
+    async fn flush_reaction_counts(&self) -> Result<usize> {
+        let _flushing = self.flushing.lock().await;
+        let deltas = self.pending.lock().await.clone();
+        if deltas.is_empty() {
+            return Ok(0);
+        }
+
+        self.writes.fetch_add(1, Ordering::AcqRel);
+        let written = self.inner.add_reaction_counts(&deltas).await;
+        if written.is_ok() {
+            // Changes recorded while writing stay pending for the next flush
+            let mut pending = self.pending.lock().await;
+            for (id, kinds) in &deltas {
+                let Some(post_pending) = pending.get_mut(id) else {
+                    continue;
+                };
+                for (kind, delta) in kinds {
+                    if let Some(left) = post_pending.get_mut(kind) {
+                        *left -= delta;
+                    }
+                }
+                post_pending.retain(|_, left| *left != 0);
+                if post_pending.is_empty() {
+                    pending.remove(id);
+                }
+            }
+            drop(pending);
+            self.evict(deltas.keys()).await;
+        }
+        self.writes.fetch_add(1, Ordering::AcqRel);
+
+        // On failure the changes stay pending and are retried at the next flush
+        written?;
+        Ok(deltas.len())
     }
 }
@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use common::{
+    error::{AppError, Result},
+    outbox,
+    pagination::Pagination,
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::authors;
+use crate::domain::{
+    AuthorId, Comment, CommentId, CommentRepository, CommentStatus, CommentThread, PostId,
+    PostStatus,
+    comment::{self, MAX_COMMENT_DEPTH},
+    entities,
+};
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmCommentRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmCommentRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+
+    async fn find_for_update(tx: &DatabaseTransaction, id: uuid::Uuid) -> Result<Comment> {
+        entities::comment::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(tx)
+            .await?
+            .filter(|comment| !comment.is_deleted())
+            .ok_or_else(|| AppError::NotFoundError("Comment not found".to_string()))
+    }
+
+    fn check_owner(comment: &Comment, author_id: AuthorId) -> Result<()> {
+        if comment.author_id != uuid::Uuid::from(author_id) {
+            return Err(AppError::ForbiddenError(
+                "Only the author can change this comment".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn has_replies(tx: &DatabaseTransaction, id: uuid::Uuid) -> Result<bool> {
+        let replies = entities::comment::Entity::find()
+            .filter(entities::comment::Column::ParentId.eq(id))
+            .count(tx)
+            .await?;
+        Ok(replies > 0)
+    }
+
+    /// Deletes `comment` and then any placeholder ancestors left without replies.
+    async fn remove(tx: &DatabaseTransaction, comment: Comment) -> Result<()> {
+        let mut parent_id = comment.parent_id;
+        comment.delete(tx).await?;
+
+        while let Some(id) = parent_id {
+            let Some(parent) = entities::comment::Entity::find_by_id(id).one(tx).await? else {
+                break;
+            };
+            if !parent.is_deleted() || Self::has_replies(tx, parent.id).await? {
+                break;
+            }
+            parent_id = parent.parent_id;
+            parent.delete(tx).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_post_exists(&self, post_id: PostId) -> Result<()> {
+        entities::post::Entity::find_by_id(uuid::Uuid::from(post_id))
+            .filter(entities::post::Column::DeletedAt.is_null())
+            .one(&self.conn)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommentRepository for SeaOrmCommentRepository {
+    async fn create_comment(&self, mut comment: Comment) -> Result<Comment> {
+        let tx = self.conn.begin().await?;
+        authors::ensure_active(&tx, comment.author_id).await?;
+
+        let post = entities::post::Entity::find_by_id(comment.post_id)
+            .filter(entities::post::Column::DeletedAt.is_null())
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+        if post.status != PostStatus::Published {
+            return Err(AppError::ValidationError(
+                "Comments are only allowed on published posts".to_string(),
+            ));
+        }
+
+        let parent = match comment.parent_id {
+            Some(parent_id) => {
+                let parent = entities::comment::Entity::find_by_id(parent_id)
+                    .one(&tx)
+                    .await?
+                    .filter(|parent| parent.post_id == comment.post_id)
+                    .ok_or_else(|| {
+                        AppError::NotFoundError("Parent comment not found".to_string())
+                    })?;
+                if parent.is_deleted() {
+                    return Err(AppError::ValidationError(
+                        "Cannot reply to a deleted comment".to_string(),
+                    ));
+                }
+                if parent.depth >= MAX_COMMENT_DEPTH {
+                    return Err(AppError::ValidationError(format!(
+                        "Replies can be nested at most {} levels deep",
+                        MAX_COMMENT_DEPTH
+                    )));
+                }
+                Some(parent)
+            }
+            None => None,
+        };
+
+        (comment.root_id, comment.depth) = match parent {
+            Some(ref parent) => (parent.root_id, parent.depth + 1),
+            None => (comment.id, 0),
+        };
+
+        let comment = entities::comment::ActiveModel::from(comment)
+            .insert(&tx)
+            .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "comment",
+            comment.id,
+            "comment_created",
+            serde_json::json!({
+                "comment_id": comment.id,
+                "post_id": post.id,
+                "post_title": post.title,
+                "post_author_id": post.author_id,
+                "author_id": comment.author_id,
+                "parent_id": comment.parent_id,
+                "parent_author_id": parent.map(|parent| parent.author_id),
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn get_comment(&self, id: CommentId) -> Result<Option<Comment>> {
+        Ok(entities::comment::Entity::find_by_id(uuid::Uuid::from(id))
+            .one(&self.conn)
+            .await?)
+    }
+
+    async fn update_comment(
+        &self,
+        id: CommentId,
+        author_id: AuthorId,
+        content: String,
+    ) -> Result<Comment> {
+        let tx = self.conn.begin().await?;
+
+        let comment = Self::find_for_update(&tx, id.into()).await?;
+        Self::check_owner(&comment, author_id)?;
+
+        let now = Utc::now();
+        let comment = entities::comment::ActiveModel {
+            id: Unchanged(comment.id),
+            content: Set(content),
+            edited_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn delete_comment(&self, id: CommentId, author_id: AuthorId) -> Result<()> {
+        let tx = self.conn.begin().await?;
+
+        let comment = Self::find_for_update(&tx, id.into()).await?;
+        Self::check_owner(&comment, author_id)?;
+
+        if Self::has_replies(&tx, comment.id).await? {
+            let now = Utc::now();
+            entities::comment::ActiveModel {
+                id: Unchanged(comment.id),
+                content: Set(String::new()),
+                deleted_at: Set(Some(now.into())),
+                updated_at: Set(now.into()),
+                ..Default::default()
+            }
+            .update(&tx)
+            .await?;
+        } else {
+            Self::remove(&tx, comment).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn set_comment_status(&self, id: CommentId, status: CommentStatus) -> Result<Comment> {
+        let tx = self.conn.begin().await?;
+
+        let comment = Self::find_for_update(&tx, id.into()).await?;
+        let comment = entities::comment::ActiveModel {
+            id: Unchanged(comment.id),
+            status: Set(status),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn list_threads(
+        &self,
+        post_id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<CommentThread>, u64)> {
+        use entities::comment::Column;
+
+        self.ensure_post_exists(post_id).await?;
+
+        let paginator = entities::comment::Entity::find()
+            .filter(Column::PostId.eq(uuid::Uuid::from(post_id)))
+            .filter(Column::ParentId.is_null())
+            .filter(Column::Status.eq(CommentStatus::Approved))
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_threads = paginator.num_items().await?;
+        let roots = paginator.fetch_page(pagination.page - 1).await?;
+
+        let descendants = if roots.is_empty() {
+            Vec::new()
+        } else {
+            entities::comment::Entity::find()
+                .filter(Column::RootId.is_in(roots.iter().map(|root| root.id)))
+                .filter(Column::ParentId.is_not_null())
+                .order_by_asc(Column::CreatedAt)
+                .order_by_asc(Column::Id)
+                .all(&self.conn)
+                .await?
+        };
+
+        Ok((comment::build_threads(roots, descendants), total_threads))
+    }
+
+    async fn list_comments_by_status(
+        &self,
+        post_id: PostId,
+        status: CommentStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Comment>, u64)> {
+        use entities::comment::Column;
+
+        self.ensure_post_exists(post_id).await?;
+
+        let paginator = entities::comment::Entity::find()
+            .filter(Column::PostId.eq(uuid::Uuid::from(post_id)))
+            .filter(Column::Status.eq(status))
+            .filter(Column::DeletedAt.is_null())
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_comments = paginator.num_items().await?;
+        let comments = paginator.fetch_page(pagination.page - 1).await?;
+
+        Ok((comments, total_comments))
+    }
+}
@@ -1,15 +1,23 @@
-use common::cache::{LocalCache, RedisCache, TieredCache};
+use common::cache::{CacheExt, LocalCache, RedisCache, TieredCache};
 use common::config::CacheSettings;
 use common::error::Result;
 use migration::{Migrator, MigratorTrait};
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
-use crate::domain::repository::DynPostRepository;
+use crate::domain::repository::{
+    DynCommentRepository, DynFeedRepository, DynPostRepository, DynReactionRepository,
+    DynTimelineStore,
+};
+use crate::infrastructure::feed::RedisTimelineStore;
 use sea_orm::DatabaseConnection;
 
 #[derive(Debug, Clone)]
 pub struct RepoProvider {
     pub posts: DynPostRepository,
+    pub comments: DynCommentRepository,
+    pub reactions: DynReactionRepository,
+    pub feeds: DynFeedRepository,
+    pub timelines: DynTimelineStore,
 }
 
 impl RepoProvider {
@@ -19,28 +27,64 @@ impl RepoProvider {
     ) -> Result<RepoProvider> {
         Migrator::up(&conn, None).await.unwrap();
 
-        let db_repo: DynPostRepository = Arc::new(super::seaorm::SeaOrmPostRepository::new(conn));
+        let db_repo: DynPostRepository =
+            Arc::new(super::seaorm::SeaOrmPostRepository::new(conn.clone()));
+        // Comment reads are whole thread pages, so they go straight to the database
+        let comments_repo: DynCommentRepository = Arc::new(super::LoggedCommentRepository::new(
+            Arc::new(super::SeaOrmCommentRepository::new(conn.clone())),
+        ));
+        let db_reactions: DynReactionRepository =
+            Arc::new(super::SeaOrmReactionRepository::new(conn.clone()));
+        let feeds: DynFeedRepository = Arc::new(super::LoggedFeedRepository::new(Arc::new(
+            super::SeaOrmFeedRepository::new(conn.clone()),
+        )));
+        let mut timelines: DynTimelineStore = Arc::new(super::PgTimelineStore::new(conn));
 
         let local_cache = LocalCache::new(cache_config);
 
-        let cached: DynPostRepository = if let Some(ref redis_cfg) = cache_config.redis {
+        let (cached, cached_reactions) = if let Some(ref redis_cfg) = cache_config.redis {
             let redis_cache = RedisCache::new(&redis_cfg.url())
-                .map_err(|e| common::error::AppError::InvalidConfiguration(e.to_string()))?;
+                .map_err(|e| common::error::AppError::InvalidConfiguration(e.to_string()))?
+                .with_key_prefix(&redis_cfg.key_prefix);
             let tiered = TieredCache::new(local_cache, cache_config.ttl()).add_l2(redis_cache);
-            Arc::new(super::cache::CachedPostRepository::new(
-                db_repo,
-                Arc::new(tiered),
-                cache_config.ttl(),
-            )) as DynPostRepository
+            timelines = Arc::new(RedisTimelineStore::new(&redis_cfg.url(), timelines)?);
+            Self::cached(db_repo, db_reactions, Arc::new(tiered), cache_config.ttl())
         } else {
-            Arc::new(super::cache::CachedPostRepository::new(
+            Self::cached(
                 db_repo,
+                db_reactions,
                 Arc::new(local_cache),
                 cache_config.ttl(),
-            )) as DynPostRepository
+            )
         };
 
         let posts_repo = Arc::new(super::logger::LoggedPostRepository::new(cached));
-        Ok(RepoProvider { posts: posts_repo })
+        let reactions_repo = Arc::new(super::LoggedReactionRepository::new(cached_reactions));
+        Ok(RepoProvider {
+            posts: posts_repo,
+            comments: comments_repo,
+            reactions: reactions_repo,
+            feeds,
+            timelines,
+        })
+    }
+
+    /// Wraps the database repositories in caching layers sharing one cache.
+    fn cached<C: CacheExt + Send + Sync + Debug + 'static>(
+        posts: DynPostRepository,
+        reactions: DynReactionRepository,
+        cache: Arc<C>,
+        ttl: Duration,
+    ) -> (DynPostRepository, DynReactionRepository) {
+        (
+            Arc::new(super::cache::CachedPostRepository::new(
+                posts,
+                cache.clone(),
+                ttl,
+            )),
+            Arc::new(super::cache::CachedReactionRepository::new(
+                reactions, cache, ttl,
+            )),
+        )
     }
 }
@@ -0,0 +1,260 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use common::error::Result;
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait, TryInsertResult,
+    sea_query::{Expr, ExprTrait, OnConflict},
+};
+use uuid::Uuid;
+
+use super::authors;
+use crate::domain::{
+    AuthorId, FeedCursor, FeedRepository, PostStatus, TimelineEntry, TimelineStore, UserId,
+    entities::{author_subscription, post, timeline_entry},
+};
+
+/// Rows per insert statement when pushing to many timelines at once.
+const PUSH_BATCH: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmFeedRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmFeedRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl FeedRepository for SeaOrmFeedRepository {
+    async fn subscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        let result = author_subscription::Entity::insert(author_subscription::ActiveModel {
+            subscriber_id: Set(subscriber_id.into()),
+            author_id: Set(author_id.into()),
+            created_at: Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                author_subscription::Column::SubscriberId,
+                author_subscription::Column::AuthorId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&self.conn)
+        .await?;
+
+        Ok(matches!(result, TryInsertResult::Inserted(rows) if rows > 0))
+    }
+
+    async fn unsubscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        let result = author_subscription::Entity::delete_many()
+            .filter(author_subscription::Column::SubscriberId.eq(Uuid::from(subscriber_id)))
+            .filter(author_subscription::Column::AuthorId.eq(Uuid::from(author_id)))
+            .exec(&self.conn)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn is_subscribed(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        let found = author_subscription::Entity::find_by_id((
+            Uuid::from(subscriber_id),
+            Uuid::from(author_id),
+        ))
+        .one(&self.conn)
+        .await?;
+
+        Ok(found.is_some())
+    }
+
+    async fn subscribers(&self, author_id: AuthorId) -> Result<Vec<UserId>> {
+        let ids = author_subscription::Entity::find()
+            .select_only()
+            .column(author_subscription::Column::SubscriberId)
+            .filter(author_subscription::Column::AuthorId.eq(Uuid::from(author_id)))
+            .into_tuple::<Uuid>()
+            .all(&self.conn)
+            .await?;
+
+        Ok(ids.into_iter().map(UserId::from).collect())
+    }
+
+    async fn subscriber_count(&self, author_id: AuthorId) -> Result<u64> {
+        let count = author_subscription::Entity::find()
+            .filter(author_subscription::Column::AuthorId.eq(Uuid::from(author_id)))
+            .count(&self.conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn hot_subscriptions(
+        &self,
+        subscriber_id: UserId,
+        threshold: u64,
+    ) -> Result<Vec<AuthorId>> {
+        let followed = author_subscription::Entity::find()
+            .select_only()
+            .column(author_subscription::Column::AuthorId)
+            .filter(author_subscription::Column::SubscriberId.eq(Uuid::from(subscriber_id)))
+            .into_query();
+
+        let ids = author_subscription::Entity::find()
+            .select_only()
+            .column(author_subscription::Column::AuthorId)
+            .filter(author_subscription::Column::AuthorId.in_subquery(followed))
+            .group_by(author_subscription::Column::AuthorId)
+            .having(
+                Expr::col(author_subscription::Column::SubscriberId)
+                    .count()
+                    .gte(threshold as i64),
+            )
+            .into_tuple::<Uuid>()
+            .all(&self.conn)
+            .await?;
+
+        Ok(ids.into_iter().map(AuthorId::from).collect())
+    }
+
+    async fn recent_posts(
+        &self,
+        author_ids: &[AuthorId],
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>> {
+        if author_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query = post::Entity::find()
+            .select_only()
+            .column(post::Column::Id)
+            .column(post::Column::PublishedAt)
+            .filter(post::Column::AuthorId.is_in(author_ids.iter().copied().map(Uuid::from)))
+            .filter(post::Column::Status.eq(PostStatus::Published))
+            .filter(post::Column::DeletedAt.is_null())
+            .filter(post::Column::AuthorId.not_in_subquery(authors::restricted_author_ids()))
+            .filter(post::Column::PublishedAt.is_not_null());
+        if let Some(cursor) = before {
+            query = query.filter(after_cursor(
+                post::Column::PublishedAt,
+                post::Column::Id,
+                cursor,
+            ));
+        }
+
+        let rows = query
+            .order_by_desc(post::Column::PublishedAt)
+            .order_by_desc(post::Column::Id)
+            .limit(limit)
+            .into_tuple::<(Uuid, sea_orm::prelude::DateTimeWithTimeZone)>()
+            .all(&self.conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(post_id, published_at)| TimelineEntry {
+                post_id,
+                published_at: published_at.into(),
+            })
+            .collect())
+    }
+}
+
+/// Timelines kept as rows in Postgres; the durable copy behind any faster store.
+#[derive(Debug, Clone)]
+pub struct PgTimelineStore {
+    conn: DatabaseConnection,
+}
+
+impl PgTimelineStore {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl TimelineStore for PgTimelineStore {
+    async fn push(&self, user_ids: &[UserId], entries: &[TimelineEntry]) -> Result<()> {
+        let rows: Vec<_> = user_ids
+            .iter()
+            .flat_map(|user_id| {
+                entries.iter().map(|entry| timeline_entry::ActiveModel {
+                    user_id: Set((*user_id).into()),
+                    post_id: Set(entry.post_id),
+                    published_at: Set(entry.published_at.into()),
+                })
+            })
+            .collect();
+
+        for batch in rows.chunks(PUSH_BATCH) {
+            timeline_entry::Entity::insert_many(batch.to_vec())
+                .on_conflict(
+                    OnConflict::columns([
+                        timeline_entry::Column::UserId,
+                        timeline_entry::Column::PostId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec_without_returning(&self.conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn page(
+        &self,
+        user_id: UserId,
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>> {
+        let mut query = timeline_entry::Entity::find()
+            .filter(timeline_entry::Column::UserId.eq(Uuid::from(user_id)));
+        if let Some(cursor) = before {
+            query = query.filter(after_cursor(
+                timeline_entry::Column::PublishedAt,
+                timeline_entry::Column::PostId,
+                cursor,
+            ));
+        }
+
+        let rows = query
+            .order_by_desc(timeline_entry::Column::PublishedAt)
+            .order_by_desc(timeline_entry::Column::PostId)
+            .limit(limit)
+            .all(&self.conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TimelineEntry {
+                post_id: row.post_id,
+                published_at: row.published_at.into(),
+            })
+            .collect())
+    }
+}
+
+/// Rows that come after `cursor` in (published_at desc, id desc) order.
+fn after_cursor(
+    published_at: impl ColumnTrait,
+    id: impl ColumnTrait,
+    cursor: &FeedCursor,
+) -> Condition {
+    Condition::any()
+        .add(published_at.lt(cursor.published_at))
+        .add(
+            Condition::all()
+                .add(published_at.eq(cursor.published_at))
+                .add(id.lt(cursor.post_id)),
+        )
+}
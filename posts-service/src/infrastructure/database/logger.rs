@@ -1,9 +1,16 @@
-use std::{sync::Arc, time::Instant};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
-use common::error::Result;
+use chrono::{DateTime, Utc};
+use common::{error::Result, etag::IfMatch, pagination::Pagination};
 
-use crate::domain::{Post, PostId, PostRepository};
+use crate::domain::{
+    AccountStatus, AuthorErasure, AuthorExport, AuthorId, Comment, CommentId, CommentRepository,
+    CommentStatus, CommentThread, FeedCursor, FeedRepository, Post, PostFilter, PostId,
+    PostRepository, PostRevision, PostSearchHit, PostSort, PostTransition, PostUpdate,
+    ReactionCounts, ReactionDeltas, ReactionKind, ReactionRepository, TagCount, TimelineEntry,
+    UserId,
+};
 
 #[derive(Debug)]
 pub struct LoggedPostRepository {
@@ -18,14 +25,16 @@ impl LoggedPostRepository {
 
 #[async_trait]
 impl PostRepository for LoggedPostRepository {
-    async fn create_post(&self, post: Post) -> Result<()> {
+    async fn create_post(&self, post: Post) -> Result<Post> {
         let start = Instant::now();
         tracing::info!(title = %post.title, "Creating post");
 
         let result = self.inner.create_post(post).await;
 
         match &result {
-            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Post created"),
+            Ok(p) => {
+                tracing::info!(post_id = %p.id, slug = %p.slug, elapsed_ms = %start.elapsed().as_millis(), "Post created")
+            }
             Err(e) => tracing::error!(error = %e, "Failed to create post"),
         }
         result
@@ -46,14 +55,30 @@ impl PostRepository for LoggedPostRepository {
         result
     }
 
-    async fn update_post(&self, post: Post) -> Result<()> {
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>> {
         let start = Instant::now();
-        tracing::info!(post_id = %post.id, title = %post.title, "Updating post");
+        let result = self.inner.get_post_by_slug(slug).await;
 
-        let result = self.inner.update_post(post).await;
+        match &result {
+            Ok(Some(p)) => {
+                tracing::info!(slug = %slug, post_id = %p.id, elapsed_ms = %start.elapsed().as_millis(), "Post found by slug")
+            }
+            Ok(None) => tracing::warn!(slug = %slug, "Post not found by slug"),
+            Err(e) => tracing::error!(slug = %slug, error = %e, "Failed to get post by slug"),
+        }
+        result
+    }
+
+    async fn update_post(&self, update: PostUpdate, if_match: &IfMatch) -> Result<Post> {
+        let start = Instant::now();
+        tracing::info!(post_id = %update.id, title = %update.title, if_match = ?if_match, "Updating post");
+
+        let result = self.inner.update_post(update, if_match).await;
 
         match &result {
-            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Post updated"),
+            Ok(p) => {
+                tracing::info!(version = p.version, elapsed_ms = %start.elapsed().as_millis(), "Post updated")
+            }
             Err(e) => tracing::error!(error = %e, "Failed to update post"),
         }
         result
@@ -73,17 +98,616 @@ impl PostRepository for LoggedPostRepository {
         result
     }
 
-    async fn list_posts(&self) -> Result<Option<Vec<Post>>> {
+    async fn list_deleted_posts(
+        &self,
+        author_id: Option<AuthorId>,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_deleted_posts(author_id, pagination).await;
+
+        match &result {
+            Ok((posts, total)) => {
+                tracing::info!(count = posts.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Trashed posts listed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to list trashed posts"),
+        }
+        result
+    }
+
+    async fn restore_post(&self, id: PostId) -> Result<Post> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(post_id = %id_str, "Restoring post");
+
+        let result = self.inner.restore_post(id).await;
+
+        match &result {
+            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Post restored"),
+            Err(e) => tracing::error!(post_id = %id_str, error = %e, "Failed to restore post"),
+        }
+        result
+    }
+
+    async fn purge_deleted_posts(&self, before: DateTime<Utc>) -> Result<u64> {
+        let start = Instant::now();
+        let result = self.inner.purge_deleted_posts(before).await;
+
+        match &result {
+            Ok(0) => {}
+            Ok(purged) => {
+                tracing::info!(purged = purged, before = %before, elapsed_ms = %start.elapsed().as_millis(), "Trashed posts purged")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to purge trashed posts"),
+        }
+        result
+    }
+
+    async fn erase_author(
+        &self,
+        author_id: AuthorId,
+        erasure_id: uuid::Uuid,
+    ) -> Result<AuthorErasure> {
+        let start = Instant::now();
+        tracing::info!(author_id = %author_id, erasure_id = %erasure_id, "Erasing author");
+
+        let result = self.inner.erase_author(author_id, erasure_id).await;
+
+        match &result {
+            Ok(erasure) => {
+                tracing::info!(posts = erasure.post_ids.len(), comments = erasure.comments_cleared, reacted_posts = erasure.reacted_post_ids.len(), elapsed_ms = %start.elapsed().as_millis(), "Author erased")
+            }
+            Err(e) => tracing::error!(author_id = %author_id, error = %e, "Failed to erase author"),
+        }
+        result
+    }
+
+    async fn export_author(
+        &self,
+        author_id: AuthorId,
+        export_id: uuid::Uuid,
+    ) -> Result<AuthorExport> {
         let start = Instant::now();
-        let result = self.inner.list_posts().await;
+        let result = self.inner.export_author(author_id, export_id).await;
 
         match &result {
-            Ok(Some(posts)) => {
-                tracing::info!(count = posts.len(), elapsed_ms = %start.elapsed().as_millis(), "Posts listed")
+            Ok(export) => {
+                tracing::info!(author_id = %author_id, export_id = %export_id, posts = export.posts.len(), comments = export.comments.len(), reactions = export.reactions.len(), elapsed_ms = %start.elapsed().as_millis(), "Author exported")
+            }
+            Err(e) => {
+                tracing::error!(author_id = %author_id, error = %e, "Failed to export author")
+            }
+        }
+        result
+    }
+
+    async fn get_export(&self, export_id: uuid::Uuid) -> Result<Option<serde_json::Value>> {
+        let start = Instant::now();
+        let result = self.inner.get_export(export_id).await;
+
+        match &result {
+            Ok(export) => {
+                tracing::info!(export_id = %export_id, found = export.is_some(), elapsed_ms = %start.elapsed().as_millis(), "Export fetched")
+            }
+            Err(e) => {
+                tracing::error!(export_id = %export_id, error = %e, "Failed to fetch export")
+            }
+        }
+        result
+    }
+
+    async fn set_author_status(
+        &self,
+        author_id: AuthorId,
+        status: AccountStatus,
+        version: i32,
+    ) -> Result<Vec<PostId>> {
+        let start = Instant::now();
+        let result = self
+            .inner
+            .set_author_status(author_id, status, version)
+            .await;
+
+        match &result {
+            Ok(post_ids) => {
+                tracing::info!(author_id = %author_id, status = ?status, version = version, affected_posts = post_ids.len(), elapsed_ms = %start.elapsed().as_millis(), "Author status recorded")
+            }
+            Err(e) => {
+                tracing::error!(author_id = %author_id, error = %e, "Failed to record author status")
+            }
+        }
+        result
+    }
+
+    async fn list_posts(
+        &self,
+        filter: &PostFilter,
+        sort: &PostSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_posts(filter, sort, pagination).await;
+
+        match &result {
+            Ok((posts, total)) => {
+                tracing::info!(count = posts.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Posts listed")
             }
-            Ok(None) => tracing::info!("No posts found"),
             Err(e) => tracing::error!(error = %e, "Failed to list posts"),
         }
         result
     }
+
+    async fn search_posts(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostSearchHit>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.search_posts(query, pagination).await;
+
+        match &result {
+            Ok((hits, total)) => {
+                tracing::info!(query = %query, count = hits.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Posts searched")
+            }
+            Err(e) => tracing::error!(query = %query, error = %e, "Failed to search posts"),
+        }
+        result
+    }
+
+    async fn change_status(&self, id: PostId, transition: PostTransition) -> Result<Post> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(post_id = %id_str, transition = ?transition, "Changing post status");
+
+        let result = self.inner.change_status(id, transition).await;
+
+        match &result {
+            Ok(p) => {
+                tracing::info!(post_id = %id_str, status = p.status.as_str(), elapsed_ms = %start.elapsed().as_millis(), "Post status changed")
+            }
+            Err(e) => {
+                tracing::error!(post_id = %id_str, error = %e, "Failed to change post status")
+            }
+        }
+        result
+    }
+
+    async fn publish_due_posts(&self, now: DateTime<Utc>) -> Result<Vec<Post>> {
+        let start = Instant::now();
+        let result = self.inner.publish_due_posts(now).await;
+
+        match &result {
+            Ok(posts) if posts.is_empty() => {}
+            Ok(posts) => {
+                tracing::info!(count = posts.len(), elapsed_ms = %start.elapsed().as_millis(), "Scheduled posts published")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to publish scheduled posts"),
+        }
+        result
+    }
+
+    async fn list_revisions(
+        &self,
+        id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostRevision>, u64)> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        let result = self.inner.list_revisions(id, pagination).await;
+
+        match &result {
+            Ok((revisions, total)) => {
+                tracing::info!(post_id = %id_str, count = revisions.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Post revisions listed")
+            }
+            Err(e) => {
+                tracing::error!(post_id = %id_str, error = %e, "Failed to list post revisions")
+            }
+        }
+        result
+    }
+
+    async fn get_revision(&self, id: PostId, revision: i32) -> Result<Option<PostRevision>> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        let result = self.inner.get_revision(id, revision).await;
+
+        match &result {
+            Ok(Some(_)) => {
+                tracing::info!(post_id = %id_str, revision = revision, elapsed_ms = %start.elapsed().as_millis(), "Post revision found")
+            }
+            Ok(None) => {
+                tracing::warn!(post_id = %id_str, revision = revision, "Post revision not found")
+            }
+            Err(e) => {
+                tracing::error!(post_id = %id_str, revision = revision, error = %e, "Failed to get post revision")
+            }
+        }
+        result
+    }
+
+    async fn restore_revision(&self, id: PostId, revision: i32) -> Result<Post> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(post_id = %id_str, revision = revision, "Restoring post revision");
+
+        let result = self.inner.restore_revision(id, revision).await;
+
+        match &result {
+            Ok(_) => {
+                tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Post revision restored")
+            }
+            Err(e) => {
+                tracing::error!(post_id = %id_str, revision = revision, error = %e, "Failed to restore post revision")
+            }
+        }
+        result
+    }
+
+    async fn list_tags(&self, pagination: &Pagination) -> Result<(Vec<TagCount>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_tags(pagination).await;
+
+        match &result {
+            Ok((tags, total)) => {
+                tracing::info!(count = tags.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Tags listed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to list tags"),
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedCommentRepository {
+    inner: Arc<dyn CommentRepository>,
+}
+
+impl LoggedCommentRepository {
+    pub fn new(inner: Arc<dyn CommentRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl CommentRepository for LoggedCommentRepository {
+    async fn create_comment(&self, comment: Comment) -> Result<Comment> {
+        let start = Instant::now();
+        tracing::info!(post_id = %comment.post_id, parent_id = ?comment.parent_id, "Creating comment");
+
+        let result = self.inner.create_comment(comment).await;
+
+        match &result {
+            Ok(c) => {
+                tracing::info!(comment_id = %c.id, depth = c.depth, elapsed_ms = %start.elapsed().as_millis(), "Comment created")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to create comment"),
+        }
+        result
+    }
+
+    async fn get_comment(&self, id: CommentId) -> Result<Option<Comment>> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        let result = self.inner.get_comment(id).await;
+
+        match &result {
+            Ok(Some(_)) => {
+                tracing::info!(comment_id = %id_str, elapsed_ms = %start.elapsed().as_millis(), "Comment found")
+            }
+            Ok(None) => tracing::warn!(comment_id = %id_str, "Comment not found"),
+            Err(e) => tracing::error!(comment_id = %id_str, error = %e, "Failed to get comment"),
+        }
+        result
+    }
+
+    async fn update_comment(
+        &self,
+        id: CommentId,
+        author_id: AuthorId,
+        content: String,
+    ) -> Result<Comment> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(comment_id = %id_str, author_id = %author_id, "Updating comment");
+
+        let result = self.inner.update_comment(id, author_id, content).await;
+
+        match &result {
+            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Comment updated"),
+            Err(e) => tracing::error!(comment_id = %id_str, error = %e, "Failed to update comment"),
+        }
+        result
+    }
+
+    async fn delete_comment(&self, id: CommentId, author_id: AuthorId) -> Result<()> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(comment_id = %id_str, author_id = %author_id, "Deleting comment");
+
+        let result = self.inner.delete_comment(id, author_id).await;
+
+        match &result {
+            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Comment deleted"),
+            Err(e) => tracing::error!(comment_id = %id_str, error = %e, "Failed to delete comment"),
+        }
+        result
+    }
+
+    async fn set_comment_status(&self, id: CommentId, status: CommentStatus) -> Result<Comment> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(comment_id = %id_str, status = ?status, "Moderating comment");
+
+        let result = self.inner.set_comment_status(id, status).await;
+
+        match &result {
+            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "Comment moderated"),
+            Err(e) => {
+                tracing::error!(comment_id = %id_str, error = %e, "Failed to moderate comment")
+            }
+        }
+        result
+    }
+
+    async fn list_threads(
+        &self,
+        post_id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<CommentThread>, u64)> {
+        let start = Instant::now();
+        let id_str = post_id.to_string();
+        let result = self.inner.list_threads(post_id, pagination).await;
+
+        match &result {
+            Ok((threads, total)) => {
+                tracing::info!(post_id = %id_str, count = threads.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Comment threads listed")
+            }
+            Err(e) => {
+                tracing::error!(post_id = %id_str, error = %e, "Failed to list comment threads")
+            }
+        }
+        result
+    }
+
+    async fn list_comments_by_status(
+        &self,
+        post_id: PostId,
+        status: CommentStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Comment>, u64)> {
+        let start = Instant::now();
+        let id_str = post_id.to_string();
+        let result = self
+            .inner
+            .list_comments_by_status(post_id, status, pagination)
+            .await;
+
+        match &result {
+            Ok((comments, total)) => {
+                tracing::info!(post_id = %id_str, status = ?status, count = comments.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Comments listed")
+            }
+            Err(e) => tracing::error!(post_id = %id_str, error = %e, "Failed to list comments"),
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedReactionRepository {
+    inner: Arc<dyn ReactionRepository>,
+}
+
+impl LoggedReactionRepository {
+    pub fn new(inner: Arc<dyn ReactionRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl ReactionRepository for LoggedReactionRepository {
+    async fn add_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let start = Instant::now();
+        let id_str = post_id.to_string();
+        tracing::info!(post_id = %id_str, user_id = %user_id, kind = ?kind, "Adding reaction");
+
+        let result = self.inner.add_reaction(post_id, user_id, kind).await;
+
+        match &result {
+            Ok(added) => {
+                tracing::info!(added = added, elapsed_ms = %start.elapsed().as_millis(), "Reaction added")
+            }
+            Err(e) => tracing::error!(post_id = %id_str, error = %e, "Failed to add reaction"),
+        }
+        result
+    }
+
+    async fn remove_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let start = Instant::now();
+        let id_str = post_id.to_string();
+        tracing::info!(post_id = %id_str, user_id = %user_id, kind = ?kind, "Removing reaction");
+
+        let result = self.inner.remove_reaction(post_id, user_id, kind).await;
+
+        match &result {
+            Ok(removed) => {
+                tracing::info!(removed = removed, elapsed_ms = %start.elapsed().as_millis(), "Reaction removed")
+            }
+            Err(e) => tracing::error!(post_id = %id_str, error = %e, "Failed to remove reaction"),
+        }
+        result
+    }
+
+    async fn user_reactions(&self, post_id: PostId, user_id: UserId) -> Result<Vec<ReactionKind>> {
+        let id_str = post_id.to_string();
+        let result = self.inner.user_reactions(post_id, user_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(post_id = %id_str, error = %e, "Failed to get user reactions");
+        }
+        result
+    }
+
+    async fn reaction_counts(
+        &self,
+        post_ids: &[PostId],
+    ) -> Result<HashMap<PostId, ReactionCounts>> {
+        let start = Instant::now();
+        let result = self.inner.reaction_counts(post_ids).await;
+
+        match &result {
+            Ok(_) => {
+                tracing::debug!(posts = post_ids.len(), elapsed_ms = %start.elapsed().as_millis(), "Reaction counts loaded")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to load reaction counts"),
+        }
+        result
+    }
+
+    async fn add_reaction_counts(&self, deltas: &ReactionDeltas) -> Result<()> {
+        let start = Instant::now();
+        let result = self.inner.add_reaction_counts(deltas).await;
+
+        match &result {
+            Ok(_) => {
+                tracing::info!(posts = deltas.len(), elapsed_ms = %start.elapsed().as_millis(), "Reaction counts stored")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to store reaction counts"),
+        }
+        result
+    }
+
+    async fn invalidate_reaction_counts(&self, post_ids: &[PostId]) -> Result<()> {
+        let result = self.inner.invalidate_reaction_counts(post_ids).await;
+
+        if let Err(e) = &result {
+            tracing::error!(error = %e, "Failed to invalidate reaction counts");
+        }
+        result
+    }
+
+    async fn flush_reaction_counts(&self) -> Result<usize> {
+        let start = Instant::now();
+        let result = self.inner.flush_reaction_counts().await;
+
+        match &result {
+            Ok(0) => {}
+            Ok(posts) => {
+                tracing::info!(posts = posts, elapsed_ms = %start.elapsed().as_millis(), "Reaction counts flushed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to flush reaction counts"),
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedFeedRepository {
+    inner: Arc<dyn FeedRepository>,
+}
+
+impl LoggedFeedRepository {
+    pub fn new(inner: Arc<dyn FeedRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl FeedRepository for LoggedFeedRepository {
+    async fn subscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        tracing::info!(subscriber_id = %subscriber_id, author_id = %author_id, "Subscribing to author");
+
+        let result = self.inner.subscribe(subscriber_id, author_id).await;
+
+        match &result {
+            Ok(subscribed) => tracing::info!(subscribed = subscribed, "Subscribed to author"),
+            Err(e) => {
+                tracing::error!(author_id = %author_id, error = %e, "Failed to subscribe to author")
+            }
+        }
+        result
+    }
+
+    async fn unsubscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        tracing::info!(subscriber_id = %subscriber_id, author_id = %author_id, "Unsubscribing from author");
+
+        let result = self.inner.unsubscribe(subscriber_id, author_id).await;
+
+        match &result {
+            Ok(removed) => tracing::info!(removed = removed, "Unsubscribed from author"),
+            Err(e) => {
+                tracing::error!(author_id = %author_id, error = %e, "Failed to unsubscribe from author")
+            }
+        }
+        result
+    }
+
+    async fn is_subscribed(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<bool> {
+        let result = self.inner.is_subscribed(subscriber_id, author_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(author_id = %author_id, error = %e, "Failed to check subscription");
+        }
+        result
+    }
+
+    async fn subscribers(&self, author_id: AuthorId) -> Result<Vec<UserId>> {
+        let start = Instant::now();
+        let result = self.inner.subscribers(author_id).await;
+
+        match &result {
+            Ok(subscribers) => {
+                tracing::info!(author_id = %author_id, count = subscribers.len(), elapsed_ms = %start.elapsed().as_millis(), "Loaded subscribers")
+            }
+            Err(e) => {
+                tracing::error!(author_id = %author_id, error = %e, "Failed to load subscribers")
+            }
+        }
+        result
+    }
+
+    async fn subscriber_count(&self, author_id: AuthorId) -> Result<u64> {
+        let result = self.inner.subscriber_count(author_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(author_id = %author_id, error = %e, "Failed to count subscribers");
+        }
+        result
+    }
+
+    async fn hot_subscriptions(
+        &self,
+        subscriber_id: UserId,
+        threshold: u64,
+    ) -> Result<Vec<AuthorId>> {
+        let result = self.inner.hot_subscriptions(subscriber_id, threshold).await;
+
+        if let Err(e) = &result {
+            tracing::error!(subscriber_id = %subscriber_id, error = %e, "Failed to load hot subscriptions");
+        }
+        result
+    }
+
+    async fn recent_posts(
+        &self,
+        author_ids: &[AuthorId],
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>> {
+        let result = self.inner.recent_posts(author_ids, before, limit).await;
+
+        if let Err(e) = &result {
+            tracing::error!(authors = author_ids.len(), error = %e, "Failed to load recent posts");
+        }
+        result
+    }
 }
@@ -1,12 +1,21 @@
+mod authors;
 mod bootstrap;
 mod cache;
+mod comments;
 mod factory;
+mod feed;
 mod logger;
+mod reactions;
 pub mod seaorm;
 mod url;
 
 pub use bootstrap::{bootstrap_db, bootstrap_outbox};
-pub use cache::CachedPostRepository;
+pub use cache::{CachedPostRepository, CachedReactionRepository};
+pub use comments::SeaOrmCommentRepository;
 pub use factory::RepoProvider;
-pub use logger::LoggedPostRepository;
+pub use feed::{PgTimelineStore, SeaOrmFeedRepository};
+pub use logger::{
+    LoggedCommentRepository, LoggedFeedRepository, LoggedPostRepository, LoggedReactionRepository,
+};
+pub use reactions::SeaOrmReactionRepository;
 pub use url::build_db_url;
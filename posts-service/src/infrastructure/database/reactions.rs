@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use common::{
+    error::{AppError, Result},
+    outbox,
+};
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+    TransactionTrait, TryInsertResult,
+    sea_query::{Expr, ExprTrait, OnConflict},
+};
+
+use super::authors;
+use crate::domain::{
+    PostId, PostStatus, ReactionCounts, ReactionDeltas, ReactionKind, ReactionRepository, UserId,
+    entities::{post, post_reaction, post_reaction_count},
+};
+
+/// Adds `deltas` to the stored counts with an upsert, so concurrent writers
+/// add up instead of overwriting each other. Counts that reach zero are
+/// dropped; a count can dip below zero for a moment when a removal is written
+/// before the addition it undoes, so those are kept.
+pub(super) async fn add_counts<C: ConnectionTrait>(
+    conn: &C,
+    deltas: &ReactionDeltas,
+) -> Result<()> {
+    let rows: Vec<post_reaction_count::ActiveModel> = deltas
+        .iter()
+        .flat_map(|(post_id, kinds)| {
+            kinds
+                .iter()
+                .filter(|(_, delta)| **delta != 0)
+                .map(|(kind, delta)| post_reaction_count::ActiveModel {
+                    post_id: Set((*post_id).into()),
+                    kind: Set(*kind),
+                    count: Set(*delta),
+                })
+        })
+        .collect();
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    post_reaction_count::Entity::insert_many(rows)
+        .on_conflict(
+            OnConflict::columns([
+                post_reaction_count::Column::PostId,
+                post_reaction_count::Column::Kind,
+            ])
+            .value(
+                post_reaction_count::Column::Count,
+                Expr::col((
+                    post_reaction_count::Entity,
+                    post_reaction_count::Column::Count,
+                ))
+                .add(Expr::cust("excluded.count")),
+            )
+            .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await?;
+
+    post_reaction_count::Entity::delete_many()
+        .filter(
+            post_reaction_count::Column::PostId.is_in(deltas.keys().copied().map(uuid::Uuid::from)),
+        )
+        .filter(post_reaction_count::Column::Count.eq(0))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmReactionRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmReactionRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+
+    async fn ensure_post_exists(&self, post_id: PostId) -> Result<()> {
+        post::Entity::find_by_id(uuid::Uuid::from(post_id))
+            .filter(post::Column::DeletedAt.is_null())
+            .one(&self.conn)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReactionRepository for SeaOrmReactionRepository {
+    async fn add_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let tx = self.conn.begin().await?;
+        authors::ensure_active(&tx, user_id.into()).await?;
+
+        let post = post::Entity::find_by_id(uuid::Uuid::from(post_id))
+            .filter(post::Column::DeletedAt.is_null())
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+        if post.status != PostStatus::Published {
+            return Err(AppError::ValidationError(
+                "Reactions are only allowed on published posts".to_string(),
+            ));
+        }
+
+        let result = post_reaction::Entity::insert(post_reaction::ActiveModel {
+            post_id: Set(post.id),
+            user_id: Set(user_id.into()),
+            kind: Set(kind),
+            created_at: Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                post_reaction::Column::PostId,
+                post_reaction::Column::UserId,
+                post_reaction::Column::Kind,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&tx)
+        .await?;
+        let inserted = matches!(result, TryInsertResult::Inserted(rows) if rows > 0);
+
+        if inserted {
+            outbox::insert_outbox_event(
+                &tx,
+                "post",
+                post.id,
+                "post_liked",
+                serde_json::json!({
+                    "post_id": post.id,
+                    "post_title": post.title,
+                    "post_author_id": post.author_id,
+                    "user_id": uuid::Uuid::from(user_id),
+                    "reaction": kind,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn remove_reaction(
+        &self,
+        post_id: PostId,
+        user_id: UserId,
+        kind: ReactionKind,
+    ) -> Result<bool> {
+        let result = post_reaction::Entity::delete_many()
+            .filter(post_reaction::Column::PostId.eq(uuid::Uuid::from(post_id)))
+            .filter(post_reaction::Column::UserId.eq(uuid::Uuid::from(user_id)))
+            .filter(post_reaction::Column::Kind.eq(kind))
+            .exec(&self.conn)
+            .await?;
+
+        if result.rows_affected == 0 {
+            self.ensure_post_exists(post_id).await?;
+        }
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn user_reactions(&self, post_id: PostId, user_id: UserId) -> Result<Vec<ReactionKind>> {
+        let mut kinds: Vec<ReactionKind> = post_reaction::Entity::find()
+            .select_only()
+            .column(post_reaction::Column::Kind)
+            .filter(post_reaction::Column::PostId.eq(uuid::Uuid::from(post_id)))
+            .filter(post_reaction::Column::UserId.eq(uuid::Uuid::from(user_id)))
+            .into_tuple()
+            .all(&self.conn)
+            .await?;
+        kinds.sort();
+        Ok(kinds)
+    }
+
+    async fn reaction_counts(
+        &self,
+        post_ids: &[PostId],
+    ) -> Result<HashMap<PostId, ReactionCounts>> {
+        let mut counts: HashMap<PostId, ReactionCounts> = post_ids
+            .iter()
+            .map(|id| (*id, ReactionCounts::new()))
+            .collect();
+        if post_ids.is_empty() {
+            return Ok(counts);
+        }
+
+        let rows = post_reaction_count::Entity::find()
+            .filter(
+                post_reaction_count::Column::PostId
+                    .is_in(post_ids.iter().copied().map(uuid::Uuid::from)),
+            )
+            .filter(post_reaction_count::Column::Count.gt(0))
+            .all(&self.conn)
+            .await?;
+        for row in rows {
+            counts
+                .entry(row.post_id.into())
+                .or_default()
+                .insert(row.kind, row.count as u64);
+        }
+
+        Ok(counts)
+    }
+
+    async fn add_reaction_counts(&self, deltas: &ReactionDeltas) -> Result<()> {
+        let tx = self.conn.begin().await?;
+        add_counts(&tx, deltas).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn invalidate_reaction_counts(&self, _post_ids: &[PostId]) -> Result<()> {
+        // Nothing is cached at this layer
+        Ok(())
+    }
+
+    async fn flush_reaction_counts(&self) -> Result<usize> {
+        // Nothing is buffered at this layer
+        Ok(0)
+    }
+}
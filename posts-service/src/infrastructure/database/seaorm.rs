@@ -1,16 +1,58 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{authors, reactions};
 use crate::domain::{
-    PostId,
-    entities::{self, post::Post},
+    AuthorErasure, AuthorExport, AuthorId, PostFilter, PostId, PostSearchHit, PostSort,
+    PostSortField, PostStatus, PostTransition, PostUpdate, ReactionDeltas, ReactionKind, TagCount,
+    content,
+    entities::{
+        self,
+        author_status::{self, AccountStatus},
+        post::Post,
+        post_revision::PostRevision,
+        post_slug, post_tag, tag,
+    },
+    export,
     repository::PostRepository,
+    slug,
 };
 use async_trait::async_trait;
-use common::{error::Result, outbox};
+use chrono::{DateTime, Utc};
+use common::{
+    db::escape_like,
+    error::{AppError, Result},
+    etag::IfMatch,
+    outbox,
+    pagination::Pagination,
+};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{Set, Unchanged},
-    DatabaseConnection, EntityTrait, ModelTrait, TransactionTrait,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr,
+    EntityTrait, FromQueryResult, JoinType, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Select, SqlErr, Statement, TransactionTrait,
+    sea_query::{Expr, ExprTrait, LockBehavior, LockType, OnConflict, Query},
 };
 
+/// Times a write picks a fresh slug after losing one to a concurrent writer.
+const SLUG_ATTEMPTS: usize = 5;
+
+/// Whether `err` is another live post already holding the slug being written.
+fn is_slug_conflict(err: &DbErr) -> bool {
+    matches!(
+        err.sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(message)) if message.contains("idx_posts_slug")
+    )
+}
+
+fn slug_error(err: DbErr) -> AppError {
+    if is_slug_conflict(&err) {
+        AppError::ConflictError("The post's slug was taken by another post, try again".to_string())
+    } else {
+        err.into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SeaOrmPostRepository {
     conn: DatabaseConnection,
@@ -20,15 +62,410 @@ impl SeaOrmPostRepository {
     pub fn new(conn: DatabaseConnection) -> Self {
         Self { conn }
     }
+
+    /// Loads a post and locks its row until the transaction ends, so version
+    /// checks and the following write cannot interleave with another writer.
+    async fn find_for_update(tx: &DatabaseTransaction, id: uuid::Uuid) -> Result<Post> {
+        Self::live()
+            .filter(entities::post::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))
+    }
+
+    /// Posts that are not in the trash.
+    fn live() -> Select<entities::post::Entity> {
+        entities::post::Entity::find().filter(entities::post::Column::DeletedAt.is_null())
+    }
+
+    /// Live posts shown to readers, leaving out those of authors who are not active.
+    fn visible() -> Select<entities::post::Entity> {
+        Self::live().filter(
+            entities::post::Column::AuthorId.not_in_subquery(authors::restricted_author_ids()),
+        )
+    }
+
+    /// Fills in `tags` for the given posts with a single query.
+    async fn load_tags<C: ConnectionTrait>(conn: &C, posts: &mut [Post]) -> Result<()> {
+        if posts.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<(uuid::Uuid, String)> = post_tag::Entity::find()
+            .select_only()
+            .column(post_tag::Column::PostId)
+            .column(tag::Column::Name)
+            .join(JoinType::InnerJoin, post_tag::Relation::Tag.def())
+            .filter(post_tag::Column::PostId.is_in(posts.iter().map(|p| p.id)))
+            .into_tuple()
+            .all(conn)
+            .await?;
+
+        let mut tags_by_post: HashMap<uuid::Uuid, Vec<String>> = HashMap::new();
+        for (post_id, name) in rows {
+            tags_by_post.entry(post_id).or_default().push(name);
+        }
+        for post in posts {
+            post.tags = tags_by_post.remove(&post.id).unwrap_or_default();
+            post.tags.sort();
+        }
+
+        Ok(())
+    }
+
+    async fn with_tags<C: ConnectionTrait>(conn: &C, mut post: Post) -> Result<Post> {
+        Self::load_tags(conn, std::slice::from_mut(&mut post)).await?;
+        Ok(post)
+    }
+
+    /// Replaces the tags of a post, creating unknown tags on the way.
+    /// Returns the tag names that were added and removed.
+    async fn save_tags(
+        tx: &DatabaseTransaction,
+        post_id: uuid::Uuid,
+        tags: &[String],
+    ) -> Result<(Vec<String>, Vec<String>)> {
+        let current: Vec<(uuid::Uuid, String)> = post_tag::Entity::find()
+            .select_only()
+            .column(post_tag::Column::TagId)
+            .column(tag::Column::Name)
+            .join(JoinType::InnerJoin, post_tag::Relation::Tag.def())
+            .filter(post_tag::Column::PostId.eq(post_id))
+            .into_tuple()
+            .all(tx)
+            .await?;
+
+        let added: Vec<String> = tags
+            .iter()
+            .filter(|tag| !current.iter().any(|(_, name)| name == *tag))
+            .cloned()
+            .collect();
+        let (removed_ids, removed): (Vec<uuid::Uuid>, Vec<String>) = current
+            .into_iter()
+            .filter(|(_, name)| !tags.contains(name))
+            .unzip();
+
+        if !removed_ids.is_empty() {
+            post_tag::Entity::delete_many()
+                .filter(post_tag::Column::PostId.eq(post_id))
+                .filter(post_tag::Column::TagId.is_in(removed_ids))
+                .exec(tx)
+                .await?;
+        }
+
+        if !added.is_empty() {
+            let now: sea_orm::prelude::DateTimeWithTimeZone = Utc::now().into();
+            tag::Entity::insert_many(added.iter().map(|name| tag::ActiveModel {
+                id: Set(uuid::Uuid::new_v4()),
+                name: Set(name.clone()),
+                created_at: Set(now),
+            }))
+            .on_conflict(
+                OnConflict::column(tag::Column::Name)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_without_returning(tx)
+            .await?;
+
+            let tag_ids: Vec<uuid::Uuid> = tag::Entity::find()
+                .select_only()
+                .column(tag::Column::Id)
+                .filter(tag::Column::Name.is_in(added.iter().cloned()))
+                .into_tuple()
+                .all(tx)
+                .await?;
+
+            post_tag::Entity::insert_many(tag_ids.into_iter().map(|tag_id| {
+                post_tag::ActiveModel {
+                    post_id: Set(post_id),
+                    tag_id: Set(tag_id),
+                }
+            }))
+            .exec_without_returning(tx)
+            .await?;
+        }
+
+        Ok((added, removed))
+    }
+
+    async fn save_transition(
+        tx: &DatabaseTransaction,
+        post: Post,
+        transition: PostTransition,
+        now: DateTime<Utc>,
+    ) -> Result<Post> {
+        let was_published = post.status == PostStatus::Published;
+        let post = post.apply_transition(transition, now)?;
+
+        let active_model = entities::post::ActiveModel {
+            id: Unchanged(post.id),
+            status: Set(post.status),
+            publish_at: Set(post.publish_at),
+            published_at: Set(post.published_at),
+            version: Set(post.version + 1),
+            updated_at: Set(post.updated_at),
+            ..Default::default()
+        };
+        let post_model = Self::with_tags(tx, active_model.update(tx).await?).await?;
+
+        if post_model.status == PostStatus::Published {
+            Self::insert_published_event(tx, &post_model).await?;
+        } else if was_published {
+            outbox::insert_outbox_event(
+                tx,
+                "post",
+                post_model.id,
+                "post_unpublished",
+                serde_json::json!({
+                    "post_id": post_model.id,
+                    "author_id": post_model.author_id,
+                    "status": post_model.status,
+                }),
+            )
+            .await?;
+        }
+
+        Ok(post_model)
+    }
+
+    /// Picks the first free slug out of `base`, `base-2`, `base-3`, ... Former
+    /// slugs of other posts stay reserved so their redirects keep working, but
+    /// trashed posts give up their current slug.
+    async fn unique_slug(
+        tx: &DatabaseTransaction,
+        base: &str,
+        post_id: uuid::Uuid,
+    ) -> Result<String> {
+        let pattern = format!("{}-%", base);
+
+        let mut taken: HashSet<String> = entities::post::Entity::find()
+            .select_only()
+            .column(entities::post::Column::Slug)
+            .filter(
+                Condition::any()
+                    .add(entities::post::Column::Slug.eq(base))
+                    .add(entities::post::Column::Slug.like(&pattern)),
+            )
+            .filter(entities::post::Column::Id.ne(post_id))
+            .filter(entities::post::Column::DeletedAt.is_null())
+            .into_tuple::<String>()
+            .all(tx)
+            .await?
+            .into_iter()
+            .collect();
+
+        taken.extend(
+            post_slug::Entity::find()
+                .select_only()
+                .column(post_slug::Column::Slug)
+                .filter(
+                    Condition::any()
+                        .add(post_slug::Column::Slug.eq(base))
+                        .add(post_slug::Column::Slug.like(&pattern)),
+                )
+                .filter(post_slug::Column::PostId.ne(post_id))
+                .into_tuple::<String>()
+                .all(tx)
+                .await?,
+        );
+
+        Ok((1..)
+            .map(|n| slug::with_suffix(base, n))
+            .find(|candidate| !taken.contains(candidate))
+            .expect("slug suffixes are unbounded"))
+    }
+
+    /// Returns the new slug for a post whose title changes to `title`, keeping
+    /// the current slug as a redirect. `None` if the slug stays the same.
+    async fn change_slug(
+        tx: &DatabaseTransaction,
+        current: &Post,
+        title: &str,
+    ) -> Result<Option<String>> {
+        let base = slug::slugify(title);
+        if base == slug::slugify(&current.title) {
+            return Ok(None);
+        }
+
+        let new_slug = Self::unique_slug(tx, &base, current.id).await?;
+        if new_slug == current.slug {
+            return Ok(None);
+        }
+
+        // Going back to an earlier title reclaims the post's own former slug
+        post_slug::Entity::delete_many()
+            .filter(post_slug::Column::Slug.eq(new_slug.as_str()))
+            .filter(post_slug::Column::PostId.eq(current.id))
+            .exec(tx)
+            .await?;
+
+        post_slug::ActiveModel {
+            slug: Set(current.slug.clone()),
+            post_id: Set(current.id),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(tx)
+        .await?;
+
+        Ok(Some(new_slug))
+    }
+
+    async fn save_update(
+        tx: &DatabaseTransaction,
+        current: &Post,
+        mut active_model: entities::post::ActiveModel,
+        tags: Option<&[String]>,
+        restored_from: Option<i32>,
+    ) -> Result<Post> {
+        if let Set(ref content) = active_model.content {
+            let format = match active_model.content_format {
+                Set(format) | Unchanged(format) => format,
+                _ => current.content_format,
+            };
+            active_model.content_html = Set(content::render_html(format, content));
+            active_model.excerpt = Set(content::excerpt(format, content));
+            active_model.reading_time_minutes = Set(content::reading_time_minutes(format, content));
+        }
+
+        let mut attempt = 1;
+        let mut post_model = loop {
+            let savepoint = tx.begin().await?;
+            let mut active_model = active_model.clone();
+            if let Set(ref title) = active_model.title
+                && let Some(slug) = Self::change_slug(&savepoint, current, title).await?
+            {
+                active_model.slug = Set(slug);
+            }
+            match entities::post::Entity::update(active_model)
+                .exec(&savepoint)
+                .await
+            {
+                Ok(post_model) => {
+                    savepoint.commit().await?;
+                    break post_model;
+                }
+                Err(e) if attempt < SLUG_ATTEMPTS && is_slug_conflict(&e) => {
+                    savepoint.rollback().await?;
+                    attempt += 1;
+                }
+                Err(e) => return Err(slug_error(e)),
+            }
+        };
+
+        let (tags_added, tags_removed) = match tags {
+            Some(tags) => Self::save_tags(tx, post_model.id, tags).await?,
+            None => (Vec::new(), Vec::new()),
+        };
+        Self::load_tags(tx, std::slice::from_mut(&mut post_model)).await?;
+
+        let revision = Self::insert_revision(tx, &post_model).await?;
+
+        outbox::insert_outbox_event(
+            tx,
+            "post",
+            post_model.id,
+            "post_updated",
+            serde_json::json!({
+                "post_id": post_model.id,
+                "author_id": post_model.author_id,
+                "title": post_model.title,
+                "slug": post_model.slug,
+                "previous_slug": (post_model.slug != current.slug).then_some(&current.slug),
+                "revision": revision.revision,
+                "version": post_model.version,
+                "tags": post_model.tags,
+                "tags_added": tags_added,
+                "tags_removed": tags_removed,
+                "restored_from": restored_from,
+            }),
+        )
+        .await?;
+
+        Ok(post_model)
+    }
+
+    /// Callers hold the post row, which keeps concurrent writers from reading
+    /// the same latest revision and colliding on the next number.
+    async fn insert_revision(tx: &DatabaseTransaction, post: &Post) -> Result<PostRevision> {
+        use entities::post_revision::Column;
+
+        let latest: Option<Option<i32>> = entities::post_revision::Entity::find()
+            .select_only()
+            .column_as(Column::Revision.max(), "revision")
+            .filter(Column::PostId.eq(post.id))
+            .into_tuple()
+            .one(tx)
+            .await?;
+
+        let revision = entities::post_revision::ActiveModel {
+            id: Set(uuid::Uuid::new_v4()),
+            post_id: Set(post.id),
+            revision: Set(latest.flatten().unwrap_or(0) + 1),
+            title: Set(post.title.clone()),
+            content: Set(post.content.clone()),
+            created_at: Set(post.updated_at),
+        };
+        Ok(revision.insert(tx).await?)
+    }
+
+    async fn insert_published_event(tx: &DatabaseTransaction, post: &Post) -> Result<()> {
+        outbox::insert_outbox_event(
+            tx,
+            "post",
+            post.id,
+            "post_published",
+            serde_json::json!({
+                "post_id": post.id,
+                "author_id": post.author_id,
+                "title": post.title,
+                "published_at": post.published_at,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl PostRepository for SeaOrmPostRepository {
-    async fn create_post(&self, post: Post) -> Result<()> {
+    async fn create_post(&self, mut post: Post) -> Result<Post> {
         let tx = self.conn.begin().await?;
+        authors::ensure_active(&tx, post.author_id).await?;
+
+        post.content_html = content::render_html(post.content_format, &post.content);
+        post.excerpt = content::excerpt(post.content_format, &post.content);
+        post.reading_time_minutes =
+            content::reading_time_minutes(post.content_format, &post.content);
+        let base = slug::slugify(&post.title);
+
+        let tags = post.tags.clone();
+        let mut attempt = 1;
+        let mut post_model = loop {
+            post.slug = Self::unique_slug(&tx, &base, post.id).await?;
+            let savepoint = tx.begin().await?;
+            match entities::post::ActiveModel::from(post.clone())
+                .insert(&savepoint)
+                .await
+            {
+                Ok(post_model) => {
+                    savepoint.commit().await?;
+                    break post_model;
+                }
+                Err(e) if attempt < SLUG_ATTEMPTS && is_slug_conflict(&e) => {
+                    savepoint.rollback().await?;
+                    attempt += 1;
+                }
+                Err(e) => return Err(slug_error(e)),
+            }
+        };
+
+        Self::save_tags(&tx, post_model.id, &tags).await?;
+        Self::load_tags(&tx, std::slice::from_mut(&mut post_model)).await?;
 
-        let active = entities::post::ActiveModel::from(post);
-        let post_model = active.insert(&tx).await?;
+        let revision = Self::insert_revision(&tx, &post_model).await?;
 
         outbox::insert_outbox_event(
             &tx,
@@ -38,74 +475,730 @@ impl PostRepository for SeaOrmPostRepository {
             serde_json::json!({
                 "post_id": post_model.id,
                 "author_id": post_model.author_id,
-                "title": post_model.title
+                "title": post_model.title,
+                "slug": post_model.slug,
+                "revision": revision.revision,
+                "tags": post_model.tags,
             }),
         )
         .await?;
 
+        if post_model.status == PostStatus::Published {
+            Self::insert_published_event(&tx, &post_model).await?;
+        }
+
         tx.commit().await?;
 
-        Ok(())
+        Ok(post_model)
     }
 
     async fn get_post(&self, id: PostId) -> Result<Option<Post>> {
-        let post = entities::post::Entity::find_by_id(uuid::Uuid::from(id))
+        let post = Self::visible()
+            .filter(entities::post::Column::Id.eq(uuid::Uuid::from(id)))
             .one(&self.conn)
             .await?;
-        Ok(post)
+
+        match post {
+            Some(post) => Ok(Some(Self::with_tags(&self.conn, post).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>> {
+        use entities::post::Column;
+
+        let post = Self::visible()
+            .filter(
+                Condition::any().add(Column::Slug.eq(slug)).add(
+                    Column::Id.in_subquery(
+                        Query::select()
+                            .column(post_slug::Column::PostId)
+                            .from(post_slug::Entity)
+                            .and_where(Expr::col(post_slug::Column::Slug).eq(slug))
+                            .to_owned(),
+                    ),
+                ),
+            )
+            .one(&self.conn)
+            .await?;
+
+        match post {
+            Some(post) => Ok(Some(Self::with_tags(&self.conn, post).await?)),
+            None => Ok(None),
+        }
     }
 
-    async fn update_post(&self, post: Post) -> Result<()> {
+    async fn update_post(&self, update: PostUpdate, if_match: &IfMatch) -> Result<Post> {
         let tx = self.conn.begin().await?;
 
+        let current = Self::find_for_update(&tx, update.id.into()).await?;
+        if_match.check(current.version)?;
+        authors::ensure_active(&tx, current.author_id).await?;
+        if update
+            .author_id
+            .is_some_and(|author_id| uuid::Uuid::from(author_id) != current.author_id)
+        {
+            return Err(AppError::ValidationError(
+                "A post cannot be moved to another author".to_string(),
+            ));
+        }
+
         let active_model = entities::post::ActiveModel {
-            id: Unchanged(post.id),
-            title: Set(post.title),
-            author_id: Set(post.author_id),
-            content: Set(post.content),
-            created_at: Unchanged(post.created_at),
+            id: Unchanged(current.id),
+            title: Set(update.title),
+            content: Set(update.content),
+            content_format: Set(update.content_format.unwrap_or(current.content_format)),
+            version: Set(current.version + 1),
+            created_at: Unchanged(current.created_at),
             updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
         };
 
-        let post_model = entities::post::Entity::update(active_model)
-            .exec(&tx)
-            .await?;
+        let post_model =
+            Self::save_update(&tx, &current, active_model, update.tags.as_deref(), None).await?;
+
+        tx.commit().await?;
+
+        Ok(post_model)
+    }
+
+    async fn delete_post(&self, id: PostId) -> Result<()> {
+        let tx = self.conn.begin().await?;
+
+        let post = Self::find_for_update(&tx, uuid::Uuid::from(id)).await?;
+        let now = Utc::now();
+        let post = entities::post::ActiveModel {
+            id: Unchanged(post.id),
+            version: Set(post.version + 1),
+            updated_at: Set(now.into()),
+            deleted_at: Set(Some(now.into())),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
 
         outbox::insert_outbox_event(
             &tx,
             "post",
-            post_model.id,
-            "post_updated",
+            post.id,
+            "post_deleted",
             serde_json::json!({
-                "post_id": post_model.id,
-                "author_id": post_model.author_id,
-                "title": post_model.title
+                "post_id": post.id,
+                "author_id": post.author_id,
+                "deleted_at": post.deleted_at,
             }),
         )
         .await?;
 
         tx.commit().await?;
-
         Ok(())
     }
 
-    async fn delete_post(&self, id: PostId) -> Result<()> {
+    async fn list_deleted_posts(
+        &self,
+        author_id: Option<AuthorId>,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        use entities::post::Column;
+
+        let mut query = entities::post::Entity::find().filter(Column::DeletedAt.is_not_null());
+        if let Some(author_id) = author_id {
+            query = query.filter(Column::AuthorId.eq(uuid::Uuid::from(author_id)));
+        }
+
+        let paginator = query
+            .order_by_desc(Column::DeletedAt)
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_posts = paginator.num_items().await?;
+        let mut posts = paginator.fetch_page(pagination.page - 1).await?;
+        Self::load_tags(&self.conn, &mut posts).await?;
+
+        Ok((posts, total_posts))
+    }
+
+    async fn restore_post(&self, id: PostId) -> Result<Post> {
+        use entities::post::Column;
+
+        let tx = self.conn.begin().await?;
+
         let post = entities::post::Entity::find_by_id(uuid::Uuid::from(id))
+            .filter(Column::DeletedAt.is_not_null())
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Post not found in trash".to_string()))?;
+
+        // Another post may have taken the slug, or kept it as a former one,
+        // while this one was in the trash
+        let base = slug::slugify(&post.title);
+        let mut attempt = 1;
+        let post = loop {
+            let slug = Self::unique_slug(&tx, &post.slug, post.id).await?;
+            let slug = if slug == post.slug {
+                slug
+            } else {
+                Self::unique_slug(&tx, &base, post.id).await?
+            };
+
+            let savepoint = tx.begin().await?;
+            let restored = entities::post::ActiveModel {
+                id: Unchanged(post.id),
+                slug: Set(slug),
+                version: Set(post.version + 1),
+                updated_at: Set(Utc::now().into()),
+                deleted_at: Set(None),
+                ..Default::default()
+            }
+            .update(&savepoint)
+            .await;
+            match restored {
+                Ok(restored) => {
+                    savepoint.commit().await?;
+                    break restored;
+                }
+                Err(e) if attempt < SLUG_ATTEMPTS && is_slug_conflict(&e) => {
+                    savepoint.rollback().await?;
+                    attempt += 1;
+                }
+                Err(e) => return Err(slug_error(e)),
+            }
+        };
+        let post = Self::with_tags(&tx, post).await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "post",
+            post.id,
+            "post_restored",
+            serde_json::json!({
+                "post_id": post.id,
+                "author_id": post.author_id,
+                "status": post.status,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(post)
+    }
+
+    async fn purge_deleted_posts(&self, before: DateTime<Utc>) -> Result<u64> {
+        use entities::post::Column;
+
+        // Revisions, tags, slugs, comments and reactions go with the post via cascades
+        let result = entities::post::Entity::delete_many()
+            .filter(Column::DeletedAt.lt(before))
+            .exec(&self.conn)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn erase_author(
+        &self,
+        author_id: AuthorId,
+        erasure_id: uuid::Uuid,
+    ) -> Result<AuthorErasure> {
+        let user_id = uuid::Uuid::from(author_id);
+        let tx = self.conn.begin().await?;
+
+        let post_ids: Vec<uuid::Uuid> = entities::post::Entity::find()
+            .select_only()
+            .column(entities::post::Column::Id)
+            .filter(entities::post::Column::AuthorId.eq(user_id))
+            .into_tuple()
+            .all(&tx)
+            .await?;
+        // Lets the search index drop them; the posts are gone by the time it looks
+        for post_id in &post_ids {
+            outbox::insert_outbox_event(
+                &tx,
+                "post",
+                *post_id,
+                "post_erased",
+                serde_json::json!({ "post_id": post_id, "author_id": user_id }),
+            )
+            .await?;
+        }
+        // Revisions, tags, slugs, comments, reactions and timeline entries cascade
+        entities::post::Entity::delete_many()
+            .filter(entities::post::Column::AuthorId.eq(user_id))
+            .exec(&tx)
+            .await?;
+
+        let now = Utc::now();
+        let comments_cleared = entities::comment::Entity::update_many()
+            .col_expr(entities::comment::Column::Content, Expr::value(""))
+            .col_expr(entities::comment::Column::UpdatedAt, Expr::value(now))
+            .col_expr(
+                entities::comment::Column::DeletedAt,
+                Expr::col(entities::comment::Column::DeletedAt).if_null(now),
+            )
+            .filter(entities::comment::Column::AuthorId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(entities::comment::Column::Content.ne(""))
+                    .add(entities::comment::Column::DeletedAt.is_null()),
+            )
+            .exec(&tx)
+            .await?
+            .rows_affected;
+
+        let reactions: Vec<(uuid::Uuid, ReactionKind)> = entities::post_reaction::Entity::find()
+            .select_only()
+            .column(entities::post_reaction::Column::PostId)
+            .column(entities::post_reaction::Column::Kind)
+            .filter(entities::post_reaction::Column::UserId.eq(user_id))
+            .into_tuple()
+            .all(&tx)
+            .await?;
+        entities::post_reaction::Entity::delete_many()
+            .filter(entities::post_reaction::Column::UserId.eq(user_id))
+            .exec(&tx)
+            .await?;
+        // Counts of the author's own posts went with them
+        let mut removed = ReactionDeltas::new();
+        for (post_id, kind) in &reactions {
+            if !post_ids.contains(post_id) {
+                *removed
+                    .entry((*post_id).into())
+                    .or_default()
+                    .entry(*kind)
+                    .or_default() -= 1;
+            }
+        }
+        reactions::add_counts(&tx, &removed).await?;
+        let reacted_post_ids: Vec<PostId> = removed.into_keys().collect();
+
+        entities::author_subscription::Entity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(entities::author_subscription::Column::SubscriberId.eq(user_id))
+                    .add(entities::author_subscription::Column::AuthorId.eq(user_id)),
+            )
+            .exec(&tx)
+            .await?;
+        entities::timeline_entry::Entity::delete_many()
+            .filter(entities::timeline_entry::Column::UserId.eq(user_id))
+            .exec(&tx)
+            .await?;
+        entities::author_export::Entity::delete_many()
+            .filter(entities::author_export::Column::AuthorId.eq(user_id))
+            .exec(&tx)
+            .await?;
+
+        let erasure = AuthorErasure {
+            post_ids: post_ids.into_iter().map(PostId::from).collect(),
+            comments_cleared,
+            reacted_post_ids,
+        };
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user_id,
+            "user_erasure_step_completed",
+            serde_json::json!({
+                "erasure_id": erasure_id,
+                "user_id": user_id,
+                "step": "posts",
+                "posts_deleted": erasure.post_ids.len(),
+                "comments_cleared": erasure.comments_cleared,
+                "reactions_removed_from": erasure.reacted_post_ids.len(),
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(erasure)
+    }
+
+    async fn export_author(
+        &self,
+        author_id: AuthorId,
+        export_id: uuid::Uuid,
+    ) -> Result<AuthorExport> {
+        let user_id = uuid::Uuid::from(author_id);
+        let tx = self.conn.begin().await?;
+
+        let export = AuthorExport {
+            posts: entities::post::Entity::find()
+                .filter(entities::post::Column::AuthorId.eq(user_id))
+                .order_by_asc(entities::post::Column::CreatedAt)
+                .all(&tx)
+                .await?,
+            comments: entities::comment::Entity::find()
+                .filter(entities::comment::Column::AuthorId.eq(user_id))
+                .order_by_asc(entities::comment::Column::CreatedAt)
+                .all(&tx)
+                .await?,
+            reactions: entities::post_reaction::Entity::find()
+                .filter(entities::post_reaction::Column::UserId.eq(user_id))
+                .order_by_asc(entities::post_reaction::Column::CreatedAt)
+                .all(&tx)
+                .await?,
+        };
+
+        // The event only points at the part: it can outgrow a Pub/Sub message,
+        // and every subscriber would see it
+        entities::author_export::Entity::insert(entities::author_export::ActiveModel {
+            export_id: Set(export_id),
+            author_id: Set(user_id),
+            data: Set(serde_json::to_value(&export)?),
+            created_at: Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::column(entities::author_export::Column::ExportId)
+                .update_columns([
+                    entities::author_export::Column::Data,
+                    entities::author_export::Column::CreatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(&tx)
+        .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user_id,
+            "user_export_part_ready",
+            serde_json::json!({
+                "export_id": export_id,
+                "user_id": user_id,
+                "part": "posts",
+                "path": export::part_path(export_id),
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(export)
+    }
+
+    async fn get_export(&self, export_id: uuid::Uuid) -> Result<Option<serde_json::Value>> {
+        Ok(entities::author_export::Entity::find_by_id(export_id)
             .one(&self.conn)
+            .await?
+            .map(|export| export.data))
+    }
+
+    async fn set_author_status(
+        &self,
+        author_id: AuthorId,
+        status: AccountStatus,
+        version: i32,
+    ) -> Result<Vec<PostId>> {
+        let user_id = uuid::Uuid::from(author_id);
+        let tx = self.conn.begin().await?;
+
+        let current = author_status::Entity::find_by_id(user_id)
+            .lock_exclusive()
+            .one(&tx)
+            .await?;
+        let was_active = match &current {
+            // Events can arrive out of order; keep the latest status
+            Some(current) if current.version >= version => return Ok(Vec::new()),
+            Some(current) => current.status == AccountStatus::Active,
+            None => true,
+        };
+
+        let row = author_status::ActiveModel {
+            author_id: Set(user_id),
+            status: Set(status),
+            version: Set(version),
+            changed_at: Set(Utc::now().into()),
+        };
+        match current {
+            Some(_) => row.update(&tx).await?,
+            None => row.insert(&tx).await?,
+        };
+
+        let post_ids = if was_active == (status == AccountStatus::Active) {
+            Vec::new()
+        } else {
+            entities::post::Entity::find()
+                .select_only()
+                .column(entities::post::Column::Id)
+                .filter(entities::post::Column::AuthorId.eq(user_id))
+                .into_tuple::<uuid::Uuid>()
+                .all(&tx)
+                .await?
+                .into_iter()
+                .map(PostId::from)
+                .collect()
+        };
+        // Lets the search index drop or re-add them; it reloads each post
+        for post_id in &post_ids {
+            outbox::insert_outbox_event(
+                &tx,
+                "post",
+                (*post_id).into(),
+                "post_visibility_changed",
+                serde_json::json!({
+                    "post_id": post_id,
+                    "author_id": user_id,
+                    "visible": status == AccountStatus::Active,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(post_ids)
+    }
+
+    async fn list_posts(
+        &self,
+        filter: &PostFilter,
+        sort: &PostSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Post>, u64)> {
+        use entities::post::Column;
+
+        let mut query = Self::visible();
+
+        if let Some(status) = filter.status {
+            query = query.filter(Column::Status.eq(status));
+        }
+        if let Some(author_id) = filter.author_id {
+            query = query.filter(Column::AuthorId.eq(author_id));
+        }
+        if let Some(created_after) = filter.created_after {
+            query = query.filter(Column::CreatedAt.gte(created_after));
+        }
+        if let Some(created_before) = filter.created_before {
+            query = query.filter(Column::CreatedAt.lt(created_before));
+        }
+        if let Some(ref title) = filter.title {
+            query = query.filter(Column::Title.ilike(format!("%{}%", escape_like(title))));
+        }
+        if let Some(ref tag) = filter.tag {
+            query = query.filter(
+                Column::Id.in_subquery(
+                    Query::select()
+                        .column(post_tag::Column::PostId)
+                        .from(post_tag::Entity)
+                        .inner_join(
+                            tag::Entity,
+                            Expr::col((tag::Entity, tag::Column::Id))
+                                .equals((post_tag::Entity, post_tag::Column::TagId)),
+                        )
+                        .and_where(Expr::col((tag::Entity, tag::Column::Name)).eq(tag.as_str()))
+                        .to_owned(),
+                ),
+            );
+        }
+
+        let sort_column = match sort.sort_by {
+            PostSortField::CreatedAt => Column::CreatedAt,
+            PostSortField::UpdatedAt => Column::UpdatedAt,
+            PostSortField::PublishedAt => Column::PublishedAt,
+            PostSortField::Title => Column::Title,
+        };
+
+        let paginator = query
+            .order_by(sort_column, sort.order.into())
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_posts = paginator.num_items().await?;
+        let mut posts = paginator.fetch_page(pagination.page - 1).await?;
+        Self::load_tags(&self.conn, &mut posts).await?;
+
+        Ok((posts, total_posts))
+    }
+
+    async fn search_posts(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostSearchHit>, u64)> {
+        let backend = self.conn.get_database_backend();
+
+        let total_hits: i64 = self
+            .conn
+            .query_one_raw(Statement::from_sql_and_values(
+                backend,
+                "SELECT COUNT(*) AS total FROM posts \
+                 WHERE status = 'published' AND deleted_at IS NULL \
+                 AND author_id NOT IN (SELECT author_id FROM author_statuses WHERE status <> 'active') \
+                 AND search_vector @@ websearch_to_tsquery('english', $1)",
+                [query.into()],
+            ))
+            .await?
+            .map(|row| row.try_get("", "total"))
+            .transpose()?
+            .unwrap_or(0);
+
+        // Content is escaped before highlighting so snippets are safe to render as HTML
+        let rows = self
+            .conn
+            .query_all_raw(Statement::from_sql_and_values(
+                backend,
+                "SELECT posts.*, \
+                    ts_rank(search_vector, query) AS rank, \
+                    ts_headline('english', \
+                        replace(replace(replace(content, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), \
+                        query, \
+                        'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2' \
+                    ) AS snippet \
+                 FROM posts, websearch_to_tsquery('english', $1) AS query \
+                 WHERE status = 'published' AND deleted_at IS NULL AND search_vector @@ query \
+                 AND author_id NOT IN (SELECT author_id FROM author_statuses WHERE status <> 'active') \
+                 ORDER BY rank DESC, published_at DESC, id \
+                 LIMIT $2 OFFSET $3",
+                [
+                    query.into(),
+                    (pagination.page_size as i64).into(),
+                    (((pagination.page - 1) * pagination.page_size) as i64).into(),
+                ],
+            ))
             .await?;
 
-        if post.is_none() {
-            return Err(common::error::AppError::NotFoundError(
-                "Post not found".to_string(),
+        let mut posts = Vec::with_capacity(rows.len());
+        let mut scores = Vec::with_capacity(rows.len());
+        for row in rows {
+            posts.push(Post::from_query_result(&row, "")?);
+            scores.push((
+                row.try_get::<f32>("", "rank")?,
+                row.try_get::<String>("", "snippet")?,
             ));
         }
+        Self::load_tags(&self.conn, &mut posts).await?;
 
-        post.unwrap().delete(&self.conn).await?;
-        Ok(())
+        let hits = posts
+            .into_iter()
+            .zip(scores)
+            .map(|(post, (rank, snippet))| PostSearchHit {
+                post,
+                rank,
+                snippet,
+            })
+            .collect();
+
+        Ok((hits, total_hits as u64))
+    }
+
+    async fn change_status(&self, id: PostId, transition: PostTransition) -> Result<Post> {
+        let tx = self.conn.begin().await?;
+
+        let post = Self::find_for_update(&tx, id.into()).await?;
+
+        let post = Self::save_transition(&tx, post, transition, Utc::now()).await?;
+
+        tx.commit().await?;
+
+        Ok(post)
+    }
+
+    async fn publish_due_posts(&self, now: DateTime<Utc>) -> Result<Vec<Post>> {
+        use entities::post::Column;
+
+        let tx = self.conn.begin().await?;
+
+        let due = Self::live()
+            .filter(Column::Status.eq(PostStatus::Scheduled))
+            .filter(Column::PublishAt.lte(now))
+            .order_by_asc(Column::PublishAt)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .all(&tx)
+            .await?;
+
+        let mut published = Vec::with_capacity(due.len());
+        for post in due {
+            published.push(Self::save_transition(&tx, post, PostTransition::Publish, now).await?);
+        }
+
+        tx.commit().await?;
+
+        Ok(published)
+    }
+
+    async fn list_revisions(
+        &self,
+        id: PostId,
+        pagination: &Pagination,
+    ) -> Result<(Vec<PostRevision>, u64)> {
+        use entities::post_revision::Column;
+
+        let paginator = entities::post_revision::Entity::find()
+            .filter(Column::PostId.eq(uuid::Uuid::from(id)))
+            .order_by_desc(Column::Revision)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_revisions = paginator.num_items().await?;
+        let revisions = paginator.fetch_page(pagination.page - 1).await?;
+
+        Ok((revisions, total_revisions))
+    }
+
+    async fn get_revision(&self, id: PostId, revision: i32) -> Result<Option<PostRevision>> {
+        use entities::post_revision::Column;
+
+        let revision = entities::post_revision::Entity::find()
+            .filter(Column::PostId.eq(uuid::Uuid::from(id)))
+            .filter(Column::Revision.eq(revision))
+            .one(&self.conn)
+            .await?;
+        Ok(revision)
     }
 
-    async fn list_posts(&self) -> Result<Option<Vec<Post>>> {
-        let posts = entities::post::Entity::find().all(&self.conn).await?;
-        Ok(Some(posts))
+    async fn restore_revision(&self, id: PostId, revision: i32) -> Result<Post> {
+        use entities::post_revision::Column;
+
+        let tx = self.conn.begin().await?;
+
+        let current = Self::find_for_update(&tx, id.into()).await?;
+
+        let revision = entities::post_revision::Entity::find()
+            .filter(Column::PostId.eq(uuid::Uuid::from(id)))
+            .filter(Column::Revision.eq(revision))
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Revision not found".to_string()))?;
+
+        let active_model = entities::post::ActiveModel {
+            id: Unchanged(revision.post_id),
+            title: Set(revision.title),
+            content: Set(revision.content),
+            version: Set(current.version + 1),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        };
+
+        let post_model =
+            Self::save_update(&tx, &current, active_model, None, Some(revision.revision)).await?;
+
+        tx.commit().await?;
+
+        Ok(post_model)
+    }
+
+    async fn list_tags(&self, pagination: &Pagination) -> Result<(Vec<TagCount>, u64)> {
+        let paginator = Self::visible()
+            .select_only()
+            .column(tag::Column::Name)
+            .column_as(post_tag::Column::PostId.count(), "post_count")
+            .join_rev(JoinType::InnerJoin, post_tag::Relation::Post.def())
+            .join(JoinType::InnerJoin, post_tag::Relation::Tag.def())
+            .filter(entities::post::Column::Status.eq(PostStatus::Published))
+            .group_by(tag::Column::Id)
+            .group_by(tag::Column::Name)
+            .order_by(Expr::col("post_count"), Order::Desc)
+            .order_by_asc(tag::Column::Name)
+            .into_tuple::<(String, i64)>()
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_tags = paginator.num_items().await?;
+        let tags = paginator
+            .fetch_page(pagination.page - 1)
+            .await?
+            .into_iter()
+            .map(|(name, post_count)| TagCount { name, post_count })
+            .collect();
+
+        Ok((tags, total_tags))
     }
 }
@@ -0,0 +1,83 @@
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use uuid::Uuid;
+
+use crate::domain::{DynPostRepository, DynReactionRepository};
+
+/// Erases a user's posts, comments, reactions and feed state when the users
+/// service asks for it, and acknowledges the erasure back to it.
+#[derive(Debug, Clone)]
+pub struct UserEraser {
+    posts: DynPostRepository,
+    reactions: DynReactionRepository,
+}
+
+impl UserEraser {
+    pub fn new(posts: DynPostRepository, reactions: DynReactionRepository) -> Self {
+        Self { posts, reactions }
+    }
+
+    /// Plain deletions carry no `erasure_id`: the user can still be restored,
+    /// so nothing goes until the users service's trash purge erases them.
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        if (event.aggregate_type.as_str(), event.event_type.as_str()) != ("user", "user_deleted") {
+            return Ok(());
+        }
+        let Some(erasure_id) = event.payload["erasure_id"]
+            .as_str()
+            .and_then(|id| id.parse::<Uuid>().ok())
+        else {
+            return Ok(());
+        };
+
+        // Counts are decremented in the database, so the user's buffered
+        // reactions have to land there first
+        self.reactions.flush_reaction_counts().await?;
+        let erasure = self
+            .posts
+            .erase_author(event.aggregate_id.into(), erasure_id)
+            .await?;
+        self.reactions
+            .invalidate_reaction_counts(&erasure.reacted_post_ids)
+            .await
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("User eraser started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let eraser = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match eraser.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to erase user for event {}: {:?}",
+                                    event.id,
+                                    e
+                                );
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start user eraser: {}", e);
+            }
+        })
+    }
+}
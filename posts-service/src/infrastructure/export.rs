@@ -0,0 +1,76 @@
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use uuid::Uuid;
+
+use crate::domain::DynPostRepository;
+
+/// Answers data export requests from the users service with the user's posts,
+/// comments and reactions.
+#[derive(Debug, Clone)]
+pub struct UserExporter {
+    posts: DynPostRepository,
+}
+
+impl UserExporter {
+    pub fn new(posts: DynPostRepository) -> Self {
+        Self { posts }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        if (event.aggregate_type.as_str(), event.event_type.as_str())
+            != ("user", "user_export_requested")
+        {
+            return Ok(());
+        }
+        let Some(export_id) = event.payload["export_id"]
+            .as_str()
+            .and_then(|id| id.parse::<Uuid>().ok())
+        else {
+            return Ok(());
+        };
+
+        self.posts
+            .export_author(event.aggregate_id.into(), export_id)
+            .await?;
+        Ok(())
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("User exporter started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let exporter = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match exporter.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to export user for event {}: {:?}",
+                                    event.id,
+                                    e
+                                );
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start user exporter: {}", e);
+            }
+        })
+    }
+}
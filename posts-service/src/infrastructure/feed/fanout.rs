@@ -0,0 +1,138 @@
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use uuid::Uuid;
+
+use crate::domain::{
+    AuthorId, DynFeedRepository, DynPostRepository, DynTimelineStore, PostStatus, TimelineEntry,
+    UserId, feed::SUBSCRIPTION_BACKFILL,
+};
+
+/// Pushes newly published posts into their author's subscribers' timelines
+/// and mirrors follows from the users service.
+///
+/// Authors with at least `hot_author_threshold` subscribers are skipped when
+/// pushing; [`super::Feed`] merges their posts in at read time instead.
+#[derive(Debug, Clone)]
+pub struct FeedFanout {
+    feeds: DynFeedRepository,
+    timelines: DynTimelineStore,
+    posts: DynPostRepository,
+    hot_author_threshold: u64,
+}
+
+impl FeedFanout {
+    pub fn new(
+        feeds: DynFeedRepository,
+        timelines: DynTimelineStore,
+        posts: DynPostRepository,
+        hot_author_threshold: u64,
+    ) -> Self {
+        Self {
+            feeds,
+            timelines,
+            posts,
+            hot_author_threshold,
+        }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        match (event.aggregate_type.as_str(), event.event_type.as_str()) {
+            ("post", "post_published") => self.fan_out_post(event.aggregate_id).await,
+            ("user", "user_followed") => match Self::follow_edge(event) {
+                Some((follower, followee)) => self.subscribe(follower, followee).await,
+                None => Ok(()),
+            },
+            ("user", "user_unfollowed") => match Self::follow_edge(event) {
+                Some((follower, followee)) => {
+                    self.feeds.unsubscribe(follower, followee).await?;
+                    Ok(())
+                }
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// The follower and the followed author named by a follow event.
+    fn follow_edge(event: &OutBoxEvent) -> Option<(UserId, AuthorId)> {
+        let id = |field: &str| event.payload[field].as_str()?.parse::<Uuid>().ok();
+        Some((id("follower_id")?.into(), id("followee_id")?.into()))
+    }
+
+    async fn is_hot(&self, author_id: AuthorId) -> Result<bool> {
+        Ok(self.feeds.subscriber_count(author_id).await? >= self.hot_author_threshold)
+    }
+
+    /// Reloads the post so redelivered events for posts unpublished in the
+    /// meantime push nothing.
+    async fn fan_out_post(&self, id: Uuid) -> Result<()> {
+        let Some(post) = self.posts.get_post(id.into()).await? else {
+            return Ok(());
+        };
+        if post.status != PostStatus::Published {
+            return Ok(());
+        }
+        let Some(entry) = TimelineEntry::for_post(&post) else {
+            return Ok(());
+        };
+
+        let author_id = AuthorId::from(post.author_id);
+        if self.is_hot(author_id).await? {
+            return Ok(());
+        }
+        let subscribers = self.feeds.subscribers(author_id).await?;
+        self.timelines.push(&subscribers, &[entry]).await
+    }
+
+    /// Subscribes and backfills the author's latest posts, so a new follow
+    /// shows up in the feed without waiting for the author's next post.
+    async fn subscribe(&self, subscriber_id: UserId, author_id: AuthorId) -> Result<()> {
+        if !self.feeds.subscribe(subscriber_id, author_id).await? || self.is_hot(author_id).await? {
+            return Ok(());
+        }
+
+        let recent = self
+            .feeds
+            .recent_posts(&[author_id], None, SUBSCRIPTION_BACKFILL)
+            .await?;
+        if recent.is_empty() {
+            return Ok(());
+        }
+        self.timelines.push(&[subscriber_id], &recent).await
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Feed fan-out started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let fanout = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match fanout.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to fan out event {}: {:?}", event.id, e);
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start feed fan-out: {}", e);
+            }
+        })
+    }
+}
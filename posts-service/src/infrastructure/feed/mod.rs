@@ -0,0 +1,7 @@
+mod fanout;
+mod reader;
+mod redis;
+
+pub use fanout::FeedFanout;
+pub use reader::Feed;
+pub use redis::RedisTimelineStore;
@@ -0,0 +1,94 @@
+use std::collections::{HashMap, HashSet};
+
+use common::error::Result;
+
+use crate::domain::{
+    AuthorId, DynFeedRepository, DynPostRepository, DynTimelineStore, FeedCursor, FeedPage,
+    PostStatus, TimelineEntry, UserId,
+};
+
+/// Builds home feeds from pushed timelines plus the posts of hot authors,
+/// which are never pushed and are merged in when the feed is read.
+#[derive(Debug, Clone)]
+pub struct Feed {
+    feeds: DynFeedRepository,
+    timelines: DynTimelineStore,
+    posts: DynPostRepository,
+    hot_author_threshold: u64,
+}
+
+impl Feed {
+    pub fn new(
+        feeds: DynFeedRepository,
+        timelines: DynTimelineStore,
+        posts: DynPostRepository,
+        hot_author_threshold: u64,
+    ) -> Self {
+        Self {
+            feeds,
+            timelines,
+            posts,
+            hot_author_threshold,
+        }
+    }
+
+    /// Posts by authors `user_id` follows, newest first.
+    ///
+    /// Entries whose post has since been unpublished or deleted, or whose
+    /// author was unfollowed, are dropped from the page, so a page can hold
+    /// fewer than `limit` posts while `next_cursor` is still set.
+    pub async fn page(
+        &self,
+        user_id: UserId,
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<FeedPage> {
+        let mut entries = self.timelines.page(user_id, before, limit).await?;
+
+        let hot = self
+            .feeds
+            .hot_subscriptions(user_id, self.hot_author_threshold)
+            .await?;
+        if !hot.is_empty() {
+            entries.extend(self.feeds.recent_posts(&hot, before, limit).await?);
+            entries.sort_by(TimelineEntry::newest_first);
+            entries.dedup_by_key(|entry| entry.post_id);
+            entries.truncate(limit as usize);
+        }
+
+        let next_cursor = match entries.last() {
+            Some(last) if entries.len() as u64 == limit => Some(last.cursor()),
+            _ => None,
+        };
+
+        let mut following: HashMap<AuthorId, bool> = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut posts = Vec::with_capacity(entries.len());
+        for entry in entries {
+            if !seen.insert(entry.post_id) {
+                continue;
+            }
+            let Some(post) = self.posts.get_post(entry.post_id.into()).await? else {
+                continue;
+            };
+            if post.status != PostStatus::Published {
+                continue;
+            }
+
+            let author_id = AuthorId::from(post.author_id);
+            let follows = match following.get(&author_id) {
+                Some(follows) => *follows,
+                None => {
+                    let follows = self.feeds.is_subscribed(user_id, author_id).await?;
+                    following.insert(author_id, follows);
+                    follows
+                }
+            };
+            if follows {
+                posts.push(post);
+            }
+        }
+
+        Ok(FeedPage { posts, next_cursor })
+    }
+}
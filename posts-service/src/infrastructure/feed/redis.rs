@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common::error::{AppError, Result};
+use redis::{AsyncCommands, Script};
+use uuid::Uuid;
+
+use crate::domain::{
+    DynTimelineStore, FeedCursor, TimelineEntry, TimelineStore, UserId, feed::MAX_TIMELINE_LEN,
+};
+
+/// Timelines nobody reads fall out of Redis after this long and are reloaded on demand.
+const TIMELINE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Adds entries to a timeline that is already cached and trims it to its
+/// maximum length. Timelines that are not cached are left alone; they are
+/// loaded from Postgres in full the next time they are read.
+const PUSH_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return 0
+end
+for i = 2, #ARGV, 2 do
+    redis.call('ZADD', KEYS[1], ARGV[i], ARGV[i + 1])
+end
+redis.call('ZREMRANGEBYRANK', KEYS[1], 0, -(tonumber(ARGV[1]) + 1))
+return 1
+"#;
+
+/// Keeps the newest part of each timeline in a Redis sorted set in front of
+/// the Postgres store, which stays the source of truth.
+///
+/// Members are post ids scored by publication time in microseconds, so equal
+/// scores order by post id just like the database does. Redis failures are
+/// logged and the request is served from Postgres instead.
+#[derive(Debug)]
+pub struct RedisTimelineStore {
+    client: redis::Client,
+    store: DynTimelineStore,
+    push_script: Script,
+}
+
+impl RedisTimelineStore {
+    pub fn new(url: &str, store: DynTimelineStore) -> Result<Self> {
+        let client =
+            redis::Client::open(url).map_err(|e| AppError::InvalidConfiguration(e.to_string()))?;
+        Ok(Self {
+            client,
+            store,
+            push_script: Script::new(PUSH_SCRIPT),
+        })
+    }
+
+    fn key(user_id: UserId) -> String {
+        format!("timeline:{}", Uuid::from(user_id))
+    }
+
+    async fn push_cached(
+        &self,
+        user_ids: &[UserId],
+        entries: &[TimelineEntry],
+    ) -> redis::RedisResult<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        for user_id in user_ids {
+            let mut invocation = self.push_script.key(Self::key(*user_id));
+            invocation.arg(MAX_TIMELINE_LEN);
+            for entry in entries {
+                invocation.arg(entry.score()).arg(entry.post_id.to_string());
+            }
+            let _: i64 = invocation.invoke_async(&mut conn).await?;
+        }
+        Ok(())
+    }
+
+    /// Serves a page from Redis, loading the timeline from Postgres first if it
+    /// is not cached. Returns `None` when the page reaches past what Redis holds.
+    async fn page_cached(
+        &self,
+        user_id: UserId,
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> anyhow::Result<Option<Vec<TimelineEntry>>> {
+        let key = Self::key(user_id);
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+
+        if !conn.exists::<_, bool>(&key).await? {
+            let entries = self
+                .store
+                .page(user_id, None, MAX_TIMELINE_LEN as u64)
+                .await?;
+            self.load(&mut conn, &key, &entries).await?;
+
+            let complete = entries.len() < MAX_TIMELINE_LEN;
+            let page: Vec<_> = entries
+                .into_iter()
+                .filter(|entry| before.is_none_or(|cursor| entry.is_after(cursor)))
+                .take(limit as usize)
+                .collect();
+            return Ok((complete || page.len() as u64 == limit).then_some(page));
+        }
+
+        let max = before.map_or("+inf".to_string(), |cursor| {
+            TimelineEntry {
+                post_id: cursor.post_id,
+                published_at: cursor.published_at,
+            }
+            .score()
+            .to_string()
+        });
+        let batch = limit.max(1) as isize;
+        let mut page = Vec::new();
+        let mut offset = 0;
+        loop {
+            let members: Vec<(String, f64)> = conn
+                .zrevrangebyscore_limit_withscores(&key, &max, "-inf", offset, batch)
+                .await?;
+            let fetched = members.len() as isize;
+            // Entries sharing the cursor's timestamp are returned again and skipped here
+            page.extend(
+                members
+                    .into_iter()
+                    .filter_map(|(member, score)| {
+                        TimelineEntry::from_score(member.parse().ok()?, score)
+                    })
+                    .filter(|entry| before.is_none_or(|cursor| entry.is_after(cursor))),
+            );
+            if page.len() as u64 >= limit || fetched < batch {
+                break;
+            }
+            offset += fetched;
+        }
+        page.truncate(limit as usize);
+
+        if (page.len() as u64) < limit && conn.zcard::<_, usize>(&key).await? >= MAX_TIMELINE_LEN {
+            return Ok(None);
+        }
+        Ok(Some(page))
+    }
+
+    async fn load(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        key: &str,
+        entries: &[TimelineEntry],
+    ) -> redis::RedisResult<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(key).ignore();
+        if !entries.is_empty() {
+            let members: Vec<_> = entries
+                .iter()
+                .map(|entry| (entry.score(), entry.post_id.to_string()))
+                .collect();
+            pipe.zadd_multiple(key, &members)
+                .ignore()
+                .expire(key, TIMELINE_TTL.as_secs() as i64)
+                .ignore();
+        }
+        pipe.query_async(conn).await
+    }
+}
+
+#[async_trait]
+impl TimelineStore for RedisTimelineStore {
+    async fn push(&self, user_ids: &[UserId], entries: &[TimelineEntry]) -> Result<()> {
+        self.store.push(user_ids, entries).await?;
+
+        if let Err(e) = self.push_cached(user_ids, entries).await {
+            tracing::warn!("Failed to push to cached timelines: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn page(
+        &self,
+        user_id: UserId,
+        before: Option<&FeedCursor>,
+        limit: u64,
+    ) -> Result<Vec<TimelineEntry>> {
+        match self.page_cached(user_id, before, limit).await {
+            Ok(Some(page)) => return Ok(page),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read cached timeline: {:?}", e),
+        }
+        self.store.page(user_id, before, limit).await
+    }
+}
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use axum::Router;
 
 use crate::presentation::{
-    routes::{health_check_router, posts_router},
+    routes::{
+        admin_router, exports_router, feed_router, health_check_router, posts_router,
+        search_index_router, tags_router,
+    },
     state::AppState,
 };
 
@@ -13,4 +16,9 @@ pub fn create_router(state: AppState) -> Router {
     Router::new()
         .merge(health_check_router(state.clone()))
         .nest("/posts", posts_router(state.clone()))
+        .nest("/tags", tags_router(state.clone()))
+        .nest("/search", search_index_router(state.clone()))
+        .nest("/feed", feed_router(state.clone()))
+        .nest("/exports", exports_router(state.clone()))
+        .nest("/admin", admin_router(state.clone()))
 }
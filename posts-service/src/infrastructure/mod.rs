@@ -1,2 +1,10 @@
 pub mod database;
+pub mod erasure;
+pub mod export;
+pub mod feed;
 pub mod http;
+pub mod moderation;
+pub mod purge;
+pub mod reactions;
+pub mod scheduler;
+pub mod search;
@@ -0,0 +1,79 @@
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+
+use crate::domain::{AccountStatus, DynPostRepository};
+
+/// Tracks account status changes announced by the users service, so posts
+/// by suspended, banned or deactivated authors are hidden until they are
+/// active again.
+#[derive(Debug, Clone)]
+pub struct AuthorStatusTracker {
+    posts: DynPostRepository,
+}
+
+impl AuthorStatusTracker {
+    pub fn new(posts: DynPostRepository) -> Self {
+        Self { posts }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        if (event.aggregate_type.as_str(), event.event_type.as_str())
+            != ("user", "user_status_changed")
+        {
+            return Ok(());
+        }
+        let (Ok(status), Some(version)) = (
+            serde_json::from_value::<AccountStatus>(event.payload["status"].clone()),
+            event.payload["version"]
+                .as_i64()
+                .and_then(|version| i32::try_from(version).ok()),
+        ) else {
+            tracing::warn!(event_id = %event.id, "Ignoring malformed status event");
+            return Ok(());
+        };
+
+        self.posts
+            .set_author_status(event.aggregate_id.into(), status, version)
+            .await?;
+        Ok(())
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Author status tracker started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let tracker = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match tracker.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to record author status for event {}: {:?}",
+                                    event.id,
+                                    e
+                                );
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start author status tracker: {}", e);
+            }
+        })
+    }
+}
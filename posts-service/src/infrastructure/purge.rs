@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::domain::DynPostRepository;
+
+/// Permanently removes posts that have been in the trash longer than the retention window.
+pub struct TrashPurger {
+    posts: DynPostRepository,
+    retention: chrono::Duration,
+    interval: Duration,
+}
+
+impl TrashPurger {
+    pub fn new(posts: DynPostRepository, retention: chrono::Duration, interval: Duration) -> Self {
+        Self {
+            posts,
+            retention,
+            interval,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Trash purger started");
+            loop {
+                let before = Utc::now() - self.retention;
+                if let Err(e) = self.posts.purge_deleted_posts(before).await {
+                    tracing::error!("Trash purger error: {:?}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use crate::domain::DynReactionRepository;
+
+/// Periodically writes reaction counts buffered in the cache layer back to Postgres.
+pub struct ReactionCountFlusher {
+    reactions: DynReactionRepository,
+    interval: Duration,
+}
+
+impl ReactionCountFlusher {
+    pub fn new(reactions: DynReactionRepository, interval: Duration) -> Self {
+        Self {
+            reactions,
+            interval,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Reaction count flusher started");
+            loop {
+                tokio::time::sleep(self.interval).await;
+                if let Err(e) = self.reactions.flush_reaction_counts().await {
+                    tracing::error!("Reaction count flush error: {:?}", e);
+                }
+            }
+        })
+    }
+}
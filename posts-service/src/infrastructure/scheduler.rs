@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::domain::DynPostRepository;
+
+pub struct PostScheduler {
+    posts: DynPostRepository,
+    poll_interval: Duration,
+}
+
+impl PostScheduler {
+    pub fn new(posts: DynPostRepository, poll_interval: Duration) -> Self {
+        Self {
+            posts,
+            poll_interval,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Post scheduler started");
+            loop {
+                if let Err(e) = self.posts.publish_due_posts(Utc::now()).await {
+                    tracing::error!("Post scheduler error: {:?}", e);
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+}
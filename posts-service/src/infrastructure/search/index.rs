@@ -0,0 +1,520 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use common::{
+    config::SearchSettings,
+    error::{AppError, Result},
+    pagination::Pagination,
+};
+use tantivy::{
+    DocAddress, Index, IndexReader, IndexWriter, ReloadPolicy, Searcher, TantivyDocument, Term,
+    collector::{Count, FacetCollector, TopDocs},
+    directory::MmapDirectory,
+    query::{BooleanQuery, BoostQuery, EmptyQuery, FuzzyTermQuery, Occur, Query, TermQuery},
+    schema::{Facet, FacetOptions, Field, IndexRecordOption, STORED, STRING, Schema, TEXT, Value},
+    snippet::SnippetGenerator,
+};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::domain::{
+    AuthorFacet, DynPostRepository, IndexedPostHit, Post, PostFilter, PostSort, PostSortField,
+    PostStatus, SearchFacets, SearchIndexFilter, SearchIndexResults, TagFacet, content,
+};
+
+const KIND_POST: &str = "post";
+const KIND_AUTHOR: &str = "author";
+const TITLE_BOOST: f32 = 2.0;
+const MAX_FACETS: usize = 20;
+const SNIPPET_MAX_CHARS: usize = 200;
+const REBUILD_PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    kind: Field,
+    id: Field,
+    title: Field,
+    slug: Field,
+    author_id: Field,
+    content: Field,
+    tags: Field,
+    tag_names: Field,
+    author: Field,
+    published_at: Field,
+    username: Field,
+}
+
+impl Fields {
+    fn schema() -> Schema {
+        let mut builder = Schema::builder();
+        builder.add_text_field("kind", STRING);
+        builder.add_text_field("id", STRING | STORED);
+        builder.add_text_field("title", TEXT | STORED);
+        builder.add_text_field("slug", STORED);
+        builder.add_text_field("author_id", STRING | STORED);
+        builder.add_text_field("content", TEXT | STORED);
+        builder.add_facet_field("tags", FacetOptions::default());
+        builder.add_text_field("tag_names", STORED);
+        builder.add_facet_field("author", FacetOptions::default());
+        builder.add_text_field("published_at", STORED);
+        builder.add_text_field("username", STORED);
+        builder.build()
+    }
+
+    fn from_schema(schema: &Schema) -> tantivy::Result<Self> {
+        Ok(Self {
+            kind: schema.get_field("kind")?,
+            id: schema.get_field("id")?,
+            title: schema.get_field("title")?,
+            slug: schema.get_field("slug")?,
+            author_id: schema.get_field("author_id")?,
+            content: schema.get_field("content")?,
+            tags: schema.get_field("tags")?,
+            tag_names: schema.get_field("tag_names")?,
+            author: schema.get_field("author")?,
+            published_at: schema.get_field("published_at")?,
+            username: schema.get_field("username")?,
+        })
+    }
+}
+
+/// On-disk Tantivy index of published posts, plus one document per known author so
+/// author facets can carry usernames.
+///
+/// Writes are serialized through a single writer and committed immediately, so a
+/// search issued after a write returns sees it.
+#[derive(Clone)]
+pub struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: Arc<Mutex<IndexWriter>>,
+    fields: Fields,
+}
+
+impl std::fmt::Debug for SearchIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SearchIndex {
+    pub fn open(settings: &SearchSettings) -> Result<Self> {
+        std::fs::create_dir_all(&settings.index_path).map_err(index_error)?;
+        let directory = MmapDirectory::open(&settings.index_path).map_err(index_error)?;
+        let index = Index::open_or_create(directory, Fields::schema()).map_err(index_error)?;
+        let fields = Fields::from_schema(&index.schema()).map_err(index_error)?;
+
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()
+            .map_err(index_error)?;
+        let writer = index
+            .writer_with_num_threads(1, settings.writer_memory_mb * 1_000_000)
+            .map_err(index_error)?;
+
+        Ok(Self {
+            index,
+            reader,
+            writer: Arc::new(Mutex::new(writer)),
+            fields,
+        })
+    }
+
+    /// Number of posts currently indexed.
+    pub fn num_posts(&self) -> Result<u64> {
+        let searcher = self.reader.searcher();
+        let count = searcher
+            .search(&self.term_query(self.fields.kind, KIND_POST), &Count)
+            .map_err(index_error)?;
+        Ok(count as u64)
+    }
+
+    /// Replaces the indexed copy of `post`. Posts that are not published are removed.
+    pub async fn index_post(&self, post: &Post) -> Result<()> {
+        let writer = self.writer.clone().lock_owned().await;
+        writer.delete_term(Term::from_field_text(self.fields.id, &post.id.to_string()));
+        if post.status == PostStatus::Published
+            && let Err(e) = writer.add_document(self.post_document(post))
+        {
+            self.rollback(writer).await?;
+            return Err(index_error(e));
+        }
+        self.commit(writer).await
+    }
+
+    pub async fn remove_post(&self, id: Uuid) -> Result<()> {
+        let writer = self.writer.clone().lock_owned().await;
+        writer.delete_term(Term::from_field_text(self.fields.id, &id.to_string()));
+        self.commit(writer).await
+    }
+
+    pub async fn index_author(&self, author_id: Uuid, username: &str) -> Result<()> {
+        let mut document = TantivyDocument::default();
+        document.add_text(self.fields.kind, KIND_AUTHOR);
+        document.add_text(self.fields.id, author_id.to_string());
+        document.add_text(self.fields.author_id, author_id.to_string());
+        document.add_text(self.fields.username, username);
+
+        let writer = self.writer.clone().lock_owned().await;
+        writer.delete_term(Term::from_field_text(
+            self.fields.id,
+            &author_id.to_string(),
+        ));
+        writer.add_document(document).map_err(index_error)?;
+        self.commit(writer).await
+    }
+
+    pub async fn remove_author(&self, author_id: Uuid) -> Result<()> {
+        let writer = self.writer.clone().lock_owned().await;
+        writer.delete_term(Term::from_field_text(
+            self.fields.id,
+            &author_id.to_string(),
+        ));
+        self.commit(writer).await
+    }
+
+    /// Drops every indexed post and replays the published posts from the repository.
+    /// The swap is a single commit, so searches keep seeing the old posts until it lands.
+    /// Author documents are kept since the posts table knows nothing about usernames.
+    pub async fn rebuild(&self, posts: &DynPostRepository) -> Result<u64> {
+        let writer = self.writer.clone().lock_owned().await;
+        writer.delete_term(Term::from_field_text(self.fields.kind, KIND_POST));
+
+        let indexed = match self.replay_posts(&writer, posts).await {
+            Ok(indexed) => indexed,
+            Err(e) => {
+                self.rollback(writer).await?;
+                return Err(e);
+            }
+        };
+        self.commit(writer).await?;
+        tracing::info!("Search index rebuilt with {} posts", indexed);
+        Ok(indexed)
+    }
+
+    async fn replay_posts(&self, writer: &IndexWriter, posts: &DynPostRepository) -> Result<u64> {
+        let filter = PostFilter {
+            status: Some(PostStatus::Published),
+            ..Default::default()
+        };
+        let sort = PostSort {
+            sort_by: PostSortField::CreatedAt,
+            order: common::pagination::SortOrder::Asc,
+        };
+        let mut pagination = Pagination {
+            page: 1,
+            page_size: REBUILD_PAGE_SIZE,
+        };
+        let mut indexed = 0;
+
+        loop {
+            let (page, _) = posts.list_posts(&filter, &sort, &pagination).await?;
+            for post in &page {
+                writer
+                    .add_document(self.post_document(post))
+                    .map_err(index_error)?;
+            }
+            indexed += page.len() as u64;
+            if (page.len() as u64) < pagination.page_size {
+                return Ok(indexed);
+            }
+            pagination.page += 1;
+        }
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        filter: &SearchIndexFilter,
+        pagination: &Pagination,
+    ) -> Result<SearchIndexResults> {
+        let index = self.clone();
+        let query = query.to_string();
+        let filter = filter.clone();
+        let pagination = pagination.clone();
+
+        tokio::task::spawn_blocking(move || index.search_blocking(&query, &filter, &pagination))
+            .await
+            .map_err(index_error)?
+    }
+
+    fn search_blocking(
+        &self,
+        query: &str,
+        filter: &SearchIndexFilter,
+        pagination: &Pagination,
+    ) -> Result<SearchIndexResults> {
+        let fields = self.fields;
+        let searcher = self.reader.searcher();
+
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![(
+            Occur::Must,
+            Box::new(self.term_query(fields.kind, KIND_POST)),
+        )];
+        if !query.trim().is_empty() {
+            clauses.push((Occur::Must, self.text_query(query)?));
+        }
+        if let Some(ref tag) = filter.tag {
+            clauses.push((Occur::Must, Box::new(facet_query(fields.tags, tag))));
+        }
+        if let Some(author_id) = filter.author_id {
+            clauses.push((
+                Occur::Must,
+                Box::new(facet_query(fields.author, &author_id.to_string())),
+            ));
+        }
+        let query = BooleanQuery::new(clauses);
+
+        let mut tag_collector = FacetCollector::for_field("tags");
+        tag_collector.add_facet(Facet::root());
+        let mut author_collector = FacetCollector::for_field("author");
+        author_collector.add_facet(Facet::root());
+        let top_docs = TopDocs::with_limit(pagination.page_size as usize)
+            .and_offset(pagination.offset() as usize);
+
+        let (top_docs, total, tag_counts, author_counts) = searcher
+            .search(&query, &(top_docs, Count, tag_collector, author_collector))
+            .map_err(index_error)?;
+
+        let mut snippets =
+            SnippetGenerator::create(&searcher, &query, fields.content).map_err(index_error)?;
+        snippets.set_max_num_chars(SNIPPET_MAX_CHARS);
+
+        let hits = top_docs
+            .into_iter()
+            .map(|(score, address)| self.hit(&searcher, &snippets, score, address))
+            .collect::<Result<Vec<_>>>()?;
+
+        let tags = tag_counts
+            .top_k(Facet::root(), MAX_FACETS)
+            .into_iter()
+            .filter_map(|(facet, count)| {
+                facet_value(facet).map(|tag| TagFacet {
+                    tag: tag.to_string(),
+                    count,
+                })
+            })
+            .collect();
+        let authors = author_counts
+            .top_k(Facet::root(), MAX_FACETS)
+            .into_iter()
+            .filter_map(|(facet, count)| {
+                let author_id = facet_value(facet)?.parse().ok()?;
+                Some((author_id, count))
+            })
+            .map(|(author_id, count)| {
+                Ok(AuthorFacet {
+                    author_id,
+                    username: self.username(&searcher, author_id)?,
+                    count,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SearchIndexResults {
+            hits,
+            total: total as u64,
+            facets: SearchFacets { tags, authors },
+        })
+    }
+
+    /// Every query token must match the title or the content, allowing typos
+    /// proportional to the token length. Exact matches also hit the fuzzy clause,
+    /// so they outrank typo matches.
+    fn text_query(&self, query: &str) -> Result<Box<dyn Query>> {
+        let mut analyzer = self
+            .index
+            .tokenizer_for_field(self.fields.title)
+            .map_err(index_error)?;
+        let mut tokens = Vec::new();
+        analyzer
+            .token_stream(query)
+            .process(&mut |token| tokens.push(token.text.clone()));
+
+        if tokens.is_empty() {
+            return Ok(Box::new(EmptyQuery));
+        }
+
+        let clauses = tokens
+            .iter()
+            .map(|token| {
+                let mut alternatives: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+                for (field, boost) in [(self.fields.title, TITLE_BOOST), (self.fields.content, 1.0)]
+                {
+                    let term = Term::from_field_text(field, token);
+                    alternatives.push((
+                        Occur::Should,
+                        Box::new(BoostQuery::new(
+                            Box::new(TermQuery::new(term.clone(), IndexRecordOption::WithFreqs)),
+                            boost,
+                        )),
+                    ));
+                    let distance = typo_distance(token);
+                    if distance > 0 {
+                        alternatives.push((
+                            Occur::Should,
+                            Box::new(BoostQuery::new(
+                                Box::new(FuzzyTermQuery::new(term, distance, true)),
+                                boost,
+                            )),
+                        ));
+                    }
+                }
+                (
+                    Occur::Must,
+                    Box::new(BooleanQuery::new(alternatives)) as Box<dyn Query>,
+                )
+            })
+            .collect();
+
+        Ok(Box::new(BooleanQuery::new(clauses)))
+    }
+
+    fn hit(
+        &self,
+        searcher: &Searcher,
+        snippets: &SnippetGenerator,
+        score: f32,
+        address: DocAddress,
+    ) -> Result<IndexedPostHit> {
+        let fields = self.fields;
+        let document: TantivyDocument = searcher.doc(address).map_err(index_error)?;
+        let text = |field: Field| {
+            document
+                .get_first(field)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let mut snippet = snippets.snippet_from_doc(&document);
+        snippet.set_snippet_prefix_postfix("<mark>", "</mark>");
+
+        Ok(IndexedPostHit {
+            id: text(fields.id).parse().map_err(index_error)?,
+            title: text(fields.title),
+            slug: text(fields.slug),
+            author_id: text(fields.author_id).parse().map_err(index_error)?,
+            tags: document
+                .get_all(fields.tag_names)
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect(),
+            published_at: DateTime::parse_from_rfc3339(&text(fields.published_at))
+                .ok()
+                .map(|published_at| published_at.with_timezone(&Utc)),
+            score,
+            snippet: snippet.to_html(),
+        })
+    }
+
+    fn username(&self, searcher: &Searcher, author_id: Uuid) -> Result<Option<String>> {
+        let query = BooleanQuery::new(vec![
+            (
+                Occur::Must,
+                Box::new(self.term_query(self.fields.kind, KIND_AUTHOR)) as Box<dyn Query>,
+            ),
+            (
+                Occur::Must,
+                Box::new(self.term_query(self.fields.id, &author_id.to_string())),
+            ),
+        ]);
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(1))
+            .map_err(index_error)?;
+
+        let Some((_, address)) = top_docs.into_iter().next() else {
+            return Ok(None);
+        };
+        let document: TantivyDocument = searcher.doc(address).map_err(index_error)?;
+        Ok(document
+            .get_first(self.fields.username)
+            .and_then(|value| value.as_str())
+            .map(str::to_string))
+    }
+
+    fn post_document(&self, post: &Post) -> TantivyDocument {
+        let fields = self.fields;
+        let mut document = TantivyDocument::default();
+        document.add_text(fields.kind, KIND_POST);
+        document.add_text(fields.id, post.id.to_string());
+        document.add_text(fields.title, &post.title);
+        document.add_text(fields.slug, &post.slug);
+        document.add_text(fields.author_id, post.author_id.to_string());
+        document.add_text(
+            fields.content,
+            content::plain_text(post.content_format, &post.content),
+        );
+        document.add_facet(
+            fields.author,
+            Facet::from_path([post.author_id.to_string()]),
+        );
+        for tag in &post.tags {
+            document.add_facet(fields.tags, Facet::from_path([tag]));
+            document.add_text(fields.tag_names, tag);
+        }
+        if let Some(published_at) = post.published_at {
+            document.add_text(fields.published_at, published_at.to_rfc3339());
+        }
+        document
+    }
+
+    fn term_query(&self, field: Field, value: &str) -> TermQuery {
+        TermQuery::new(
+            Term::from_field_text(field, value),
+            IndexRecordOption::Basic,
+        )
+    }
+
+    /// Commits the pending operations. A failed commit rolls them back so they are not
+    /// picked up by whichever write commits next.
+    async fn commit(&self, mut writer: tokio::sync::OwnedMutexGuard<IndexWriter>) -> Result<()> {
+        let reader = self.reader.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = writer.commit() {
+                writer.rollback()?;
+                return Err(e);
+            }
+            reader.reload()
+        })
+        .await
+        .map_err(index_error)?
+        .map_err(index_error)
+    }
+
+    /// Discards the pending operations of a write that failed part way.
+    async fn rollback(&self, mut writer: tokio::sync::OwnedMutexGuard<IndexWriter>) -> Result<()> {
+        tokio::task::spawn_blocking(move || writer.rollback())
+            .await
+            .map_err(index_error)?
+            .map_err(index_error)?;
+        Ok(())
+    }
+}
+
+fn facet_query(field: Field, value: &str) -> TermQuery {
+    TermQuery::new(
+        Term::from_facet(field, &Facet::from_path([value])),
+        IndexRecordOption::Basic,
+    )
+}
+
+fn facet_value(facet: &Facet) -> Option<&str> {
+    facet.to_path().last().copied()
+}
+
+/// Typos tolerated for a query token: none for short words, one from four
+/// characters and two from eight.
+fn typo_distance(token: &str) -> u8 {
+    match token.chars().count() {
+        0..4 => 0,
+        4..8 => 1,
+        _ => 2,
+    }
+}
+
+fn index_error(err: impl std::fmt::Display) -> AppError {
+    AppError::InternalServerError(anyhow::anyhow!("search index: {}", err))
+}
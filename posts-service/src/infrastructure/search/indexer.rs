@@ -0,0 +1,93 @@
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use uuid::Uuid;
+
+use crate::{domain::DynPostRepository, infrastructure::search::SearchIndex};
+
+/// Keeps the search index in step with the outbox event stream.
+///
+/// Post events only name the post that changed; the indexer reloads it from the
+/// repository so redelivered or out-of-order events still converge on the current row.
+#[derive(Clone)]
+pub struct SearchIndexer {
+    posts: DynPostRepository,
+    index: SearchIndex,
+}
+
+impl SearchIndexer {
+    pub fn new(posts: DynPostRepository, index: SearchIndex) -> Self {
+        Self { posts, index }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        match (event.aggregate_type.as_str(), event.event_type.as_str()) {
+            // Reactions do not change anything the index holds
+            ("post", "post_liked") => Ok(()),
+            // Trashed and erased posts leave the index without a lookup
+            ("post", "post_deleted" | "post_erased") => {
+                self.index.remove_post(event.aggregate_id).await
+            }
+            // Everything else, unpublishing included, reloads the post; `index_post`
+            // drops it when it is no longer published
+            ("post", _) => self.reindex_post(event.aggregate_id).await,
+            ("user", "user_registered" | "user_updated" | "user_restored") => {
+                match event.payload["username"].as_str() {
+                    Some(username) => self.index.index_author(event.aggregate_id, username).await,
+                    None => Ok(()),
+                }
+            }
+            ("user", "user_deleted") => self.index.remove_author(event.aggregate_id).await,
+            _ => Ok(()),
+        }
+    }
+
+    async fn reindex_post(&self, id: Uuid) -> Result<()> {
+        match self.posts.get_post(id.into()).await? {
+            Some(post) => self.index.index_post(&post).await,
+            None => self.index.remove_post(id).await,
+        }
+    }
+
+    /// Rebuilds the index from the posts table when it is empty, e.g. on a fresh volume.
+    pub async fn bootstrap(&self) -> Result<()> {
+        if self.index.num_posts()? == 0 {
+            self.index.rebuild(&self.posts).await?;
+        }
+        Ok(())
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Search indexer started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let indexer = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match indexer.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to index event {}: {:?}", event.id, e);
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start search indexer: {}", e);
+            }
+        })
+    }
+}
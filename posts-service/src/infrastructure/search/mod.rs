@@ -0,0 +1,5 @@
+mod index;
+mod indexer;
+
+pub use index::SearchIndex;
+pub use indexer::SearchIndexer;
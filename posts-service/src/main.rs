@@ -1,11 +1,20 @@
 use common::{
-    config::get_configuration,
+    config::{PubSubSettings, get_configuration},
+    pubsub::PubSubSubscriber,
     telemetry::{get_subscriber, init_subscriber},
 };
 use posts_service::{
     infrastructure::{
         database::{RepoProvider, bootstrap_db, bootstrap_outbox},
+        erasure::UserEraser,
+        export::UserExporter,
+        feed::{Feed, FeedFanout},
         http::create_router,
+        moderation::AuthorStatusTracker,
+        purge::TrashPurger,
+        reactions::ReactionCountFlusher,
+        scheduler::PostScheduler,
+        search::{SearchIndex, SearchIndexer},
     },
     presentation::state::AppState,
 };
@@ -30,12 +39,132 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let outbox_poller = bootstrap_outbox(conn, &config.pubsub).await?;
     outbox_poller.spawn();
 
-    let state = AppState::new(repo_provider);
+    let scheduler = PostScheduler::new(
+        repo_provider.posts.clone(),
+        std::time::Duration::from_secs(10),
+    );
+    scheduler.spawn();
+
+    let reactions = repo_provider.reactions.clone();
+    let flusher = ReactionCountFlusher::new(reactions.clone(), std::time::Duration::from_secs(5));
+    flusher.spawn();
+
+    let purger = TrashPurger::new(
+        repo_provider.posts.clone(),
+        config.trash.retention(),
+        config.trash.purge_interval(),
+    );
+    purger.spawn();
+
+    let search_index = SearchIndex::open(&config.search)?;
+    let indexer = SearchIndexer::new(repo_provider.posts.clone(), search_index.clone());
+    {
+        let indexer = indexer.clone();
+        tokio::spawn(async move {
+            if let Err(e) = indexer.bootstrap().await {
+                tracing::error!("Failed to bootstrap search index: {:?}", e);
+            }
+        });
+    }
+    if let Some(subscription) = config.search.instance_subscription() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        indexer.spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    let fanout = FeedFanout::new(
+        repo_provider.feeds.clone(),
+        repo_provider.timelines.clone(),
+        repo_provider.posts.clone(),
+        config.feed.hot_author_threshold,
+    );
+    if let Some(subscription) = config.feed.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        fanout.spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    if let Some(subscription) = config.erasure.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        UserEraser::new(repo_provider.posts.clone(), repo_provider.reactions.clone())
+            .spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    if let Some(subscription) = config.export.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        UserExporter::new(repo_provider.posts.clone()).spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    if let Some(subscription) = config.suspension.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        AuthorStatusTracker::new(repo_provider.posts.clone())
+            .spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    let feed = Feed::new(
+        repo_provider.feeds.clone(),
+        repo_provider.timelines.clone(),
+        repo_provider.posts.clone(),
+        config.feed.hot_author_threshold,
+    );
+    let state = AppState::new(repo_provider, search_index, feed);
     let router = create_router(state);
 
     tracing::info!("server starting on port: {}...", config.application.port);
 
-    axum::serve(listener, router).await?;
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // Buffered reaction deltas would otherwise go down with the process
+    match reactions.flush_reaction_counts().await {
+        Ok(flushed) => tracing::info!("Flushed {} reaction counts on shutdown", flushed),
+        Err(e) => tracing::error!("Failed to flush reaction counts on shutdown: {:?}", e),
+    }
 
     Ok(())
 }
+
+/// Resolves on Ctrl+C or, on Unix, on the SIGTERM sent by deploys.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+    tracing::info!("Shutting down");
+}
@@ -0,0 +1,152 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::domain::{Comment, CommentId, PostId};
+use crate::presentation::{
+    handlers::types::{
+        CommentAuthorQuery, CommentResponse, CommentStatusQuery, CreateCommentRequest,
+        SetCommentStatusRequest, UpdateCommentRequest,
+    },
+    responses::ListCommentResponse,
+    state::AppState,
+};
+use common::{
+    error::{AppError, Result},
+    extract::ValidatedJson,
+    pagination::{PaginatedResponse, Pagination},
+};
+
+/// Loads a comment, treating comments that belong to another post as missing.
+async fn find_comment(state: &AppState, post_id: PostId, id: CommentId) -> Result<Comment> {
+    state
+        .repos
+        .comments
+        .get_comment(id)
+        .await?
+        .filter(|comment| comment.post_id == uuid::Uuid::from(post_id) && !comment.is_deleted())
+        .ok_or_else(|| AppError::NotFoundError("Comment not found".to_string()))
+}
+
+pub async fn list_comments(
+    State(state): State<Arc<AppState>>,
+    Path(post_id): Path<PostId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListCommentResponse>> {
+    let pagination = pagination.normalize();
+    let (threads, total_threads) = state
+        .repos
+        .comments
+        .list_threads(post_id, &pagination)
+        .await?;
+
+    let threads: Vec<CommentResponse> = threads.into_iter().map(CommentResponse::from).collect();
+    let count = threads.len() as u64;
+    let paginated_response = PaginatedResponse::new(
+        threads,
+        count,
+        total_threads,
+        pagination.page,
+        pagination.page_size,
+    );
+    Ok(Json(paginated_response))
+}
+
+pub async fn list_comments_by_status(
+    State(state): State<Arc<AppState>>,
+    Path(post_id): Path<PostId>,
+    Query(pagination): Query<Pagination>,
+    Query(query): Query<CommentStatusQuery>,
+) -> Result<Json<ListCommentResponse>> {
+    let pagination = pagination.normalize();
+    let (comments, total_comments) = state
+        .repos
+        .comments
+        .list_comments_by_status(post_id, query.status, &pagination)
+        .await?;
+
+    let comments: Vec<CommentResponse> = comments.into_iter().map(CommentResponse::from).collect();
+    let count = comments.len() as u64;
+    let paginated_response = PaginatedResponse::new(
+        comments,
+        count,
+        total_comments,
+        pagination.page,
+        pagination.page_size,
+    );
+    Ok(Json(paginated_response))
+}
+
+pub async fn create_comment(
+    State(state): State<Arc<AppState>>,
+    Path(post_id): Path<PostId>,
+    ValidatedJson(payload): ValidatedJson<CreateCommentRequest>,
+) -> Result<Json<CommentResponse>> {
+    let now = Utc::now();
+    let id = CommentId::new().into();
+    let comment = Comment {
+        id,
+        post_id: post_id.into(),
+        parent_id: payload.parent_id,
+        root_id: id,
+        depth: 0,
+        author_id: payload.author_id.into(),
+        content: payload.content,
+        status: Default::default(),
+        created_at: now.into(),
+        updated_at: now.into(),
+        edited_at: None,
+        deleted_at: None,
+    };
+
+    let comment = state.repos.comments.create_comment(comment).await?;
+    Ok(Json(comment.into()))
+}
+
+pub async fn update_comment(
+    State(state): State<Arc<AppState>>,
+    Path((post_id, id)): Path<(PostId, CommentId)>,
+    ValidatedJson(payload): ValidatedJson<UpdateCommentRequest>,
+) -> Result<Json<CommentResponse>> {
+    find_comment(&state, post_id, id).await?;
+
+    let comment = state
+        .repos
+        .comments
+        .update_comment(id, payload.author_id, payload.content)
+        .await?;
+    Ok(Json(comment.into()))
+}
+
+pub async fn delete_comment(
+    State(state): State<Arc<AppState>>,
+    Path((post_id, id)): Path<(PostId, CommentId)>,
+    Query(query): Query<CommentAuthorQuery>,
+) -> Result<()> {
+    find_comment(&state, post_id, id).await?;
+
+    state
+        .repos
+        .comments
+        .delete_comment(id, query.author_id)
+        .await?;
+    Ok(())
+}
+
+pub async fn set_comment_status(
+    State(state): State<Arc<AppState>>,
+    Path((post_id, id)): Path<(PostId, CommentId)>,
+    ValidatedJson(payload): ValidatedJson<SetCommentStatusRequest>,
+) -> Result<Json<CommentResponse>> {
+    find_comment(&state, post_id, id).await?;
+
+    let comment = state
+        .repos
+        .comments
+        .set_comment_status(id, payload.status)
+        .await?;
+    Ok(Json(comment.into()))
+}
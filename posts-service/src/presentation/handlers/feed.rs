@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use common::error::Result;
+
+use crate::{
+    domain::FeedCursor,
+    presentation::{
+        handlers::{
+            reactions::post_responses,
+            types::{FeedQuery, FeedResponse},
+        },
+        state::AppState,
+    },
+};
+
+pub async fn get_feed(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FeedQuery>,
+) -> Result<Json<FeedResponse>> {
+    let cursor = query.cursor.as_deref().map(FeedCursor::parse).transpose()?;
+    let limit = query.limit();
+
+    let page = state
+        .feed
+        .page(query.user_id, cursor.as_ref(), limit)
+        .await?;
+
+    Ok(Json(FeedResponse {
+        data: post_responses(&state, page.posts).await?,
+        next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
@@ -0,0 +1,64 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use std::sync::Arc;
+
+use crate::domain::{PostId, PostTransition};
+use crate::presentation::{
+    handlers::{
+        reactions::post_response,
+        types::{PostResponse, SchedulePostRequest},
+    },
+    state::AppState,
+};
+use common::{error::Result, extract::ValidatedJson};
+
+pub async fn publish_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+) -> Result<Json<PostResponse>> {
+    let post = state
+        .repos
+        .posts
+        .change_status(id, PostTransition::Publish)
+        .await?;
+    Ok(Json(post_response(&state, post).await?))
+}
+
+pub async fn unpublish_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+) -> Result<Json<PostResponse>> {
+    let post = state
+        .repos
+        .posts
+        .change_status(id, PostTransition::Unpublish)
+        .await?;
+    Ok(Json(post_response(&state, post).await?))
+}
+
+pub async fn schedule_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+    ValidatedJson(payload): ValidatedJson<SchedulePostRequest>,
+) -> Result<Json<PostResponse>> {
+    let post = state
+        .repos
+        .posts
+        .change_status(id, PostTransition::Schedule(payload.publish_at))
+        .await?;
+    Ok(Json(post_response(&state, post).await?))
+}
+
+pub async fn archive_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+) -> Result<Json<PostResponse>> {
+    let post = state
+        .repos
+        .posts
+        .change_status(id, PostTransition::Archive)
+        .await?;
+    Ok(Json(post_response(&state, post).await?))
+}
@@ -1,7 +1,25 @@
+mod comments;
+mod exports;
+mod feed;
 mod health;
+mod lifecycle;
 mod posts;
+mod reactions;
+mod revisions;
+mod search;
+mod search_index;
+mod tags;
 pub(crate) mod types;
 
+pub use comments::*;
+pub use exports::*;
+pub use feed::*;
 pub use health::*;
+pub use lifecycle::*;
 pub use posts::*;
+pub use reactions::*;
+pub use revisions::*;
+pub use search::*;
+pub use search_index::*;
+pub use tags::*;
 pub use types::CreatePostRequest;
@@ -1,68 +1,193 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    response::{IntoResponse, Redirect, Response},
 };
 use chrono::Utc;
 use std::sync::Arc;
 
-use crate::presentation::{handlers::types::PostResponse, state::AppState};
+use crate::presentation::{
+    handlers::{
+        reactions::{post_response, post_responses},
+        types::{PostResponse, TrashQuery, UpdatePostRequest},
+    },
+    responses::ListPostResponse,
+    state::AppState,
+};
 use crate::{
-    domain::{Post, PostId},
+    domain::{Post, PostFilter, PostId, PostSort, PostStatus, PostUpdate, tag},
     presentation::handlers::CreatePostRequest,
 };
-use common::error::Result;
+use common::{
+    error::{AppError, Result},
+    etag::{self, ETagHeader, IfMatch},
+    extract::ValidatedJson,
+    pagination::{PaginatedResponse, Pagination},
+};
 
-pub async fn list_posts(State(state): State<Arc<AppState>>) -> Result<Json<Vec<PostResponse>>> {
-    let posts = state
+pub async fn list_posts(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<Pagination>,
+    Query(mut filter): Query<PostFilter>,
+    Query(sort): Query<PostSort>,
+) -> Result<Json<ListPostResponse>> {
+    let pagination = pagination.normalize();
+    filter.status.get_or_insert(PostStatus::Published);
+    filter.tag = filter.tag.as_deref().map(tag::normalize_tag);
+    let (posts, total_posts) = state
         .repos
         .posts
-        .list_posts()
-        .await?
-        .unwrap_or_default()
-        .into_iter()
-        .map(PostResponse::from)
-        .collect();
+        .list_posts(&filter, &sort, &pagination)
+        .await?;
 
-    Ok(Json(posts))
+    let posts = post_responses(&state, posts).await?;
+    let count = posts.len() as u64;
+    let paginated_response = PaginatedResponse::new(
+        posts,
+        count,
+        total_posts,
+        pagination.page,
+        pagination.page_size,
+    );
+    Ok(Json(paginated_response))
 }
 
 pub async fn create_post(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreatePostRequest>,
-) -> Result<Json<PostResponse>> {
+    ValidatedJson(payload): ValidatedJson<CreatePostRequest>,
+) -> Result<(ETagHeader, Json<PostResponse>)> {
+    let published_at = match payload.status {
+        PostStatus::Draft => None,
+        PostStatus::Published => Some(Utc::now().into()),
+        PostStatus::Scheduled | PostStatus::Archived => {
+            return Err(AppError::ValidationError(
+                "Posts can only be created as draft or published".to_string(),
+            ));
+        }
+    };
+
+    let tags = tag::normalize_tags(&payload.tags)?;
+
     let post = Post::builder()
         .id(PostId::new().into())
         .title(payload.title)
         .author_id(payload.author_id.into())
         .content(payload.content)
+        .content_format(payload.content_format)
+        .status(payload.status)
+        .published_at(published_at)
+        .tags(tags)
         .created_at(Utc::now().into())
         .updated_at(Utc::now().into())
         .build();
 
-    state.repos.posts.create_post(post.clone()).await?;
-    Ok(Json(post.into()))
+    let post = state.repos.posts.create_post(post).await?;
+    Ok((etag::header(post.version), Json(post.into())))
 }
 
 pub async fn get_post(
     State(state): State<Arc<AppState>>,
     Path(id): Path<PostId>,
-) -> Result<Json<PostResponse>> {
+) -> Result<(ETagHeader, Json<PostResponse>)> {
     let post = state
         .repos
         .posts
         .get_post(id)
         .await?
-        .ok_or_else(|| common::error::AppError::NotFoundError("Post not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
 
-    Ok(Json(post.into()))
+    let version = post.version;
+    Ok((
+        etag::header(version),
+        Json(post_response(&state, post).await?),
+    ))
 }
 
-pub async fn update_post(State(state): State<Arc<AppState>>, Json(post): Json<Post>) -> Result<()> {
-    state.repos.posts.update_post(post.clone()).await?;
-    Ok(())
+pub async fn get_post_by_slug(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> Result<Response> {
+    let post = state
+        .repos
+        .posts
+        .get_post_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+
+    // Former slugs stay valid as permanent redirects to the current one
+    if post.slug != slug {
+        let location = format!("/posts/by-slug/{}", post.slug);
+        return Ok(Redirect::permanent(&location).into_response());
+    }
+
+    let version = post.version;
+    let response = post_response(&state, post).await?;
+    Ok((etag::header(version), Json(response)).into_response())
+}
+
+pub async fn update_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+    if_match: IfMatch,
+    ValidatedJson(payload): ValidatedJson<UpdatePostRequest>,
+) -> Result<(ETagHeader, Json<PostResponse>)> {
+    let update = PostUpdate {
+        id,
+        title: payload.title,
+        content: payload.content,
+        author_id: payload.author_id,
+        content_format: payload.content_format,
+        tags: payload
+            .tags
+            .as_deref()
+            .map(tag::normalize_tags)
+            .transpose()?,
+    };
+    let post = state.repos.posts.update_post(update, &if_match).await?;
+    let version = post.version;
+    Ok((
+        etag::header(version),
+        Json(post_response(&state, post).await?),
+    ))
 }
 
 pub async fn delete_post(State(state): State<Arc<AppState>>, Path(id): Path<PostId>) -> Result<()> {
     state.repos.posts.delete_post(id).await?;
     Ok(())
 }
+
+pub async fn list_trash(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<Pagination>,
+    Query(query): Query<TrashQuery>,
+) -> Result<Json<ListPostResponse>> {
+    let pagination = pagination.normalize();
+    let (posts, total_posts) = state
+        .repos
+        .posts
+        .list_deleted_posts(query.author_id, &pagination)
+        .await?;
+
+    let posts: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+    let count = posts.len() as u64;
+    let paginated_response = PaginatedResponse::new(
+        posts,
+        count,
+        total_posts,
+        pagination.page,
+        pagination.page_size,
+    );
+    Ok(Json(paginated_response))
+}
+
+pub async fn restore_post(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+) -> Result<(ETagHeader, Json<PostResponse>)> {
+    let post = state.repos.posts.restore_post(id).await?;
+    let version = post.version;
+    Ok((
+        etag::header(version),
+        Json(post_response(&state, post).await?),
+    ))
+}
@@ -0,0 +1,115 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use std::sync::Arc;
+
+use crate::domain::{Post, PostId, ReactionKind, UserId};
+use crate::presentation::{
+    handlers::types::{PostResponse, ReactionQuery, ReactionRequest, ReactionsResponse},
+    state::AppState,
+};
+use common::{
+    error::{AppError, Result},
+    extract::ValidatedJson,
+};
+
+/// Builds the response for a post, including its reaction counts.
+pub(crate) async fn post_response(state: &AppState, post: Post) -> Result<PostResponse> {
+    Ok(post_responses(state, vec![post]).await?.remove(0))
+}
+
+/// Builds responses for posts, loading all their reaction counts at once.
+pub(crate) async fn post_responses(
+    state: &AppState,
+    posts: Vec<Post>,
+) -> Result<Vec<PostResponse>> {
+    let ids: Vec<PostId> = posts.iter().map(|post| post.id.into()).collect();
+    let mut counts = state.repos.reactions.reaction_counts(&ids).await?;
+
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let reactions = counts.remove(&post.id.into()).unwrap_or_default();
+            PostResponse::from(post).with_reactions(reactions)
+        })
+        .collect())
+}
+
+async fn reactions_response(
+    state: &AppState,
+    post_id: PostId,
+    user_id: Option<UserId>,
+) -> Result<ReactionsResponse> {
+    let reactions = state
+        .repos
+        .reactions
+        .reaction_counts(&[post_id])
+        .await?
+        .remove(&post_id)
+        .unwrap_or_default();
+    let user_reactions = match user_id {
+        Some(user_id) => {
+            state
+                .repos
+                .reactions
+                .user_reactions(post_id, user_id)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(ReactionsResponse {
+        post_id: post_id.into(),
+        reactions,
+        user_reactions,
+    })
+}
+
+pub async fn get_reactions(
+    State(state): State<Arc<AppState>>,
+    Path(post_id): Path<PostId>,
+    Query(query): Query<ReactionQuery>,
+) -> Result<Json<ReactionsResponse>> {
+    if state.repos.posts.get_post(post_id).await?.is_none() {
+        return Err(AppError::NotFoundError("Post not found".to_string()));
+    }
+
+    Ok(Json(
+        reactions_response(&state, post_id, query.user_id).await?,
+    ))
+}
+
+/// Adds a reaction; repeating the request leaves the reaction in place.
+pub async fn add_reaction(
+    State(state): State<Arc<AppState>>,
+    Path((post_id, kind)): Path<(PostId, ReactionKind)>,
+    ValidatedJson(payload): ValidatedJson<ReactionRequest>,
+) -> Result<Json<ReactionsResponse>> {
+    state
+        .repos
+        .reactions
+        .add_reaction(post_id, payload.user_id, kind)
+        .await?;
+
+    Ok(Json(
+        reactions_response(&state, post_id, Some(payload.user_id)).await?,
+    ))
+}
+
+/// Removes a reaction; removing one that is not there is not an error.
+pub async fn remove_reaction(
+    State(state): State<Arc<AppState>>,
+    Path((post_id, kind)): Path<(PostId, ReactionKind)>,
+    Query(query): Query<ReactionRequest>,
+) -> Result<Json<ReactionsResponse>> {
+    state
+        .repos
+        .reactions
+        .remove_reaction(post_id, query.user_id, kind)
+        .await?;
+
+    Ok(Json(
+        reactions_response(&state, post_id, Some(query.user_id)).await?,
+    ))
+}
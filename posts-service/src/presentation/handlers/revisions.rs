@@ -0,0 +1,81 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use std::sync::Arc;
+
+use crate::domain::{PostId, revision::diff_lines};
+use crate::presentation::{
+    handlers::{
+        reactions::post_response,
+        types::{PostResponse, PostRevisionResponse, RevisionDiffResponse},
+    },
+    responses::ListRevisionResponse,
+    state::AppState,
+};
+use common::{
+    error::{AppError, Result},
+    pagination::{PaginatedResponse, Pagination},
+};
+
+pub async fn list_revisions(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<PostId>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListRevisionResponse>> {
+    let pagination = pagination.normalize();
+
+    if state.repos.posts.get_post(id).await?.is_none() {
+        return Err(AppError::NotFoundError("Post not found".to_string()));
+    }
+
+    let (revisions, total_revisions) = state.repos.posts.list_revisions(id, &pagination).await?;
+
+    let revisions: Vec<PostRevisionResponse> = revisions
+        .into_iter()
+        .map(PostRevisionResponse::from)
+        .collect();
+    let count = revisions.len() as u64;
+    let paginated_response = PaginatedResponse::new(
+        revisions,
+        count,
+        total_revisions,
+        pagination.page,
+        pagination.page_size,
+    );
+    Ok(Json(paginated_response))
+}
+
+pub async fn get_revision(
+    State(state): State<Arc<AppState>>,
+    Path((id, revision)): Path<(PostId, i32)>,
+) -> Result<Json<RevisionDiffResponse>> {
+    let post = state
+        .repos
+        .posts
+        .get_post(id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Post not found".to_string()))?;
+
+    let revision = state
+        .repos
+        .posts
+        .get_revision(id, revision)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Revision not found".to_string()))?;
+
+    Ok(Json(RevisionDiffResponse {
+        diff: diff_lines(&revision.content, &post.content),
+        title_changed: revision.title != post.title,
+        current_title: post.title,
+        revision: revision.into(),
+    }))
+}
+
+pub async fn restore_revision(
+    State(state): State<Arc<AppState>>,
+    Path((id, revision)): Path<(PostId, i32)>,
+) -> Result<Json<PostResponse>> {
+    let post = state.repos.posts.restore_revision(id, revision).await?;
+    Ok(Json(post_response(&state, post).await?))
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use common::{
+    error::{AppError, Result},
+    pagination::{PaginatedResponse, Pagination},
+};
+
+use crate::{
+    domain::{PostId, search::MAX_QUERY_LENGTH},
+    presentation::{
+        handlers::types::{SearchPostsQuery, SearchResultResponse},
+        responses::ListSearchResultResponse,
+        state::AppState,
+    },
+};
+
+pub async fn search_posts(
+    State(state): State<Arc<AppState>>,
+    Query(search): Query<SearchPostsQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListSearchResultResponse>> {
+    let query = search.q.trim();
+    if query.is_empty() {
+        return Err(AppError::ValidationError(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Search query must be at most {} characters",
+            MAX_QUERY_LENGTH
+        )));
+    }
+
+    let pagination = pagination.normalize();
+    let (hits, total_hits) = state.repos.posts.search_posts(query, &pagination).await?;
+
+    let ids: Vec<PostId> = hits.iter().map(|hit| hit.post.id.into()).collect();
+    let mut counts = state.repos.reactions.reaction_counts(&ids).await?;
+
+    let hits: Vec<SearchResultResponse> = hits
+        .into_iter()
+        .map(|hit| {
+            let reactions = counts.remove(&hit.post.id.into()).unwrap_or_default();
+            let mut response = SearchResultResponse::from(hit);
+            response.post.reactions = reactions;
+            response
+        })
+        .collect();
+    let count = hits.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        hits,
+        count,
+        total_hits,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use common::{
+    error::{AppError, Result},
+    pagination::{PaginatedResponse, Pagination},
+};
+
+use crate::{
+    domain::{SearchIndexFilter, search::MAX_QUERY_LENGTH, tag},
+    presentation::{
+        handlers::types::{
+            IndexedPostResponse, RebuildSearchIndexResponse, SearchIndexQuery, SearchIndexResponse,
+        },
+        state::AppState,
+    },
+};
+
+/// Fuzzy search over the search index. An empty `q` browses by the tag and author filters.
+pub async fn search_index(
+    State(state): State<Arc<AppState>>,
+    Query(search): Query<SearchIndexQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<SearchIndexResponse>> {
+    let query = search.q.trim();
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Search query must be at most {} characters",
+            MAX_QUERY_LENGTH
+        )));
+    }
+
+    let filter = SearchIndexFilter {
+        tag: search.tag.as_deref().map(tag::normalize_tag),
+        author_id: search.author_id,
+    };
+    let pagination = pagination.normalize();
+    let results = state.search.search(query, &filter, &pagination).await?;
+
+    let hits: Vec<IndexedPostResponse> = results
+        .hits
+        .into_iter()
+        .map(IndexedPostResponse::from)
+        .collect();
+    let count = hits.len() as u64;
+    Ok(Json(SearchIndexResponse {
+        results: PaginatedResponse::new(
+            hits,
+            count,
+            results.total,
+            pagination.page,
+            pagination.page_size,
+        ),
+        facets: results.facets,
+    }))
+}
+
+pub async fn rebuild_search_index(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<RebuildSearchIndexResponse>> {
+    let indexed = state.search.rebuild(&state.repos.posts).await?;
+    Ok(Json(RebuildSearchIndexResponse { indexed }))
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use common::{
+    error::Result,
+    pagination::{PaginatedResponse, Pagination},
+};
+
+use crate::presentation::{
+    handlers::types::TagResponse, responses::ListTagResponse, state::AppState,
+};
+
+pub async fn list_tags(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListTagResponse>> {
+    let pagination = pagination.normalize();
+    let (tags, total_tags) = state.repos.posts.list_tags(&pagination).await?;
+
+    let tags: Vec<TagResponse> = tags.into_iter().map(TagResponse::from).collect();
+    let count = tags.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        tags,
+        count,
+        total_tags,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
@@ -1,33 +1,353 @@
+use chrono::{DateTime, Utc};
+use common::pagination::PaginatedResponse;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
-use crate::domain::{AuthorId, Post};
+use crate::domain::{
+    AuthorId, Comment, CommentStatus, CommentThread, ContentFormat, IndexedPostHit, Post,
+    PostRevision, PostSearchHit, PostStatus, ReactionCounts, ReactionKind, SearchFacets, TagCount,
+    UserId, comment::MAX_COMMENT_LENGTH, revision::DiffLine,
+};
 
 #[derive(Debug, Deserialize, Serialize, Validate)]
 pub struct CreatePostRequest {
-    #[validate(length(min = 1, max = 200, message = "Title must be between 1-200 characters"))]
+    #[validate(
+        length(min = 1, max = 200, message = "Title must be between 1-200 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
     pub title: String,
     pub author_id: AuthorId,
     #[validate(length(min = 1, message = "Content cannot be empty"))]
     pub content: String,
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    #[serde(default)]
+    pub status: PostStatus,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Body of `PUT /posts/{id}`. Omitted optional fields keep their stored value.
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct UpdatePostRequest {
+    #[validate(
+        length(min = 1, max = 200, message = "Title must be between 1-200 characters"),
+        custom(function = "common::extract::not_blank")
+    )]
+    pub title: String,
+    pub author_id: Option<AuthorId>,
+    #[validate(length(min = 1, message = "Content cannot be empty"))]
+    pub content: String,
+    pub content_format: Option<ContentFormat>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrashQuery {
+    pub author_id: Option<AuthorId>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct SchedulePostRequest {
+    pub publish_at: DateTime<Utc>,
+}
+
+/// Largest page of a feed returned in one request.
+pub const MAX_FEED_LIMIT: u64 = 100;
+const DEFAULT_FEED_LIMIT: u64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub user_id: UserId,
+    pub cursor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+impl FeedQuery {
+    pub fn limit(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_FEED_LIMIT)
+            .clamp(1, MAX_FEED_LIMIT)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedResponse {
+    pub data: Vec<PostResponse>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PostResponse {
     pub id: String,
     pub title: String,
+    pub slug: String,
     pub content: String,
+    pub content_format: ContentFormat,
+    pub content_html: String,
+    pub excerpt: String,
+    pub reading_time_minutes: i32,
     pub author_id: String,
+    pub status: PostStatus,
+    pub publish_at: Option<String>,
+    pub published_at: Option<String>,
+    pub version: i32,
+    pub tags: Vec<String>,
+    pub reactions: ReactionCounts,
     pub created_at: String,
+    pub updated_at: String,
+    pub deleted_at: Option<String>,
+}
+
+impl PostResponse {
+    pub fn with_reactions(mut self, reactions: ReactionCounts) -> Self {
+        self.reactions = reactions;
+        self
+    }
 }
+
 impl From<Post> for PostResponse {
     fn from(post: Post) -> Self {
         Self {
             id: post.id.to_string(),
             title: post.title,
+            slug: post.slug,
+            excerpt: post.excerpt,
+            reading_time_minutes: post.reading_time_minutes,
             content: post.content,
+            content_format: post.content_format,
+            content_html: post.content_html,
             author_id: post.author_id.to_string(),
+            status: post.status,
+            publish_at: post.publish_at.map(|t| t.to_rfc3339()),
+            published_at: post.published_at.map(|t| t.to_rfc3339()),
+            version: post.version,
+            tags: post.tags,
+            reactions: ReactionCounts::new(),
             created_at: post.created_at.to_rfc3339(),
+            updated_at: post.updated_at.to_rfc3339(),
+            deleted_at: post.deleted_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostRevisionResponse {
+    pub post_id: String,
+    pub revision: i32,
+    pub title: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+impl From<PostRevision> for PostRevisionResponse {
+    fn from(revision: PostRevision) -> Self {
+        Self {
+            post_id: revision.post_id.to_string(),
+            revision: revision.revision,
+            title: revision.title,
+            content: revision.content,
+            created_at: revision.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevisionDiffResponse {
+    pub revision: PostRevisionResponse,
+    pub current_title: String,
+    pub title_changed: bool,
+    pub diff: Vec<DiffLine>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagResponse {
+    pub name: String,
+    pub post_count: i64,
+}
+
+impl From<TagCount> for TagResponse {
+    fn from(tag: TagCount) -> Self {
+        Self {
+            name: tag.name,
+            post_count: tag.post_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchPostsQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResultResponse {
+    #[serde(flatten)]
+    pub post: PostResponse,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+impl From<PostSearchHit> for SearchResultResponse {
+    fn from(hit: PostSearchHit) -> Self {
+        Self {
+            post: hit.post.into(),
+            rank: hit.rank,
+            snippet: hit.snippet,
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIndexQuery {
+    #[serde(default)]
+    pub q: String,
+    pub tag: Option<String>,
+    pub author_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IndexedPostResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub author_id: Uuid,
+    pub tags: Vec<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub score: f32,
+    pub snippet: String,
+}
+
+impl From<IndexedPostHit> for IndexedPostResponse {
+    fn from(hit: IndexedPostHit) -> Self {
+        Self {
+            id: hit.id,
+            title: hit.title,
+            slug: hit.slug,
+            author_id: hit.author_id,
+            tags: hit.tags,
+            published_at: hit.published_at,
+            score: hit.score,
+            snippet: hit.snippet,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchIndexResponse {
+    #[serde(flatten)]
+    pub results: PaginatedResponse<IndexedPostResponse>,
+    pub facets: SearchFacets,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RebuildSearchIndexResponse {
+    pub indexed: u64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCommentRequest {
+    pub author_id: AuthorId,
+    #[validate(
+        length(max = MAX_COMMENT_LENGTH, message = "Comment is too long"),
+        custom(function = "common::extract::not_blank")
+    )]
+    pub content: String,
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCommentRequest {
+    pub author_id: AuthorId,
+    #[validate(
+        length(max = MAX_COMMENT_LENGTH, message = "Comment is too long"),
+        custom(function = "common::extract::not_blank")
+    )]
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetCommentStatusRequest {
+    pub status: CommentStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentAuthorQuery {
+    pub author_id: AuthorId,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentStatusQuery {
+    #[serde(default = "pending")]
+    pub status: CommentStatus,
+}
+
+fn pending() -> CommentStatus {
+    CommentStatus::Pending
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    /// `None` for deleted comments that are kept as placeholders in a thread.
+    pub author_id: Option<Uuid>,
+    pub content: String,
+    pub status: CommentStatus,
+    pub depth: i32,
+    pub deleted: bool,
+    pub edited_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub replies: Vec<CommentResponse>,
+}
+
+impl From<Comment> for CommentResponse {
+    fn from(comment: Comment) -> Self {
+        let deleted = comment.is_deleted();
+        Self {
+            id: comment.id,
+            post_id: comment.post_id,
+            parent_id: comment.parent_id,
+            author_id: (!deleted).then_some(comment.author_id),
+            content: comment.content,
+            status: comment.status,
+            depth: comment.depth,
+            deleted,
+            edited_at: comment.edited_at.map(|t| t.to_rfc3339()),
+            created_at: comment.created_at.to_rfc3339(),
+            updated_at: comment.updated_at.to_rfc3339(),
+            replies: Vec::new(),
+        }
+    }
+}
+
+impl From<CommentThread> for CommentResponse {
+    fn from(thread: CommentThread) -> Self {
+        Self {
+            replies: thread.replies.into_iter().map(Self::from).collect(),
+            ..thread.comment.into()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReactionRequest {
+    pub user_id: UserId,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionQuery {
+    pub user_id: Option<UserId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionsResponse {
+    pub post_id: Uuid,
+    pub reactions: ReactionCounts,
+    /// Reactions of the requesting user; empty when no user was given.
+    pub user_reactions: Vec<ReactionKind>,
+}
@@ -1,3 +1,4 @@
 pub mod handlers;
+pub mod responses;
 pub mod routes;
 pub mod state;
@@ -0,0 +1,11 @@
+use common::pagination::PaginatedResponse;
+
+use crate::presentation::handlers::types::{
+    CommentResponse, PostResponse, PostRevisionResponse, SearchResultResponse, TagResponse,
+};
+
+pub type ListCommentResponse = PaginatedResponse<CommentResponse>;
+pub type ListPostResponse = PaginatedResponse<PostResponse>;
+pub type ListRevisionResponse = PaginatedResponse<PostRevisionResponse>;
+pub type ListTagResponse = PaginatedResponse<TagResponse>;
+pub type ListSearchResultResponse = PaginatedResponse<SearchResultResponse>;
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::post};
+
+use crate::presentation::{handlers::rebuild_search_index, state::AppState};
+
+pub fn admin_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/search/rebuild", post(rebuild_search_index))
+        .with_state(state)
+}
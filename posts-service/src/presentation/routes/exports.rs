@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::presentation::{handlers::get_export_part, state::AppState};
+
+pub fn exports_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/{export_id}", get(get_export_part))
+        .with_state(state)
+}
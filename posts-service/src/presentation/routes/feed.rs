@@ -0,0 +1,9 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::presentation::{handlers::get_feed, state::AppState};
+
+pub fn feed_router(state: Arc<AppState>) -> Router {
+    Router::new().route("/", get(get_feed)).with_state(state)
+}
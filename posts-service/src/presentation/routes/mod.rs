@@ -1,5 +1,15 @@
+mod admin;
+mod exports;
+mod feed;
 mod health;
 mod posts;
+mod search_index;
+mod tags;
 
+pub use admin::admin_router;
+pub use exports::exports_router;
+pub use feed::feed_router;
 pub use health::health_check_router;
 pub use posts::posts_router;
+pub use search_index::search_index_router;
+pub use tags::tags_router;
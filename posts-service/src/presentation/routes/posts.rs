@@ -6,7 +6,13 @@ use axum::{
 };
 
 use crate::presentation::{
-    handlers::{create_post, delete_post, get_post, list_posts, update_post},
+    handlers::{
+        add_reaction, archive_post, create_comment, create_post, delete_comment, delete_post,
+        get_post, get_post_by_slug, get_reactions, get_revision, list_comments,
+        list_comments_by_status, list_posts, list_revisions, list_trash, publish_post,
+        remove_reaction, restore_post, restore_revision, schedule_post, search_posts,
+        set_comment_status, unpublish_post, update_comment, update_post,
+    },
     state::AppState,
 };
 
@@ -14,8 +20,31 @@ pub fn posts_router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", get(list_posts))
         .route("/", post(create_post))
+        .route("/search", get(search_posts))
+        .route("/trash", get(list_trash))
         .route("/{id}", get(get_post))
+        .route("/by-slug/{slug}", get(get_post_by_slug))
         .route("/{id}", put(update_post))
         .route("/{id}", delete(delete_post))
+        .route("/{id}/publish", post(publish_post))
+        .route("/{id}/unpublish", post(unpublish_post))
+        .route("/{id}/schedule", post(schedule_post))
+        .route("/{id}/archive", post(archive_post))
+        .route("/{id}/restore", post(restore_post))
+        .route("/{id}/revisions", get(list_revisions))
+        .route("/{id}/revisions/{rev}", get(get_revision))
+        .route("/{id}/revisions/{rev}/restore", post(restore_revision))
+        .route("/{id}/reactions", get(get_reactions))
+        .route("/{id}/reactions/{kind}", put(add_reaction))
+        .route("/{id}/reactions/{kind}", delete(remove_reaction))
+        .route("/{id}/comments", get(list_comments))
+        .route("/{id}/comments", post(create_comment))
+        .route("/{id}/comments/moderation", get(list_comments_by_status))
+        .route("/{id}/comments/{comment_id}", put(update_comment))
+        .route("/{id}/comments/{comment_id}", delete(delete_comment))
+        .route(
+            "/{id}/comments/{comment_id}/status",
+            put(set_comment_status),
+        )
         .with_state(state)
 }
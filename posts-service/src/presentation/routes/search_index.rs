@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::presentation::{handlers::search_index, state::AppState};
+
+pub fn search_index_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(search_index))
+        .with_state(state)
+}
@@ -0,0 +1,9 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+
+use crate::presentation::{handlers::list_tags, state::AppState};
+
+pub fn tags_router(state: Arc<AppState>) -> Router {
+    Router::new().route("/", get(list_tags)).with_state(state)
+}
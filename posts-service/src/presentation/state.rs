@@ -1,13 +1,17 @@
-use crate::infrastructure::database::RepoProvider;
+use crate::infrastructure::{database::RepoProvider, feed::Feed, search::SearchIndex};
 
 pub struct AppState {
     pub repos: RepoProvider,
+    pub search: SearchIndex,
+    pub feed: Feed,
 }
 
 impl AppState {
-    pub fn new(repo_provider: RepoProvider) -> Self {
+    pub fn new(repo_provider: RepoProvider, search: SearchIndex, feed: Feed) -> Self {
         Self {
             repos: repo_provider,
+            search,
+            feed,
         }
     }
 }
@@ -0,0 +1,294 @@
+mod common;
+
+use common::{CommentResponse, PaginatedResponse, TestApp};
+use uuid::Uuid;
+
+async fn threads(app: &TestApp, post_id: Uuid) -> PaginatedResponse<CommentResponse> {
+    let response = app.list_comments(post_id).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn replies_are_nested_under_their_parents() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    let first = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": alice, "content": "First!" }),
+        )
+        .await;
+    let reply = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": bob, "content": "Reply", "parent_id": first.id }),
+        )
+        .await;
+    let nested = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": alice, "content": "Nested", "parent_id": reply.id }),
+        )
+        .await;
+    let second = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": bob, "content": "Second" }),
+        )
+        .await;
+    assert_eq!(first.depth, 0);
+    assert_eq!(nested.depth, 2);
+
+    let threads = threads(&app, post_id).await;
+    assert_eq!(threads.total, 2);
+    assert_eq!(threads.data[0].id, first.id);
+    assert_eq!(threads.data[0].replies[0].id, reply.id);
+    assert_eq!(threads.data[0].replies[0].replies[0].id, nested.id);
+    assert_eq!(threads.data[0].replies[0].replies[0].content, "Nested");
+    assert_eq!(threads.data[1].id, second.id);
+    assert!(threads.data[1].replies.is_empty());
+}
+
+#[tokio::test]
+async fn replies_cannot_exceed_the_maximum_depth() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let author = Uuid::new_v4();
+
+    let mut parent = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": author, "content": "Depth 0" }),
+        )
+        .await;
+    for depth in 1..=8 {
+        parent = app
+            .create_comment(
+                post_id,
+                serde_json::json!({
+                    "author_id": author,
+                    "content": "Deeper",
+                    "parent_id": parent.id,
+                }),
+            )
+            .await;
+        assert_eq!(parent.depth, depth);
+    }
+
+    let response = app
+        .post_comment(
+            post_id,
+            &serde_json::json!({
+                "author_id": author,
+                "content": "Too deep",
+                "parent_id": parent.id,
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn only_the_author_can_edit_or_delete_a_comment() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let author = Uuid::new_v4();
+    let created = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": author, "content": "Original" }),
+        )
+        .await;
+
+    let response = app
+        .update_comment(
+            post_id,
+            created.id,
+            &serde_json::json!({ "author_id": Uuid::new_v4(), "content": "Hijacked" }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+    let response = app
+        .delete_comment(post_id, created.id, Uuid::new_v4())
+        .await;
+    assert_eq!(response.status(), 403);
+
+    let response = app
+        .update_comment(
+            post_id,
+            created.id,
+            &serde_json::json!({ "author_id": author, "content": "Edited" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let updated: CommentResponse = response.json().await.unwrap();
+    assert_eq!(updated.content, "Edited");
+    assert!(updated.edited_at.is_some());
+}
+
+#[tokio::test]
+async fn deleted_comments_with_replies_become_placeholders() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    let parent = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": alice, "content": "Parent" }),
+        )
+        .await;
+    let reply = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": bob, "content": "Reply", "parent_id": parent.id }),
+        )
+        .await;
+
+    let response = app.delete_comment(post_id, parent.id, alice).await;
+    assert_eq!(response.status(), 200);
+
+    let listed = threads(&app, post_id).await;
+    assert_eq!(listed.total, 1);
+    let placeholder = &listed.data[0];
+    assert!(placeholder.deleted);
+    assert_eq!(placeholder.content, "");
+    assert_eq!(placeholder.author_id, None);
+    assert_eq!(placeholder.replies[0].id, reply.id);
+
+    // Removing the last reply also removes the placeholder it kept alive
+    let response = app.delete_comment(post_id, reply.id, bob).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(threads(&app, post_id).await.total, 0);
+
+    let response = app.delete_comment(post_id, parent.id, alice).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn moderated_comments_are_hidden_from_threads() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let author = Uuid::new_v4();
+
+    let spam = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": author, "content": "Spam" }),
+        )
+        .await;
+    app.create_comment(
+        post_id,
+        serde_json::json!({
+            "author_id": author,
+            "content": "Reply to spam",
+            "parent_id": spam.id,
+        }),
+    )
+    .await;
+    let kept = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": author, "content": "Legit" }),
+        )
+        .await;
+
+    let response = app.set_comment_status(post_id, spam.id, "pending").await;
+    assert_eq!(response.status(), 200);
+
+    let listed = threads(&app, post_id).await;
+    assert_eq!(listed.total, 1);
+    assert_eq!(listed.data[0].id, kept.id);
+
+    let response = app.list_comments_by_status(post_id, "pending").await;
+    assert_eq!(response.status(), 200);
+    let pending: PaginatedResponse<CommentResponse> = response.json().await.unwrap();
+    assert_eq!(pending.total, 1);
+    assert_eq!(pending.data[0].id, spam.id);
+    assert_eq!(pending.data[0].status, "pending");
+
+    let response = app.set_comment_status(post_id, spam.id, "approved").await;
+    assert_eq!(response.status(), 200);
+    let listed = threads(&app, post_id).await;
+    assert_eq!(listed.total, 2);
+    assert_eq!(listed.data[0].replies.len(), 1);
+}
+
+#[tokio::test]
+async fn comments_are_only_accepted_on_published_posts() {
+    let app = common::spawn_app().await;
+    let draft = app
+        .create_post(serde_json::json!({ "status": "draft" }))
+        .await;
+
+    let response = app
+        .post_comment(
+            draft,
+            &serde_json::json!({ "author_id": Uuid::new_v4(), "content": "Early" }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .post_comment(
+            Uuid::new_v4(),
+            &serde_json::json!({ "author_id": Uuid::new_v4(), "content": "Nowhere" }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let published = app.create_post(serde_json::json!({})).await;
+    let response = app
+        .post_comment(
+            published,
+            &serde_json::json!({ "author_id": Uuid::new_v4(), "content": "   " }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn comment_created_events_name_the_post_author() {
+    let app = common::spawn_app().await;
+    let post_author = Uuid::new_v4();
+    let post_id = app
+        .create_post(serde_json::json!({
+            "title": "Commented post",
+            "author_id": post_author,
+        }))
+        .await;
+    let commenter = Uuid::new_v4();
+
+    let parent = app
+        .create_comment(
+            post_id,
+            serde_json::json!({ "author_id": commenter, "content": "Hello" }),
+        )
+        .await;
+    let reply = app
+        .create_comment(
+            post_id,
+            serde_json::json!({
+                "author_id": post_author,
+                "content": "Thanks",
+                "parent_id": parent.id,
+            }),
+        )
+        .await;
+
+    let payloads = app.outbox_payloads("comment_created").await;
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0]["comment_id"], parent.id.to_string());
+    assert_eq!(payloads[0]["post_id"], post_id.to_string());
+    assert_eq!(payloads[0]["post_title"], "Commented post");
+    assert_eq!(payloads[0]["post_author_id"], post_author.to_string());
+    assert_eq!(payloads[0]["author_id"], commenter.to_string());
+    assert_eq!(payloads[0]["parent_id"], serde_json::Value::Null);
+    assert_eq!(payloads[1]["comment_id"], reply.id.to_string());
+    assert_eq!(payloads[1]["parent_author_id"], commenter.to_string());
+}
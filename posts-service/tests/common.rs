@@ -1,11 +1,18 @@
-use std::sync::LazyLock;
+#![allow(dead_code)]
+
+use std::{path::PathBuf, sync::LazyLock};
 
 use anyhow::Context;
-use common::telemetry;
+use common::{outbox::OutBoxEvent, telemetry};
 use posts_service::{
     infrastructure::{
         database::{RepoProvider, bootstrap_db, build_db_url},
+        erasure::UserEraser,
+        export::UserExporter,
+        feed::{Feed, FeedFanout},
         http::create_router,
+        moderation::AuthorStatusTracker,
+        search::{SearchIndex, SearchIndexer},
     },
     presentation::state::AppState,
 };
@@ -21,6 +28,9 @@ static TRACING: LazyLock<()> = LazyLock::new(|| {
 pub struct TestApp {
     pub address: String,
     pub repo_provider: RepoProvider,
+    pub search_index: SearchIndex,
+    pub hot_author_threshold: u64,
+    pub index_path: PathBuf,
     pub db_name: String,
     pub db_config: common::config::DatabaseSettings,
     pub api_client: reqwest::Client,
@@ -28,6 +38,7 @@ pub struct TestApp {
 
 impl Drop for TestApp {
     fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.index_path);
         let db_config = self.db_config.clone();
         let db_name = self.db_name.clone();
         std::thread::spawn(move || {
@@ -55,9 +66,19 @@ pub async fn spawn_app() -> TestApp {
         common::config::get_configuration::<common::config::Settings>("config").unwrap();
     // Randomize database name
     config.database.database_name = Uuid::new_v4().to_string();
+    // Apps share the test Redis, so keep their cache entries apart
+    if let Some(redis) = config.cache.redis.as_mut() {
+        redis.key_prefix = format!("{}:", config.database.database_name);
+    }
 
     configure_database(&config.database).await;
 
+    let index_path = std::env::temp_dir().join(format!(
+        "posts-search-index-{}",
+        config.database.database_name
+    ));
+    config.search.index_path = index_path.to_string_lossy().into_owned();
+
     let listener = tokio::net::TcpListener::bind(format!("{}:0", config.application.host))
         .await
         .unwrap();
@@ -70,7 +91,14 @@ pub async fn spawn_app() -> TestApp {
     let repo_provider = RepoProvider::from_connection(conn, &config.cache)
         .await
         .unwrap();
-    let state = AppState::new(repo_provider.clone());
+    let search_index = SearchIndex::open(&config.search).unwrap();
+    let feed = Feed::new(
+        repo_provider.feeds.clone(),
+        repo_provider.timelines.clone(),
+        repo_provider.posts.clone(),
+        config.feed.hot_author_threshold,
+    );
+    let state = AppState::new(repo_provider.clone(), search_index.clone(), feed);
     let router = create_router(state);
 
     tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
@@ -83,6 +111,9 @@ pub async fn spawn_app() -> TestApp {
     TestApp {
         address: addr.to_string(),
         repo_provider,
+        search_index,
+        hot_author_threshold: config.feed.hot_author_threshold,
+        index_path,
         db_name: config.database.database_name.clone(),
         db_config: config.database.clone(),
         api_client: client,
@@ -173,6 +204,14 @@ pub struct PostRequest {
     pub title: String,
     pub author_id: uuid::Uuid,
     pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedResponse {
+    pub data: Vec<GetPostResponse>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -184,17 +223,152 @@ pub struct ListPostResponse {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub count: u64,
+    pub total: u64,
+    pub page: u64,
+    pub page_count: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GetPostResponse {
     pub id: uuid::Uuid,
     pub title: String,
+    pub slug: String,
     pub content: String,
+    pub content_format: String,
+    pub content_html: String,
+    pub excerpt: String,
+    pub reading_time_minutes: u32,
+    pub status: String,
+    pub publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub version: i32,
+    pub tags: Vec<String>,
+    pub reactions: std::collections::BTreeMap<String, u64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionsResponse {
+    pub post_id: uuid::Uuid,
+    pub reactions: std::collections::BTreeMap<String, u64>,
+    pub user_reactions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentResponse {
+    pub id: uuid::Uuid,
+    pub post_id: uuid::Uuid,
+    pub parent_id: Option<uuid::Uuid>,
+    pub author_id: Option<uuid::Uuid>,
+    pub content: String,
+    pub status: String,
+    pub depth: i32,
+    pub deleted: bool,
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub replies: Vec<CommentResponse>,
+}
+
+fn merge(body: &mut serde_json::Value, fields: serde_json::Value) {
+    if let serde_json::Value::Object(fields) = fields {
+        for (key, value) in fields {
+            body[key] = value;
+        }
+    }
+}
+
+pub fn etag(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .expect("Missing ETag header")
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchResultResponse {
+    pub id: uuid::Uuid,
+    pub title: String,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IndexedPostResponse {
+    pub id: uuid::Uuid,
+    pub title: String,
+    pub slug: String,
+    pub author_id: uuid::Uuid,
+    pub tags: Vec<String>,
+    pub score: f32,
+    pub snippet: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagFacet {
+    pub tag: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthorFacet {
+    pub author_id: uuid::Uuid,
+    pub username: Option<String>,
+    pub count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchFacets {
+    pub tags: Vec<TagFacet>,
+    pub authors: Vec<AuthorFacet>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIndexResponse {
+    pub data: Vec<IndexedPostResponse>,
+    pub total: u64,
+    pub facets: SearchFacets,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagResponse {
+    pub name: String,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevisionResponse {
+    pub post_id: uuid::Uuid,
+    pub revision: i32,
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiffLineResponse {
+    pub op: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevisionDiffResponse {
+    pub revision: RevisionResponse,
+    pub current_title: String,
+    pub title_changed: bool,
+    pub diff: Vec<DiffLineResponse>,
 }
 
 impl TestApp {
-    pub async fn post_post(&self, body: &PostRequest) -> reqwest::Response {
+    pub async fn post_post<T: Serialize + ?Sized>(&self, body: &T) -> reqwest::Response {
         self.api_client
             .post(format!("http://{}/posts", self.address))
             .json(body)
@@ -203,6 +377,23 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    /// Creates a published post and returns its id. `fields` override the
+    /// defaults, so tests only spell out what they care about.
+    pub async fn create_post(&self, fields: serde_json::Value) -> Uuid {
+        let mut body = serde_json::json!({
+            "title": "Post",
+            "author_id": Uuid::new_v4(),
+            "content": "Content.",
+            "status": "published",
+        });
+        merge(&mut body, fields);
+
+        let response = self.post_post(&body).await;
+        assert_eq!(response.status(), 200);
+        let created: CreatePostResponse = response.json().await.unwrap();
+        created.id
+    }
+
     pub async fn get_post(&self, id: Uuid) -> reqwest::Response {
         self.api_client
             .get(format!("http://{}/posts/{}", self.address, id))
@@ -212,8 +403,13 @@ impl TestApp {
     }
 
     pub async fn list_posts(&self) -> reqwest::Response {
+        self.list_posts_with_query(&[]).await
+    }
+
+    pub async fn list_posts_with_query(&self, query: &[(&str, &str)]) -> reqwest::Response {
         self.api_client
             .get(format!("http://{}/posts", self.address))
+            .query(query)
             .send()
             .await
             .expect("Failed to execute request.")
@@ -223,15 +419,427 @@ impl TestApp {
         &self,
         id: Uuid,
         body: &T,
+        if_match: Option<&str>,
+    ) -> reqwest::Response {
+        let mut request = self
+            .api_client
+            .put(format!("http://{}/posts/{}", self.address, id));
+        if let Some(if_match) = if_match {
+            request = request.header(reqwest::header::IF_MATCH, if_match);
+        }
+        request
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn transition_post(&self, id: Uuid, action: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/posts/{}/{}", self.address, id, action))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn schedule_post(
+        &self,
+        id: Uuid,
+        publish_at: chrono::DateTime<chrono::Utc>,
     ) -> reqwest::Response {
         self.api_client
-            .put(format!("http://{}/posts/{}", self.address, id))
+            .post(format!("http://{}/posts/{}/schedule", self.address, id))
+            .json(&serde_json::json!({ "publish_at": publish_at }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_revisions(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/posts/{}/revisions", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_revision(&self, id: Uuid, revision: i32) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/posts/{}/revisions/{}",
+                self.address, id, revision
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn restore_revision(&self, id: Uuid, revision: i32) -> reqwest::Response {
+        self.api_client
+            .post(format!(
+                "http://{}/posts/{}/revisions/{}/restore",
+                self.address, id, revision
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn outbox_event_types(&self) -> Vec<String> {
+        use sea_orm::{EntityTrait, QueryOrder};
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        common::outbox::Entity::find()
+            .order_by_asc(common::outbox::Column::CreatedAt)
+            .all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.event_type)
+            .collect()
+    }
+
+    pub async fn outbox_payloads(&self, event_type: &str) -> Vec<serde_json::Value> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        common::outbox::Entity::find()
+            .filter(common::outbox::Column::EventType.eq(event_type))
+            .order_by_asc(common::outbox::Column::CreatedAt)
+            .all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.payload)
+            .collect()
+    }
+
+    pub async fn outbox_events(&self) -> Vec<OutBoxEvent> {
+        use sea_orm::{EntityTrait, QueryOrder};
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        common::outbox::Entity::find()
+            .order_by_asc(common::outbox::Column::CreatedAt)
+            .all(&conn)
+            .await
+            .unwrap()
+    }
+
+    pub fn indexer(&self) -> SearchIndexer {
+        SearchIndexer::new(self.repo_provider.posts.clone(), self.search_index.clone())
+    }
+
+    /// Feeds every outbox event written so far through the search indexer, standing in
+    /// for the Pub/Sub subscription.
+    pub async fn index_outbox_events(&self) {
+        let indexer = self.indexer();
+        for event in self.outbox_events().await {
+            indexer.handle_event(&event).await.unwrap();
+        }
+    }
+
+    pub fn fanout(&self) -> FeedFanout {
+        FeedFanout::new(
+            self.repo_provider.feeds.clone(),
+            self.repo_provider.timelines.clone(),
+            self.repo_provider.posts.clone(),
+            self.hot_author_threshold,
+        )
+    }
+
+    pub fn eraser(&self) -> UserEraser {
+        UserEraser::new(
+            self.repo_provider.posts.clone(),
+            self.repo_provider.reactions.clone(),
+        )
+    }
+
+    pub fn exporter(&self) -> UserExporter {
+        UserExporter::new(self.repo_provider.posts.clone())
+    }
+
+    pub fn author_status(&self) -> AuthorStatusTracker {
+        AuthorStatusTracker::new(self.repo_provider.posts.clone())
+    }
+
+    /// Feeds every outbox event written so far through the feed fan-out.
+    pub async fn fan_out_outbox_events(&self) {
+        let fanout = self.fanout();
+        for event in self.outbox_events().await {
+            fanout.handle_event(&event).await.unwrap();
+        }
+    }
+
+    pub async fn get_feed(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/feed", self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn search_index(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/search", self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn rebuild_search_index(&self) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/admin/search/rebuild", self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn search_posts(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/posts/search", self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_post_by_slug(&self, slug: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/posts/by-slug/{}", self.address, slug))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Fetches an export part from the path its `user_export_part_ready`
+    /// event points at.
+    pub async fn get_export_part(&self, path: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}{}", self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_tags(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/tags", self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn add_reaction(
+        &self,
+        post_id: Uuid,
+        kind: &str,
+        user_id: Uuid,
+    ) -> reqwest::Response {
+        self.api_client
+            .put(format!(
+                "http://{}/posts/{}/reactions/{}",
+                self.address, post_id, kind
+            ))
+            .json(&serde_json::json!({ "user_id": user_id }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn remove_reaction(
+        &self,
+        post_id: Uuid,
+        kind: &str,
+        user_id: Uuid,
+    ) -> reqwest::Response {
+        self.api_client
+            .delete(format!(
+                "http://{}/posts/{}/reactions/{}",
+                self.address, post_id, kind
+            ))
+            .query(&[("user_id", user_id)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_reactions(&self, post_id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/posts/{}/reactions",
+                self.address, post_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Writes straight to Postgres, bypassing the cache layer.
+    pub async fn uncached_posts(
+        &self,
+    ) -> posts_service::infrastructure::database::seaorm::SeaOrmPostRepository {
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+        posts_service::infrastructure::database::seaorm::SeaOrmPostRepository::new(conn)
+    }
+
+    /// Counts as written back to Postgres, bypassing the cache layer.
+    pub async fn stored_reaction_counts(
+        &self,
+        post_id: Uuid,
+    ) -> std::collections::BTreeMap<String, u64> {
+        use posts_service::{
+            domain::ReactionRepository, infrastructure::database::SeaOrmReactionRepository,
+        };
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        let counts = SeaOrmReactionRepository::new(conn)
+            .reaction_counts(&[post_id.into()])
+            .await
+            .unwrap();
+        serde_json::from_value(serde_json::to_value(&counts[&post_id.into()]).unwrap()).unwrap()
+    }
+
+    /// Creates a top-level comment by a random author unless `fields` say
+    /// otherwise.
+    pub async fn create_comment(
+        &self,
+        post_id: Uuid,
+        fields: serde_json::Value,
+    ) -> CommentResponse {
+        let mut body = serde_json::json!({
+            "author_id": Uuid::new_v4(),
+            "content": "Comment.",
+        });
+        merge(&mut body, fields);
+
+        let response = self.post_comment(post_id, &body).await;
+        assert_eq!(response.status(), 200);
+        response.json().await.unwrap()
+    }
+
+    pub async fn post_comment<T: Serialize + ?Sized>(
+        &self,
+        post_id: Uuid,
+        body: &T,
+    ) -> reqwest::Response {
+        self.api_client
+            .post(format!(
+                "http://{}/posts/{}/comments",
+                self.address, post_id
+            ))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_comments(&self, post_id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/posts/{}/comments",
+                self.address, post_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_comments_by_status(&self, post_id: Uuid, status: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/posts/{}/comments/moderation",
+                self.address, post_id
+            ))
+            .query(&[("status", status)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_comment<T: Serialize + ?Sized>(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        body: &T,
+    ) -> reqwest::Response {
+        self.api_client
+            .put(format!(
+                "http://{}/posts/{}/comments/{}",
+                self.address, post_id, comment_id
+            ))
             .json(body)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
+    pub async fn delete_comment(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        author_id: Uuid,
+    ) -> reqwest::Response {
+        self.api_client
+            .delete(format!(
+                "http://{}/posts/{}/comments/{}",
+                self.address, post_id, comment_id
+            ))
+            .query(&[("author_id", author_id)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn set_comment_status(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        status: &str,
+    ) -> reqwest::Response {
+        self.api_client
+            .put(format!(
+                "http://{}/posts/{}/comments/{}/status",
+                self.address, post_id, comment_id
+            ))
+            .json(&serde_json::json!({ "status": status }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_trash(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/posts/trash", self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn restore_post(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/posts/{}/restore", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn delete_post(&self, id: Uuid) -> reqwest::Response {
         self.api_client
             .delete(format!("http://{}/posts/{}", self.address, id))
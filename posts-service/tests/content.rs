@@ -0,0 +1,193 @@
+mod common;
+
+use common::{GetPostResponse, TestApp, etag};
+
+async fn create_formatted_post(
+    app: &TestApp,
+    content: &str,
+    format: Option<&str>,
+) -> GetPostResponse {
+    let mut fields = serde_json::json!({
+        "title": "Formatting",
+        "content": content,
+        "status": "draft",
+    });
+    if let Some(format) = format {
+        fields["content_format"] = format.into();
+    }
+
+    let id = app.create_post(fields).await;
+    app.get_post(id).await.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn markdown_is_the_default_format_and_renders_heading_anchors() {
+    let app = common::spawn_app().await;
+
+    let post = create_formatted_post(
+        &app,
+        "# Getting started\n\nSome **bold** text.\n\n## Getting started\n",
+        None,
+    )
+    .await;
+
+    assert_eq!(post.content_format, "markdown");
+    assert!(
+        post.content_html
+            .contains("<h1 id=\"getting-started\">Getting started</h1>")
+    );
+    assert!(
+        post.content_html
+            .contains("<h2 id=\"getting-started-2\">Getting started</h2>")
+    );
+    assert!(post.content_html.contains("<strong>bold</strong>"));
+}
+
+#[tokio::test]
+async fn markdown_code_blocks_are_highlighted() {
+    let app = common::spawn_app().await;
+
+    let post = create_formatted_post(&app, "```rust\nfn main() {}\n```\n", None).await;
+
+    assert!(
+        post.content_html
+            .contains("<pre><code class=\"language-rust\">")
+    );
+    assert!(post.content_html.contains("<span class=\""));
+    assert!(post.content_html.contains("main"));
+}
+
+#[tokio::test]
+async fn rendered_html_is_sanitized() {
+    let app = common::spawn_app().await;
+
+    let markdown = create_formatted_post(
+        &app,
+        "Hello <script>alert(1)</script> [link](javascript:alert(1)) <img src=x onerror=alert(1)>",
+        Some("markdown"),
+    )
+    .await;
+    assert!(!markdown.content_html.contains("<script"));
+    assert!(!markdown.content_html.contains("javascript:"));
+    assert!(!markdown.content_html.contains("onerror"));
+
+    let html = create_formatted_post(
+        &app,
+        "<p onclick=\"x()\">Hi <b>there</b></p><iframe src=\"https://example.com\"></iframe>",
+        Some("html"),
+    )
+    .await;
+    assert_eq!(html.content_format, "html");
+    assert_eq!(html.content_html, "<p>Hi <b>there</b></p>");
+}
+
+#[tokio::test]
+async fn plain_text_is_escaped_into_paragraphs() {
+    let app = common::spawn_app().await;
+
+    let post =
+        create_formatted_post(&app, "a < b\nstill first\n\n# not a heading", Some("plain")).await;
+
+    assert_eq!(
+        post.content_html,
+        "<p>a &lt; b<br>\nstill first</p>\n<p># not a heading</p>\n"
+    );
+}
+
+#[tokio::test]
+async fn excerpt_and_reading_time_are_derived_from_the_text() {
+    let app = common::spawn_app().await;
+
+    let content = format!("# Title\n\n{}", "word ".repeat(450));
+    let post = create_formatted_post(&app, &content, None).await;
+
+    assert_eq!(post.reading_time_minutes, 3);
+    assert!(post.excerpt.starts_with("Title word word"));
+    assert!(post.excerpt.ends_with('…'));
+    assert!(post.excerpt.chars().count() <= 201);
+
+    let short = create_formatted_post(&app, "Just *a* few words.", None).await;
+    assert_eq!(short.reading_time_minutes, 1);
+    assert_eq!(short.excerpt, "Just a few words.");
+
+    let response = app.list_posts().await;
+    let list: serde_json::Value = response.json().await.unwrap();
+    assert!(
+        list["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|post| post["excerpt"].is_string() && post["reading_time_minutes"].is_u64())
+    );
+}
+
+#[tokio::test]
+async fn updates_and_restores_rerender_the_content() {
+    let app = common::spawn_app().await;
+    let created = create_formatted_post(&app, "*first*", None).await;
+    let response = app.get_post(created.id).await;
+    let version = etag(&response);
+
+    let response = app
+        .update_post(
+            created.id,
+            &serde_json::json!({
+                "id": created.id,
+                "title": "Formatting",
+                "content": "*second*",
+                "content_format": "plain",
+                "created_at": chrono::Utc::now(),
+                "updated_at": chrono::Utc::now(),
+            }),
+            Some(&version),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let updated: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(updated.content_format, "plain");
+    assert_eq!(updated.content_html, "<p>*second*</p>\n");
+    assert_eq!(updated.excerpt, "*second*");
+
+    let response = app.restore_revision(created.id, 1).await;
+    assert_eq!(response.status(), 200);
+    let restored: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(restored.content, "*first*");
+    assert_eq!(restored.content_html, "<p>*first*</p>\n");
+    assert_eq!(restored.excerpt, "*first*");
+
+    let fetched: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.content_html, restored.content_html);
+}
+
+#[tokio::test]
+async fn existing_posts_get_their_excerpts_backfilled() {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let app = common::spawn_app().await;
+    let content = format!("<h1>Legacy</h1>\n<p>{}</p>", "word ".repeat(250));
+    let id = app
+        .create_post(serde_json::json!({ "content": content, "content_format": "html" }))
+        .await;
+
+    let url = posts_service::infrastructure::database::build_db_url(&app.db_config)
+        .await
+        .unwrap();
+    let conn = sea_orm::Database::connect(&url).await.unwrap();
+    Migrator::down(&conn, Some(1)).await.unwrap();
+    Migrator::up(&conn, None).await.unwrap();
+
+    let row = conn
+        .query_one_raw(Statement::from_string(
+            conn.get_database_backend(),
+            format!("SELECT excerpt, reading_time_minutes FROM posts WHERE id = '{id}'"),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    let excerpt: String = row.try_get("", "excerpt").unwrap();
+    let reading_time: i32 = row.try_get("", "reading_time_minutes").unwrap();
+    assert!(excerpt.starts_with("Legacy word word"));
+    assert!(excerpt.ends_with('…'));
+    assert_eq!(reading_time, 2);
+}
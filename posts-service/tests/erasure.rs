@@ -0,0 +1,142 @@
+mod common;
+
+use common::{CommentResponse, PaginatedResponse, ReactionsResponse, TestApp};
+use uuid::Uuid;
+
+fn user_deleted(user_id: Uuid, erasure_id: Option<Uuid>) -> ::common::outbox::OutBoxEvent {
+    ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: "user_deleted".to_string(),
+        payload: serde_json::json!({ "id": user_id, "erasure_id": erasure_id }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    }
+}
+
+async fn acknowledgements(app: &TestApp) -> Vec<serde_json::Value> {
+    app.outbox_events()
+        .await
+        .into_iter()
+        .filter(|event| event.event_type == "user_erasure_step_completed")
+        .map(|event| event.payload)
+        .collect()
+}
+
+#[tokio::test]
+async fn erasure_removes_the_users_content_and_acknowledges() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let own_post = app
+        .create_post(serde_json::json!({ "title": "Mine", "author_id": user }))
+        .await;
+    let other_post = app
+        .create_post(serde_json::json!({ "title": "Theirs", "author_id": other }))
+        .await;
+
+    let own_comment = app
+        .create_comment(
+            other_post,
+            serde_json::json!({ "author_id": user, "content": "Something personal" }),
+        )
+        .await
+        .id;
+    app.create_comment(
+        other_post,
+        serde_json::json!({ "author_id": other, "parent_id": own_comment }),
+    )
+    .await;
+    assert_eq!(
+        app.add_reaction(other_post, "like", user).await.status(),
+        200
+    );
+    assert_eq!(
+        app.add_reaction(other_post, "love", other).await.status(),
+        200
+    );
+
+    let erasure_id = Uuid::new_v4();
+    app.eraser()
+        .handle_event(&user_deleted(user, Some(erasure_id)))
+        .await
+        .unwrap();
+
+    assert_eq!(app.get_post(own_post).await.status(), 404);
+    assert_eq!(app.get_post(other_post).await.status(), 200);
+
+    let response = app.list_comments(other_post).await;
+    let threads: PaginatedResponse<CommentResponse> = response.json().await.unwrap();
+    let placeholder = &threads.data[0];
+    assert_eq!(placeholder.id, own_comment);
+    assert!(placeholder.deleted);
+    assert!(placeholder.content.is_empty());
+    assert_eq!(placeholder.replies.len(), 1);
+
+    let response = app.get_reactions(other_post).await;
+    let reactions: ReactionsResponse = response.json().await.unwrap();
+    assert_eq!(reactions.reactions.get("like"), None);
+    assert_eq!(reactions.reactions.get("love"), Some(&1));
+
+    let acks = acknowledgements(&app).await;
+    assert_eq!(acks.len(), 1);
+    assert_eq!(acks[0]["erasure_id"], erasure_id.to_string());
+    assert_eq!(acks[0]["step"], "posts");
+    assert_eq!(acks[0]["posts_deleted"], 1);
+    assert_eq!(acks[0]["comments_cleared"], 1);
+
+    let erased: Vec<_> = app
+        .outbox_events()
+        .await
+        .into_iter()
+        .filter(|event| event.event_type == "post_erased")
+        .map(|event| event.aggregate_id)
+        .collect();
+    assert_eq!(erased, vec![own_post]);
+}
+
+#[tokio::test]
+async fn redelivered_erasure_acknowledges_again() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    app.create_post(serde_json::json!({ "title": "Mine", "author_id": user }))
+        .await;
+
+    let event = user_deleted(user, Some(Uuid::new_v4()));
+    app.eraser().handle_event(&event).await.unwrap();
+    app.eraser().handle_event(&event).await.unwrap();
+
+    let acks = acknowledgements(&app).await;
+    assert_eq!(acks.len(), 2);
+    assert_eq!(acks[1]["posts_deleted"], 0);
+}
+
+#[tokio::test]
+async fn plain_deletion_leaves_content_until_the_purge_erases_it() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({ "title": "Mine", "author_id": user }))
+        .await;
+
+    app.eraser()
+        .handle_event(&user_deleted(user, None))
+        .await
+        .unwrap();
+
+    assert_eq!(app.get_post(post).await.status(), 200);
+    assert!(acknowledgements(&app).await.is_empty());
+
+    // Once the user has been in the trash long enough the purge erases them
+    app.eraser()
+        .handle_event(&user_deleted(user, Some(Uuid::new_v4())))
+        .await
+        .unwrap();
+
+    assert_eq!(app.get_post(post).await.status(), 404);
+    assert_eq!(acknowledgements(&app).await.len(), 1);
+}
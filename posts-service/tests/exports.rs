@@ -0,0 +1,135 @@
+mod common;
+
+use uuid::Uuid;
+
+fn export_requested(user_id: Uuid, export_id: Uuid) -> ::common::outbox::OutBoxEvent {
+    ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: "user_export_requested".to_string(),
+        payload: serde_json::json!({ "export_id": export_id, "user_id": user_id }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    }
+}
+
+#[tokio::test]
+async fn export_sends_the_users_posts_comments_and_reactions() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let own_post = app
+        .create_post(serde_json::json!({ "title": "Mine", "author_id": user }))
+        .await;
+    let trashed = app
+        .create_post(serde_json::json!({ "title": "Trashed", "author_id": user }))
+        .await;
+    let other_post = app
+        .create_post(serde_json::json!({ "title": "Theirs", "author_id": other }))
+        .await;
+    assert_eq!(app.delete_post(trashed).await.status(), 200);
+
+    let response = app
+        .post_comment(
+            other_post,
+            &serde_json::json!({ "author_id": user, "content": "Nice" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        app.add_reaction(other_post, "like", user).await.status(),
+        200
+    );
+    assert_eq!(
+        app.add_reaction(own_post, "love", other).await.status(),
+        200
+    );
+
+    let export_id = Uuid::new_v4();
+    app.exporter()
+        .handle_event(&export_requested(user, export_id))
+        .await
+        .unwrap();
+
+    let parts: Vec<_> = app
+        .outbox_events()
+        .await
+        .into_iter()
+        .filter(|event| event.event_type == "user_export_part_ready")
+        .map(|event| event.payload)
+        .collect();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0]["export_id"], export_id.to_string());
+    assert_eq!(parts[0]["part"], "posts");
+    // Only a reference goes out; the data is fetched from the posts service
+    assert!(parts[0].get("data").is_none());
+    let path = parts[0]["path"].as_str().unwrap();
+    assert_eq!(path, format!("/exports/{}", export_id));
+
+    let response = app.get_export_part(path).await;
+    assert_eq!(response.status(), 200);
+    let data: serde_json::Value = response.json().await.unwrap();
+    let post_ids: Vec<_> = data["posts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| post["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(post_ids, vec![own_post.to_string(), trashed.to_string()]);
+    assert_eq!(data["comments"][0]["content"], "Nice");
+    let reactions = data["reactions"].as_array().unwrap();
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0]["post_id"], other_post.to_string());
+}
+
+#[tokio::test]
+async fn other_user_events_are_ignored() {
+    let app = common::spawn_app().await;
+    let mut event = export_requested(Uuid::new_v4(), Uuid::new_v4());
+    event.event_type = "user_registered".to_string();
+
+    app.exporter().handle_event(&event).await.unwrap();
+
+    assert!(
+        app.outbox_events()
+            .await
+            .iter()
+            .all(|event| event.event_type != "user_export_part_ready")
+    );
+}
+
+#[tokio::test]
+async fn unknown_export_part_returns_404() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .get_export_part(&format!("/exports/{}", Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn erasing_an_author_removes_their_export_parts() {
+    let app = common::spawn_app().await;
+    let user = Uuid::new_v4();
+    app.create_post(serde_json::json!({ "title": "Mine", "author_id": user }))
+        .await;
+    let export_id = Uuid::new_v4();
+    app.exporter()
+        .handle_event(&export_requested(user, export_id))
+        .await
+        .unwrap();
+    let path = format!("/exports/{}", export_id);
+    assert_eq!(app.get_export_part(&path).await.status(), 200);
+
+    let mut erasure = export_requested(user, export_id);
+    erasure.event_type = "user_deleted".to_string();
+    erasure.payload = serde_json::json!({ "id": user, "erasure_id": Uuid::new_v4() });
+    app.eraser().handle_event(&erasure).await.unwrap();
+
+    assert_eq!(app.get_export_part(&path).await.status(), 404);
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use common::{FeedResponse, TestApp};
+use uuid::Uuid;
+
+fn follow_event(
+    event_type: &str,
+    follower_id: Uuid,
+    followee_id: Uuid,
+) -> ::common::outbox::OutBoxEvent {
+    ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: followee_id,
+        event_type: event_type.to_string(),
+        payload: serde_json::json!({ "follower_id": follower_id, "followee_id": followee_id }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    }
+}
+
+async fn follow(app: &TestApp, follower_id: Uuid, followee_id: Uuid) {
+    app.fanout()
+        .handle_event(&follow_event("user_followed", follower_id, followee_id))
+        .await
+        .unwrap();
+}
+
+async fn feed(app: &TestApp, query: &[(&str, &str)]) -> FeedResponse {
+    let response = app.get_feed(query).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+fn ids(feed: &FeedResponse) -> Vec<Uuid> {
+    feed.data.iter().map(|post| post.id).collect()
+}
+
+#[tokio::test]
+async fn feed_shows_published_posts_of_followed_authors_newest_first() {
+    let app = common::spawn_app().await;
+    let reader = Uuid::new_v4();
+    let author = Uuid::new_v4();
+    let stranger = Uuid::new_v4();
+    follow(&app, reader, author).await;
+
+    let first = app
+        .create_post(serde_json::json!({ "title": "First", "author_id": author }))
+        .await;
+    let second = app
+        .create_post(serde_json::json!({ "title": "Second", "author_id": author }))
+        .await;
+    app.create_post(
+        serde_json::json!({ "title": "Draft", "status": "draft", "author_id": author }),
+    )
+    .await;
+    app.create_post(serde_json::json!({ "title": "Elsewhere", "author_id": stranger }))
+        .await;
+    app.fan_out_outbox_events().await;
+
+    let reader_id = reader.to_string();
+    let page = feed(&app, &[("user_id", &reader_id)]).await;
+    assert_eq!(ids(&page), vec![second, first]);
+    assert!(page.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn following_backfills_recent_posts() {
+    let app = common::spawn_app().await;
+    let reader = Uuid::new_v4();
+    let author = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({ "title": "Before the follow", "author_id": author }))
+        .await;
+    app.fan_out_outbox_events().await;
+
+    follow(&app, reader, author).await;
+
+    let reader_id = reader.to_string();
+    assert_eq!(
+        ids(&feed(&app, &[("user_id", &reader_id)]).await),
+        vec![post]
+    );
+}
+
+#[tokio::test]
+async fn feed_pages_with_a_cursor() {
+    let app = common::spawn_app().await;
+    let reader = Uuid::new_v4();
+    let author = Uuid::new_v4();
+    follow(&app, reader, author).await;
+
+    let mut posts = Vec::new();
+    for i in 0..5 {
+        posts.push(
+            app.create_post(
+                serde_json::json!({ "title": format!("Post {i}"), "author_id": author }),
+            )
+            .await,
+        );
+    }
+    app.fan_out_outbox_events().await;
+    posts.reverse();
+
+    let reader_id = reader.to_string();
+    let mut seen = Vec::new();
+    let mut query = vec![("user_id", reader_id.clone()), ("limit", "2".to_string())];
+    loop {
+        let query_refs: Vec<_> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let page = feed(&app, &query_refs).await;
+        assert!(page.data.len() <= 2);
+        seen.extend(ids(&page));
+        match page.next_cursor {
+            Some(cursor) => {
+                query.retain(|(k, _)| *k != "cursor");
+                query.push(("cursor", cursor));
+            }
+            None => break,
+        }
+    }
+    assert_eq!(seen, posts);
+
+    let response = app
+        .get_feed(&[("user_id", &reader_id), ("cursor", "not-a-cursor")])
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn unfollowed_and_deleted_posts_leave_the_feed() {
+    let app = common::spawn_app().await;
+    let reader = Uuid::new_v4();
+    let author = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    follow(&app, reader, author).await;
+    follow(&app, reader, other).await;
+
+    let kept = app
+        .create_post(serde_json::json!({ "title": "Kept", "author_id": author }))
+        .await;
+    let deleted = app
+        .create_post(serde_json::json!({ "title": "Deleted", "author_id": author }))
+        .await;
+    app.create_post(serde_json::json!({ "title": "Unfollowed", "author_id": other }))
+        .await;
+    app.fan_out_outbox_events().await;
+
+    assert_eq!(app.delete_post(deleted).await.status(), 200);
+    app.fanout()
+        .handle_event(&follow_event("user_unfollowed", reader, other))
+        .await
+        .unwrap();
+
+    let reader_id = reader.to_string();
+    assert_eq!(
+        ids(&feed(&app, &[("user_id", &reader_id)]).await),
+        vec![kept]
+    );
+}
+
+#[tokio::test]
+async fn hot_authors_are_merged_in_at_read_time() {
+    let app = common::spawn_app().await;
+    let reader = Uuid::new_v4();
+    let hot = Uuid::new_v4();
+    let regular = Uuid::new_v4();
+    follow(&app, reader, regular).await;
+    for _ in 0..app.hot_author_threshold {
+        follow(&app, Uuid::new_v4(), hot).await;
+    }
+    follow(&app, reader, hot).await;
+
+    let regular_post = app
+        .create_post(serde_json::json!({ "title": "Regular", "author_id": regular }))
+        .await;
+    let hot_post = app
+        .create_post(serde_json::json!({ "title": "Hot", "author_id": hot }))
+        .await;
+    app.fan_out_outbox_events().await;
+
+    // Posts by hot authors are never pushed into timelines
+    let timeline = app
+        .repo_provider
+        .timelines
+        .page(reader.into(), None, 10)
+        .await
+        .unwrap();
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].post_id, regular_post);
+
+    let reader_id = reader.to_string();
+    assert_eq!(
+        ids(&feed(&app, &[("user_id", &reader_id)]).await),
+        vec![hot_post, regular_post]
+    );
+}
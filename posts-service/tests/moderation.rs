@@ -0,0 +1,131 @@
+mod common;
+
+use common::{ListPostResponse, PaginatedResponse, TagResponse, TestApp};
+use uuid::Uuid;
+
+async fn set_status(app: &TestApp, user_id: Uuid, status: &str, version: i32) {
+    let event = ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: "user_status_changed".to_string(),
+        payload: serde_json::json!({
+            "id": user_id,
+            "status": status,
+            "version": version,
+        }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    };
+    app.author_status().handle_event(&event).await.unwrap();
+}
+
+async fn listed_titles(app: &TestApp) -> Vec<String> {
+    let response = app.list_posts().await;
+    assert_eq!(response.status(), 200);
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    listed.data.into_iter().map(|post| post.title).collect()
+}
+
+#[tokio::test]
+async fn suspended_authors_posts_are_hidden_until_reactivation() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({ "title": "Hidden soon", "author_id": author }))
+        .await;
+    app.create_post(serde_json::json!({ "title": "Still visible" }))
+        .await;
+
+    // Warm the cache so hiding has to evict it
+    assert_eq!(app.get_post(post).await.status(), 200);
+
+    set_status(&app, author, "suspended", 2).await;
+    assert_eq!(app.get_post(post).await.status(), 404);
+    assert_eq!(listed_titles(&app).await, vec!["Still visible"]);
+
+    set_status(&app, author, "active", 3).await;
+    assert_eq!(app.get_post(post).await.status(), 200);
+    assert_eq!(listed_titles(&app).await.len(), 2);
+}
+
+#[tokio::test]
+async fn restricted_authors_cannot_write() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({ "title": "Someone else's", "author_id": other }))
+        .await;
+
+    set_status(&app, author, "banned", 2).await;
+
+    assert_eq!(
+        app.post_post(
+            &serde_json::json!({ "title": "Nope", "author_id": author, "content": "Content." })
+        )
+        .await
+        .status(),
+        403
+    );
+    let response = app
+        .post_comment(
+            post,
+            &serde_json::json!({ "author_id": author, "content": "Nope" }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(app.add_reaction(post, "like", author).await.status(), 403);
+}
+
+#[tokio::test]
+async fn stale_status_events_are_ignored() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({ "title": "Mine", "author_id": author }))
+        .await;
+
+    set_status(&app, author, "suspended", 5).await;
+    // A reactivation that was overtaken by the suspension arrives late
+    set_status(&app, author, "active", 4).await;
+
+    assert_eq!(app.get_post(post).await.status(), 404);
+    assert_eq!(
+        app.post_post(
+            &serde_json::json!({ "title": "Again", "author_id": author, "content": "Content." })
+        )
+        .await
+        .status(),
+        403
+    );
+}
+
+#[tokio::test]
+async fn suspended_authors_tags_are_not_counted() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    for (title, author_id) in [("Hidden", author), ("Visible", Uuid::new_v4())] {
+        app.create_post(serde_json::json!({
+            "title": title,
+            "author_id": author_id,
+            "tags": [title.to_lowercase(), "shared"],
+        }))
+        .await;
+    }
+
+    set_status(&app, author, "suspended", 2).await;
+
+    let response = app.list_tags().await;
+    assert_eq!(response.status(), 200);
+    let listed: PaginatedResponse<TagResponse> = response.json().await.unwrap();
+    let counts: Vec<(&str, i64)> = listed
+        .data
+        .iter()
+        .map(|tag| (tag.name.as_str(), tag.post_count))
+        .collect();
+    assert_eq!(counts, vec![("shared", 1), ("visible", 1)]);
+}
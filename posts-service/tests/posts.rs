@@ -1,12 +1,22 @@
 mod common;
 
-use common::{CreatePostResponse, GetPostResponse, ListPostResponse, PostRequest};
+use common::{
+    CreatePostResponse, GetPostResponse, ListPostResponse, PaginatedResponse, PostRequest,
+};
 
 fn sample_post() -> PostRequest {
     PostRequest {
         title: "Test Post".to_string(),
         author_id: uuid::Uuid::new_v4(),
         content: "Test Content".to_string(),
+        status: None,
+    }
+}
+
+fn published_post() -> PostRequest {
+    PostRequest {
+        status: Some("published".to_string()),
+        ..sample_post()
     }
 }
 
@@ -43,6 +53,24 @@ async fn create_post_returns_422_for_missing_fields() {
     assert_eq!(response.status(), 422);
 }
 
+#[tokio::test]
+async fn create_post_returns_field_errors_for_invalid_data() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .post_post(&PostRequest {
+            title: "   ".to_string(),
+            content: String::new(),
+            ..sample_post()
+        })
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["fields"]["title"][0], "Cannot be blank");
+    assert_eq!(body["fields"]["content"][0], "Content cannot be empty");
+}
+
 #[tokio::test]
 async fn get_post_returns_200_for_existing_post() {
     let app = common::spawn_app().await;
@@ -74,8 +102,9 @@ async fn list_posts_returns_empty_list_when_no_posts() {
     let response = app.list_posts().await;
     assert_eq!(response.status(), 200);
 
-    let posts: Vec<ListPostResponse> = response.json().await.unwrap();
-    assert!(posts.is_empty());
+    let posts: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert!(posts.data.is_empty());
+    assert_eq!(posts.total, 0);
 }
 
 #[tokio::test]
@@ -87,16 +116,19 @@ async fn list_posts_returns_all_created_posts() {
             title: "Post 1".to_string(),
             author_id: uuid::Uuid::new_v4(),
             content: "Content 1".to_string(),
+            status: Some("published".to_string()),
         },
         PostRequest {
             title: "Post 2".to_string(),
             author_id: uuid::Uuid::new_v4(),
             content: "Content 2".to_string(),
+            status: Some("published".to_string()),
         },
         PostRequest {
             title: "Post 3".to_string(),
             author_id: uuid::Uuid::new_v4(),
             content: "Content 3".to_string(),
+            status: Some("published".to_string()),
         },
     ];
 
@@ -108,8 +140,217 @@ async fn list_posts_returns_all_created_posts() {
     let response = app.list_posts().await;
     assert_eq!(response.status(), 200);
 
-    let listed: Vec<ListPostResponse> = response.json().await.unwrap();
-    assert_eq!(listed.len(), 3);
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.data.len(), 3);
+    assert_eq!(listed.total, 3);
+}
+
+async fn listed_titles(app: &common::TestApp, query: &[(&str, &str)]) -> (Vec<String>, u64) {
+    let response = app.list_posts_with_query(query).await;
+    assert_eq!(response.status(), 200);
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    (
+        listed.data.into_iter().map(|post| post.title).collect(),
+        listed.total,
+    )
+}
+
+#[tokio::test]
+async fn list_posts_serves_cached_pages_until_posts_change() {
+    use posts_service::domain::PostRepository;
+
+    let app = common::spawn_app().await;
+    let author = uuid::Uuid::new_v4();
+    let mut ids = Vec::new();
+    for title in ["First", "Second"] {
+        let response = app
+            .post_post(&PostRequest {
+                title: title.to_string(),
+                author_id: author,
+                content: "Content".to_string(),
+                status: Some("published".to_string()),
+            })
+            .await;
+        let created: CreatePostResponse = response.json().await.unwrap();
+        ids.push(created.id);
+    }
+    assert_eq!(listed_titles(&app, &[]).await.1, 2);
+    assert_eq!(listed_titles(&app, &[("page_size", "1")]).await.1, 2);
+
+    // Deleted behind the cache's back, so only a cached page still lists it
+    app.uncached_posts()
+        .await
+        .delete_post(ids[1].into())
+        .await
+        .unwrap();
+    assert_eq!(listed_titles(&app, &[]).await.1, 2);
+
+    // Filters and pages are cached apart, and all of them go stale on a write
+    let author = author.to_string();
+    let response = app
+        .post_post(&PostRequest {
+            title: "Third".to_string(),
+            author_id: uuid::Uuid::new_v4(),
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        })
+        .await;
+    assert!(response.status().is_success());
+    let (mut titles, total) = listed_titles(&app, &[]).await;
+    titles.sort();
+    assert_eq!((titles, total), (vec!["First".into(), "Third".into()], 2));
+    assert_eq!(
+        listed_titles(&app, &[("author_id", author.as_str())]).await,
+        (vec!["First".to_string()], 1)
+    );
+    assert_eq!(listed_titles(&app, &[("page_size", "1")]).await.1, 2);
+
+    assert_eq!(app.delete_post(ids[0]).await.status(), 200);
+    assert_eq!(
+        listed_titles(&app, &[("author_id", author.as_str())]).await,
+        (Vec::new(), 0)
+    );
+}
+
+#[tokio::test]
+async fn list_posts_paginates_results() {
+    let app = common::spawn_app().await;
+
+    for i in 0..5 {
+        let post = PostRequest {
+            title: format!("Post {}", i),
+            author_id: uuid::Uuid::new_v4(),
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        };
+        assert!(app.post_post(&post).await.status().is_success());
+    }
+
+    let response = app
+        .list_posts_with_query(&[("page", "2"), ("page_size", "2")])
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.count, 2);
+    assert_eq!(listed.total, 5);
+    assert_eq!(listed.page, 2);
+    assert_eq!(listed.page_count, 3);
+}
+
+#[tokio::test]
+async fn list_posts_filters_by_author_and_title() {
+    let app = common::spawn_app().await;
+    let author_id = uuid::Uuid::new_v4();
+
+    let posts = vec![
+        PostRequest {
+            title: "Rust ownership".to_string(),
+            author_id,
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        },
+        PostRequest {
+            title: "Async Rust".to_string(),
+            author_id,
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        },
+        PostRequest {
+            title: "Rust by someone else".to_string(),
+            author_id: uuid::Uuid::new_v4(),
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        },
+    ];
+
+    for post in &posts {
+        assert!(app.post_post(post).await.status().is_success());
+    }
+
+    let author = author_id.to_string();
+    let response = app
+        .list_posts_with_query(&[("author_id", author.as_str())])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 2);
+
+    let response = app
+        .list_posts_with_query(&[("author_id", author.as_str()), ("title", "async")])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+    assert_eq!(listed.data[0].title, "Async Rust");
+}
+
+#[tokio::test]
+async fn list_posts_title_filter_matches_wildcards_literally() {
+    let app = common::spawn_app().await;
+    for title in ["100% Rust", "1000 Rust tips", "rust_async", "rustXasync"] {
+        app.create_post(serde_json::json!({ "title": title })).await;
+    }
+
+    for (title, expected) in [("_", "rust_async"), ("0%", "100% Rust")] {
+        let response = app.list_posts_with_query(&[("title", title)]).await;
+        let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+        assert_eq!(listed.total, 1);
+        assert_eq!(listed.data[0].title, expected);
+    }
+}
+
+#[tokio::test]
+async fn list_posts_filters_by_created_date_range() {
+    let app = common::spawn_app().await;
+
+    assert!(app.post_post(&published_post()).await.status().is_success());
+
+    let past = (chrono::Utc::now() - chrono::Duration::hours(1)).to_rfc3339();
+    let future = (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+
+    let response = app
+        .list_posts_with_query(&[("created_after", past.as_str())])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+
+    let response = app
+        .list_posts_with_query(&[("created_after", future.as_str())])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 0);
+
+    let response = app
+        .list_posts_with_query(&[("created_before", past.as_str())])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 0);
+}
+
+#[tokio::test]
+async fn list_posts_sorts_by_requested_field() {
+    let app = common::spawn_app().await;
+
+    for title in ["Bravo", "Charlie", "Alpha"] {
+        let post = PostRequest {
+            title: title.to_string(),
+            author_id: uuid::Uuid::new_v4(),
+            content: "Content".to_string(),
+            status: Some("published".to_string()),
+        };
+        assert!(app.post_post(&post).await.status().is_success());
+    }
+
+    let response = app
+        .list_posts_with_query(&[("sort_by", "title"), ("order", "asc")])
+        .await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    let titles: Vec<_> = listed.data.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(titles, vec!["Alpha", "Bravo", "Charlie"]);
+
+    let response = app.list_posts().await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    let titles: Vec<_> = listed.data.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(titles, vec!["Alpha", "Charlie", "Bravo"]);
 }
 
 #[tokio::test]
@@ -120,23 +361,156 @@ async fn update_post_persists_changes() {
     let created: CreatePostResponse = app.post_post(&post).await.json().await.unwrap();
 
     // Fetch the full post so we have all fields for the PUT body
-    let original: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    let response = app.get_post(created.id).await;
+    let etag = common::etag(&response);
+    let original: GetPostResponse = response.json().await.unwrap();
 
     let update_body = serde_json::json!({
         "id": original.id,
         "title": "Updated Title",
-        "author_id": uuid::Uuid::new_v4(),
         "content": "Updated Content",
         "created_at": original.created_at,
         "updated_at": chrono::Utc::now(),
     });
 
-    let response = app.update_post(created.id, &update_body).await;
+    let response = app.update_post(created.id, &update_body, Some(&etag)).await;
     assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"2\"");
+
+    let response = app.get_post(created.id).await;
+    assert_eq!(common::etag(&response), "\"2\"");
 
-    let updated: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    let updated: GetPostResponse = response.json().await.unwrap();
     assert_eq!(updated.title, "Updated Title");
     assert_eq!(updated.content, "Updated Content");
+    assert_eq!(updated.version, 2);
+}
+
+fn update_body(id: uuid::Uuid, title: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "title": title,
+        "content": "Concurrent edit",
+        "created_at": chrono::Utc::now(),
+        "updated_at": chrono::Utc::now(),
+    })
+}
+
+#[tokio::test]
+async fn get_post_returns_etag() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let response = app.get_post(created.id).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"1\"");
+}
+
+#[tokio::test]
+async fn update_post_without_if_match_returns_428() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let response = app
+        .update_post(
+            created.id,
+            &update_body(created.id, "No precondition"),
+            None,
+        )
+        .await;
+    assert_eq!(response.status(), 428);
+}
+
+#[tokio::test]
+async fn update_post_rejects_invalid_data() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_post(created.id).await);
+
+    let response = app
+        .update_post(
+            created.id,
+            &update_body(created.id, &"x".repeat(201)),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body["fields"]["title"][0],
+        "Title must be between 1-200 characters"
+    );
+
+    let fetched: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.title, "Test Post");
+}
+
+#[tokio::test]
+async fn update_post_with_stale_etag_returns_412() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_post(created.id).await);
+
+    let first = app
+        .update_post(
+            created.id,
+            &update_body(created.id, "First editor"),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(first.status(), 200);
+
+    let second = app
+        .update_post(
+            created.id,
+            &update_body(created.id, "Second editor"),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(second.status(), 412);
+
+    let fetched: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.title, "First editor");
+}
+
+#[tokio::test]
+async fn update_post_accepts_wildcard_if_match() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let response = app
+        .update_post(
+            created.id,
+            &update_body(created.id, "Any version"),
+            Some("*"),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let response = app
+        .update_post(
+            uuid::Uuid::new_v4(),
+            &update_body(created.id, "Missing"),
+            Some("*"),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn status_transitions_change_the_etag() {
+    let app = common::spawn_app().await;
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_post(created.id).await);
+
+    app.transition_post(created.id, "publish").await;
+
+    assert_ne!(common::etag(&app.get_post(created.id).await), etag);
+    let response = app
+        .update_post(created.id, &update_body(created.id, "Stale"), Some(&etag))
+        .await;
+    assert_eq!(response.status(), 412);
 }
 
 #[tokio::test]
@@ -170,3 +544,149 @@ async fn get_post_twice_returns_same_data() {
     assert_eq!(first.title, second.title);
     assert_eq!(first.content, second.content);
 }
+
+#[tokio::test]
+async fn draft_posts_are_hidden_from_default_listing() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+    assert!(app.post_post(&published_post()).await.status().is_success());
+
+    let listed: PaginatedResponse<ListPostResponse> = app.list_posts().await.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+    assert_ne!(listed.data[0].id, created.id);
+
+    let response = app.list_posts_with_query(&[("status", "draft")]).await;
+    let listed: PaginatedResponse<ListPostResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+    assert_eq!(listed.data[0].id, created.id);
+}
+
+#[tokio::test]
+async fn publish_and_unpublish_toggle_visibility() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let response = app.transition_post(created.id, "publish").await;
+    assert_eq!(response.status(), 200);
+    let published: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(published.status, "published");
+    assert!(published.published_at.is_some());
+
+    let listed: PaginatedResponse<ListPostResponse> = app.list_posts().await.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+
+    let response = app.transition_post(created.id, "unpublish").await;
+    assert_eq!(response.status(), 200);
+    let unpublished: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(unpublished.status, "draft");
+
+    let listed: PaginatedResponse<ListPostResponse> = app.list_posts().await.json().await.unwrap();
+    assert_eq!(listed.total, 0);
+}
+
+#[tokio::test]
+async fn publishing_a_published_post_returns_409() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&published_post()).await.json().await.unwrap();
+
+    let response = app.transition_post(created.id, "publish").await;
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn transition_returns_404_for_nonexistent_post() {
+    let app = common::spawn_app().await;
+
+    let response = app.transition_post(uuid::Uuid::new_v4(), "publish").await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn schedule_post_rejects_past_publish_at() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let publish_at = chrono::Utc::now() - chrono::Duration::hours(1);
+    let response = app.schedule_post(created.id, publish_at).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn scheduler_publishes_due_posts() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+
+    let publish_at = chrono::Utc::now() + chrono::Duration::minutes(5);
+    let response = app.schedule_post(created.id, publish_at).await;
+    assert_eq!(response.status(), 200);
+    let scheduled: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(scheduled.status, "scheduled");
+
+    let published = app
+        .repo_provider
+        .posts
+        .publish_due_posts(chrono::Utc::now())
+        .await
+        .unwrap();
+    assert!(published.is_empty());
+
+    let published = app
+        .repo_provider
+        .posts
+        .publish_due_posts(publish_at + chrono::Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(published.len(), 1);
+
+    let fetched: GetPostResponse = app.get_post(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.status, "published");
+    assert!(fetched.publish_at.is_none());
+}
+
+#[tokio::test]
+async fn post_published_event_is_emitted_only_when_post_goes_live() {
+    let app = common::spawn_app().await;
+
+    let created: CreatePostResponse = app.post_post(&sample_post()).await.json().await.unwrap();
+    assert_eq!(app.outbox_event_types().await, vec!["post_created"]);
+
+    app.transition_post(created.id, "publish").await;
+    assert_eq!(
+        app.outbox_event_types().await,
+        vec!["post_created", "post_published"]
+    );
+}
+
+#[tokio::test]
+async fn update_post_cannot_move_the_post_to_another_author() {
+    let app = common::spawn_app().await;
+    let author_id = uuid::Uuid::new_v4();
+    let id = app
+        .create_post(serde_json::json!({ "author_id": author_id }))
+        .await;
+
+    let body = serde_json::json!({
+        "title": "Taken over",
+        "author_id": uuid::Uuid::new_v4(),
+        "content": "Content.",
+    });
+    let response = app.update_post(id, &body, Some("*")).await;
+    assert_eq!(response.status(), 400);
+
+    // Naming the current author is fine
+    let body = serde_json::json!({
+        "title": "Kept",
+        "author_id": author_id,
+        "content": "Content.",
+    });
+    assert_eq!(app.update_post(id, &body, Some("*")).await.status(), 200);
+
+    let fetched: serde_json::Value = app.get_post(id).await.json().await.unwrap();
+    assert_eq!(fetched["title"], "Kept");
+    assert_eq!(fetched["author_id"], author_id.to_string());
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{GetPostResponse, ReactionsResponse, TestApp};
+use uuid::Uuid;
+
+async fn react(app: &TestApp, post_id: Uuid, kind: &str, user_id: Uuid) -> ReactionsResponse {
+    let response = app.add_reaction(post_id, kind, user_id).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+fn counts(entries: &[(&str, u64)]) -> BTreeMap<String, u64> {
+    entries
+        .iter()
+        .map(|(kind, count)| (kind.to_string(), *count))
+        .collect()
+}
+
+#[tokio::test]
+async fn adding_a_reaction_is_idempotent() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    react(&app, post_id, "like", alice).await;
+    let body = react(&app, post_id, "like", alice).await;
+    assert_eq!(body.reactions, counts(&[("like", 1)]));
+    assert_eq!(body.user_reactions, vec!["like"]);
+
+    react(&app, post_id, "like", bob).await;
+    let body = react(&app, post_id, "love", bob).await;
+    assert_eq!(body.reactions, counts(&[("like", 2), ("love", 1)]));
+    assert_eq!(body.user_reactions, vec!["like", "love"]);
+
+    let response = app.get_post(post_id).await;
+    assert_eq!(response.status(), 200);
+    let post: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(post.reactions, counts(&[("like", 2), ("love", 1)]));
+}
+
+#[tokio::test]
+async fn removing_a_reaction_is_idempotent() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    react(&app, post_id, "insightful", alice).await;
+    react(&app, post_id, "like", bob).await;
+
+    for _ in 0..2 {
+        let response = app.remove_reaction(post_id, "insightful", alice).await;
+        assert_eq!(response.status(), 200);
+        let body: ReactionsResponse = response.json().await.unwrap();
+        assert_eq!(body.reactions, counts(&[("like", 1)]));
+        assert!(body.user_reactions.is_empty());
+    }
+
+    let response = app.get_reactions(post_id).await;
+    assert_eq!(response.status(), 200);
+    let body: ReactionsResponse = response.json().await.unwrap();
+    assert_eq!(body.reactions, counts(&[("like", 1)]));
+}
+
+#[tokio::test]
+async fn counts_are_written_back_on_flush() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    let alice = Uuid::new_v4();
+    react(&app, post_id, "like", alice).await;
+    react(&app, post_id, "funny", alice).await;
+    react(&app, post_id, "like", Uuid::new_v4()).await;
+    app.remove_reaction(post_id, "funny", alice).await;
+
+    assert!(app.stored_reaction_counts(post_id).await.is_empty());
+
+    let flushed = app
+        .repo_provider
+        .reactions
+        .flush_reaction_counts()
+        .await
+        .unwrap();
+    assert_eq!(flushed, 1);
+    assert_eq!(
+        app.stored_reaction_counts(post_id).await,
+        counts(&[("like", 2)])
+    );
+
+    // Nothing is pending any more, and reads now come from the stored counts
+    let flushed = app
+        .repo_provider
+        .reactions
+        .flush_reaction_counts()
+        .await
+        .unwrap();
+    assert_eq!(flushed, 0);
+    let post: GetPostResponse = app.get_post(post_id).await.json().await.unwrap();
+    assert_eq!(post.reactions, counts(&[("like", 2)]));
+}
+
+#[tokio::test]
+async fn list_posts_includes_reaction_counts() {
+    let app = common::spawn_app().await;
+    let post_id = app.create_post(serde_json::json!({})).await;
+    react(&app, post_id, "celebrate", Uuid::new_v4()).await;
+
+    let response = app.list_posts().await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body["data"][0]["reactions"],
+        serde_json::json!({ "celebrate": 1 })
+    );
+}
+
+#[tokio::test]
+async fn reactions_require_a_published_post_and_a_known_kind() {
+    let app = common::spawn_app().await;
+    let draft = app
+        .create_post(serde_json::json!({ "status": "draft" }))
+        .await;
+    let published = app.create_post(serde_json::json!({})).await;
+
+    let response = app.add_reaction(draft, "like", Uuid::new_v4()).await;
+    assert_eq!(response.status(), 400);
+
+    let response = app.add_reaction(published, "meh", Uuid::new_v4()).await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .add_reaction(Uuid::new_v4(), "like", Uuid::new_v4())
+        .await;
+    assert_eq!(response.status(), 404);
+    let response = app
+        .remove_reaction(Uuid::new_v4(), "like", Uuid::new_v4())
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn post_liked_events_are_emitted_for_new_reactions_only() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let post_id = app
+        .create_post(serde_json::json!({ "title": "Reacted post", "author_id": author }))
+        .await;
+    let reader = Uuid::new_v4();
+
+    react(&app, post_id, "like", reader).await;
+    react(&app, post_id, "like", reader).await;
+    app.remove_reaction(post_id, "like", reader).await;
+    react(&app, post_id, "love", reader).await;
+
+    let payloads = app.outbox_payloads("post_liked").await;
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0]["post_id"], post_id.to_string());
+    assert_eq!(payloads[0]["post_title"], "Reacted post");
+    assert_eq!(payloads[0]["post_author_id"], author.to_string());
+    assert_eq!(payloads[0]["user_id"], reader.to_string());
+    assert_eq!(payloads[0]["reaction"], "like");
+    assert_eq!(payloads[1]["reaction"], "love");
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use common::{GetPostResponse, PaginatedResponse, RevisionDiffResponse, RevisionResponse, TestApp};
+
+async fn edit_post(app: &TestApp, id: uuid::Uuid, title: &str, content: &str) {
+    let response = app.get_post(id).await;
+    let etag = common::etag(&response);
+    let original: GetPostResponse = response.json().await.unwrap();
+    let body = serde_json::json!({
+        "id": id,
+        "title": title,
+        "content": content,
+        "created_at": original.created_at,
+        "updated_at": chrono::Utc::now(),
+    });
+    let response = app.update_post(id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn updates_are_recorded_as_revisions() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(
+            serde_json::json!({ "title": "Original Title", "content": "first", "status": "draft" }),
+        )
+        .await;
+
+    edit_post(&app, id, "Second Title", "second").await;
+    edit_post(&app, id, "Third Title", "third").await;
+
+    let response = app.list_revisions(id).await;
+    assert_eq!(response.status(), 200);
+
+    let listed: PaginatedResponse<RevisionResponse> = response.json().await.unwrap();
+    assert_eq!(listed.total, 3);
+
+    let revisions: Vec<_> = listed.data.iter().map(|r| r.revision).collect();
+    assert_eq!(revisions, vec![3, 2, 1]);
+    assert_eq!(listed.data[2].content, "first");
+    assert!(listed.data.iter().all(|r| r.post_id == id));
+}
+
+#[tokio::test]
+async fn concurrent_updates_get_consecutive_revisions() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(
+            serde_json::json!({ "title": "Original Title", "content": "first", "status": "draft" }),
+        )
+        .await;
+
+    let app = &app;
+    let update = |content: &'static str| {
+        let body = serde_json::json!({
+            "id": id,
+            "title": "Original Title",
+            "content": content,
+            "created_at": chrono::Utc::now(),
+            "updated_at": chrono::Utc::now(),
+        });
+        async move { app.update_post(id, &body, Some("*")).await }
+    };
+    let (a, b, c, d) = tokio::join!(update("a"), update("b"), update("c"), update("d"));
+    for response in [a, b, c, d] {
+        assert_eq!(response.status(), 200);
+    }
+
+    let listed: PaginatedResponse<RevisionResponse> =
+        app.list_revisions(id).await.json().await.unwrap();
+    let revisions: Vec<_> = listed.data.iter().map(|r| r.revision).collect();
+    assert_eq!(revisions, vec![5, 4, 3, 2, 1]);
+}
+
+#[tokio::test]
+async fn get_revision_returns_line_diff_against_current_version() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({
+            "title": "Original Title",
+            "content": "line one\nline two\nline three",
+            "status": "draft",
+        }))
+        .await;
+
+    edit_post(
+        &app,
+        id,
+        "New Title",
+        "line one\nline 2\nline three\nline four",
+    )
+    .await;
+
+    let response = app.get_revision(id, 1).await;
+    assert_eq!(response.status(), 200);
+
+    let detail: RevisionDiffResponse = response.json().await.unwrap();
+    assert_eq!(detail.revision.revision, 1);
+    assert_eq!(detail.revision.title, "Original Title");
+    assert_eq!(detail.current_title, "New Title");
+    assert!(detail.title_changed);
+
+    let ops: Vec<(&str, &str)> = detail
+        .diff
+        .iter()
+        .map(|line| (line.op.as_str(), line.text.as_str()))
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            ("equal", "line one"),
+            ("delete", "line two"),
+            ("insert", "line 2"),
+            ("equal", "line three"),
+            ("insert", "line four"),
+        ]
+    );
+    assert_eq!(detail.diff[1].old_line, Some(2));
+    assert_eq!(detail.diff[1].new_line, None);
+    assert_eq!(detail.diff[4].new_line, Some(4));
+}
+
+#[tokio::test]
+async fn restore_revision_reverts_content_and_records_new_revision() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({
+            "title": "Original Title",
+            "content": "good content",
+            "status": "draft",
+        }))
+        .await;
+
+    edit_post(&app, id, "Vandalised", "bad content").await;
+
+    let response = app.restore_revision(id, 1).await;
+    assert_eq!(response.status(), 200);
+
+    let restored: GetPostResponse = app.get_post(id).await.json().await.unwrap();
+    assert_eq!(restored.title, "Original Title");
+    assert_eq!(restored.content, "good content");
+
+    let listed: PaginatedResponse<RevisionResponse> =
+        app.list_revisions(id).await.json().await.unwrap();
+    assert_eq!(listed.total, 3);
+    assert_eq!(listed.data[0].revision, 3);
+    assert_eq!(listed.data[0].content, "good content");
+}
+
+#[tokio::test]
+async fn missing_revision_returns_404() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({
+            "title": "Original Title",
+            "content": "content",
+            "status": "draft",
+        }))
+        .await;
+
+    assert_eq!(app.get_revision(id, 42).await.status(), 404);
+    assert_eq!(app.restore_revision(id, 42).await.status(), 404);
+    assert_eq!(app.list_revisions(uuid::Uuid::new_v4()).await.status(), 404);
+}
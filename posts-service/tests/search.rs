@@ -0,0 +1,118 @@
+mod common;
+
+use common::{PaginatedResponse, SearchResultResponse, TestApp};
+
+async fn search(app: &TestApp, query: &[(&str, &str)]) -> PaginatedResponse<SearchResultResponse> {
+    let response = app.search_posts(query).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn search_finds_published_posts_ranking_title_matches_first() {
+    let app = common::spawn_app().await;
+    let in_content = app
+        .create_post(serde_json::json!({
+            "title": "Weekend notes",
+            "content": "Some thoughts about borrowing in Rust.",
+        }))
+        .await;
+    let in_title = app
+        .create_post(serde_json::json!({
+            "title": "Understanding the Rust borrow checker",
+            "content": "Lifetimes and references explained.",
+        }))
+        .await;
+    app.create_post(serde_json::json!({
+        "title": "Gardening",
+        "content": "Tomatoes need sun.",
+    }))
+    .await;
+    app.create_post(serde_json::json!({
+        "title": "Rust draft",
+        "content": "Unfinished borrowing post.",
+        "status": "draft",
+    }))
+    .await;
+
+    let results = search(&app, &[("q", "borrowing")]).await;
+
+    assert_eq!(results.total, 2);
+    let ids: Vec<_> = results.data.iter().map(|hit| hit.id).collect();
+    assert_eq!(ids, vec![in_title, in_content]);
+    assert!(results.data[0].rank > results.data[1].rank);
+}
+
+#[tokio::test]
+async fn search_returns_escaped_highlighted_snippets() {
+    let app = common::spawn_app().await;
+    app.create_post(serde_json::json!({
+        "title": "Markup",
+        "content": "Never trust <script>alert(1)</script> in a searchable snippet.",
+    }))
+    .await;
+
+    let results = search(&app, &[("q", "searchable")]).await;
+
+    let snippet = &results.data[0].snippet;
+    assert!(snippet.contains("<mark>searchable</mark>"));
+    assert!(snippet.contains("&lt;script&gt;"));
+    assert!(!snippet.contains("<script>"));
+}
+
+#[tokio::test]
+async fn search_supports_web_search_syntax_and_pagination() {
+    let app = common::spawn_app().await;
+    for i in 0..3 {
+        app.create_post(serde_json::json!({
+            "title": format!("Async post {}", i),
+            "content": "Tokio runtime internals",
+        }))
+        .await;
+    }
+    app.create_post(
+        serde_json::json!({ "title": "Blocking post", "content": "Tokio blocking pool" }),
+    )
+    .await;
+
+    let results = search(&app, &[("q", "tokio -blocking"), ("page_size", "2")]).await;
+    assert_eq!(results.total, 3);
+    assert_eq!(results.data.len(), 2);
+
+    let second_page = search(
+        &app,
+        &[("q", "tokio -blocking"), ("page_size", "2"), ("page", "2")],
+    )
+    .await;
+    assert_eq!(second_page.data.len(), 1);
+}
+
+#[tokio::test]
+async fn search_index_follows_updates() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({ "title": "Original", "content": "Nothing special" }))
+        .await;
+
+    let etag = common::etag(&app.get_post(id).await);
+    let body = serde_json::json!({
+        "id": id,
+        "title": "Original",
+        "content": "Now mentions kubernetes",
+        "created_at": chrono::Utc::now(),
+        "updated_at": chrono::Utc::now(),
+    });
+    assert_eq!(app.update_post(id, &body, Some(&etag)).await.status(), 200);
+
+    let results = search(&app, &[("q", "kubernetes")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, id);
+}
+
+#[tokio::test]
+async fn search_rejects_empty_query() {
+    let app = common::spawn_app().await;
+
+    assert_eq!(app.search_posts(&[("q", "   ")]).await.status(), 400);
+    assert_eq!(app.search_posts(&[]).await.status(), 400);
+}
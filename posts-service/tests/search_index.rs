@@ -0,0 +1,276 @@
+mod common;
+
+use common::{SearchIndexResponse, TestApp};
+use uuid::Uuid;
+
+async fn search(app: &TestApp, query: &[(&str, &str)]) -> SearchIndexResponse {
+    let response = app.search_index(query).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+fn user_event(user_id: Uuid, event_type: &str, username: &str) -> ::common::outbox::OutBoxEvent {
+    ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: event_type.to_string(),
+        payload: serde_json::json!({ "id": user_id, "username": username }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    }
+}
+
+#[tokio::test]
+async fn outbox_events_index_published_posts_only() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let published = app
+        .create_post(serde_json::json!({
+            "title": "Rust ownership",
+            "content": "Moves and borrows.",
+            "author_id": author,
+        }))
+        .await;
+    let draft = app
+        .create_post(serde_json::json!({
+            "title": "Rust lifetimes",
+            "content": "Draft about lifetimes.",
+            "status": "draft",
+            "author_id": author,
+        }))
+        .await;
+
+    app.index_outbox_events().await;
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, published);
+
+    assert_eq!(app.transition_post(draft, "publish").await.status(), 200);
+    app.index_outbox_events().await;
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 2);
+
+    assert_eq!(
+        app.transition_post(published, "unpublish").await.status(),
+        200
+    );
+    app.index_outbox_events().await;
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, draft);
+
+    assert_eq!(app.delete_post(draft).await.status(), 200);
+    app.index_outbox_events().await;
+    assert_eq!(search(&app, &[("q", "rust")]).await.total, 0);
+}
+
+#[tokio::test]
+async fn search_tolerates_typos_and_ranks_exact_title_matches_first() {
+    let app = common::spawn_app().await;
+    let exact = app
+        .create_post(serde_json::json!({
+            "title": "Understanding the borrow checker",
+            "content": "Lifetimes and references explained.",
+        }))
+        .await;
+    let fuzzy = app
+        .create_post(serde_json::json!({
+            "title": "Weekend notes",
+            "content": "Some thoughts on borrowed data and the borrow checker.",
+        }))
+        .await;
+    app.index_outbox_events().await;
+
+    let results = search(&app, &[("q", "borow cheker")]).await;
+    assert_eq!(results.total, 2);
+
+    let results = search(&app, &[("q", "borrow checker")]).await;
+    assert_eq!(results.total, 2);
+    assert_eq!(results.data[0].id, exact);
+    assert_eq!(results.data[1].id, fuzzy);
+    assert!(results.data[1].snippet.contains("<mark>borrow</mark>"));
+}
+
+#[tokio::test]
+async fn search_returns_tag_and_author_facets() {
+    let app = common::spawn_app().await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    app.create_post(serde_json::json!({
+        "title": "Async Rust",
+        "content": "Futures.",
+        "author_id": alice,
+        "tags": ["rust", "async"],
+    }))
+    .await;
+    app.create_post(serde_json::json!({
+        "title": "Rust macros",
+        "content": "Macros.",
+        "author_id": alice,
+        "tags": ["rust"],
+    }))
+    .await;
+    let bobs = app
+        .create_post(serde_json::json!({
+            "title": "Rust in Go land",
+            "content": "Go.",
+            "author_id": bob,
+            "tags": ["go"],
+        }))
+        .await;
+    app.index_outbox_events().await;
+    app.indexer()
+        .handle_event(&user_event(alice, "user_registered", "alice"))
+        .await
+        .unwrap();
+
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 3);
+
+    let tags: Vec<(&str, u64)> = results
+        .facets
+        .tags
+        .iter()
+        .map(|facet| (facet.tag.as_str(), facet.count))
+        .collect();
+    assert_eq!(tags[0], ("rust", 2));
+    assert!(tags.contains(&("async", 1)));
+    assert!(tags.contains(&("go", 1)));
+
+    let authors = &results.facets.authors;
+    assert_eq!(authors[0].author_id, alice);
+    assert_eq!(authors[0].username.as_deref(), Some("alice"));
+    assert_eq!(authors[0].count, 2);
+    assert_eq!(authors[1].author_id, bob);
+    assert_eq!(authors[1].username, None);
+
+    let results = search(&app, &[("q", "rust"), ("tag", "Go")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, bobs);
+    assert_eq!(results.data[0].tags, vec!["go"]);
+
+    let alice_id = alice.to_string();
+    let results = search(&app, &[("author_id", alice_id.as_str())]).await;
+    assert_eq!(results.total, 2);
+    assert!(results.data.iter().all(|hit| hit.author_id == alice));
+}
+
+#[tokio::test]
+async fn user_events_keep_author_names_current() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    app.create_post(
+        serde_json::json!({ "title": "Rust", "content": "Content.", "author_id": author }),
+    )
+    .await;
+    app.index_outbox_events().await;
+
+    let indexer = app.indexer();
+    indexer
+        .handle_event(&user_event(author, "user_registered", "before"))
+        .await
+        .unwrap();
+    indexer
+        .handle_event(&user_event(author, "user_updated", "after"))
+        .await
+        .unwrap();
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.facets.authors[0].username.as_deref(), Some("after"));
+
+    indexer
+        .handle_event(&user_event(author, "user_deleted", "after"))
+        .await
+        .unwrap();
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.facets.authors[0].username, None);
+
+    indexer
+        .handle_event(&user_event(author, "user_restored", "after"))
+        .await
+        .unwrap();
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.facets.authors[0].username.as_deref(), Some("after"));
+}
+
+#[tokio::test]
+async fn rebuild_replays_the_posts_table() {
+    let app = common::spawn_app().await;
+    let kept = app
+        .create_post(serde_json::json!({ "title": "Rust testing", "content": "Content." }))
+        .await;
+    let deleted = app
+        .create_post(serde_json::json!({ "title": "Rust benchmarks", "content": "Content." }))
+        .await;
+    app.create_post(
+        serde_json::json!({ "title": "Rust drafts", "content": "Content.", "status": "draft" }),
+    )
+    .await;
+    app.index_outbox_events().await;
+    assert_eq!(search(&app, &[("q", "rust")]).await.total, 2);
+
+    assert_eq!(app.delete_post(deleted).await.status(), 200);
+
+    let response = app.rebuild_search_index().await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["indexed"], 1);
+
+    let results = search(&app, &[("q", "rust")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, kept);
+}
+
+#[tokio::test]
+async fn rebuild_is_only_served_under_admin() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .api_client
+        .post(format!("http://{}/search/rebuild", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn search_rejects_overlong_queries() {
+    let app = common::spawn_app().await;
+
+    let query = "a".repeat(201);
+    let response = app.search_index(&[("q", query.as_str())]).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn suspended_authors_posts_leave_the_index_until_reactivation() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let post = app
+        .create_post(serde_json::json!({
+            "title": "Rust macros",
+            "content": "Declarative macros.",
+            "author_id": author,
+        }))
+        .await;
+    app.index_outbox_events().await;
+    assert_eq!(search(&app, &[("q", "macros")]).await.total, 1);
+
+    let mut event = user_event(author, "user_status_changed", "author");
+    event.payload = serde_json::json!({ "id": author, "status": "suspended", "version": 2 });
+    app.author_status().handle_event(&event).await.unwrap();
+    app.index_outbox_events().await;
+    assert_eq!(search(&app, &[("q", "macros")]).await.total, 0);
+
+    event.payload = serde_json::json!({ "id": author, "status": "active", "version": 3 });
+    app.author_status().handle_event(&event).await.unwrap();
+    app.index_outbox_events().await;
+    let results = search(&app, &[("q", "macros")]).await;
+    assert_eq!(results.total, 1);
+    assert_eq!(results.data[0].id, post);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::{GetPostResponse, TestApp};
+
+async fn create_post_titled(app: &TestApp, title: &str) -> GetPostResponse {
+    let id = app
+        .create_post(serde_json::json!({ "title": title, "status": "draft" }))
+        .await;
+    app.get_post(id).await.json().await.unwrap()
+}
+
+async fn rename_post(app: &TestApp, post: &GetPostResponse, title: &str) {
+    let etag = common::etag(&app.get_post(post.id).await);
+    let body = serde_json::json!({
+        "id": post.id,
+        "title": title,
+        "content": post.content,
+        "created_at": post.created_at,
+        "updated_at": chrono::Utc::now(),
+    });
+    let response = app.update_post(post.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn slugs_are_transliterated_and_deduplicated() {
+    let app = common::spawn_app().await;
+
+    let first = create_post_titled(&app, "Héllo, Wörld! Ünïcode & Rust").await;
+    assert_eq!(first.slug, "hello-world-unicode-rust");
+
+    let second = create_post_titled(&app, "Hello World: Unicode / Rust").await;
+    assert_eq!(second.slug, "hello-world-unicode-rust-2");
+
+    let untitled = create_post_titled(&app, "!!!").await;
+    assert_eq!(untitled.slug, "post");
+}
+
+#[tokio::test]
+async fn get_post_by_slug_returns_post() {
+    let app = common::spawn_app().await;
+    let post = create_post_titled(&app, "Permalinks in Axum").await;
+
+    let response = app.get_post_by_slug("permalinks-in-axum").await;
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().contains_key(reqwest::header::ETAG));
+
+    let fetched: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(fetched.id, post.id);
+
+    assert_eq!(app.get_post_by_slug("no-such-post").await.status(), 404);
+}
+
+#[tokio::test]
+async fn renamed_post_redirects_from_old_slug() {
+    let app = common::spawn_app().await;
+    let post = create_post_titled(&app, "Original Title").await;
+
+    rename_post(&app, &post, "Brand New Title").await;
+
+    let renamed: GetPostResponse = app.get_post(post.id).await.json().await.unwrap();
+    assert_eq!(renamed.slug, "brand-new-title");
+
+    let response = app.get_post_by_slug("original-title").await;
+    assert_eq!(response.status(), 308);
+    assert_eq!(
+        response.headers()[reqwest::header::LOCATION],
+        "/posts/by-slug/brand-new-title"
+    );
+
+    let payloads = app.outbox_payloads("post_updated").await;
+    assert_eq!(payloads[0]["slug"], "brand-new-title");
+    assert_eq!(payloads[0]["previous_slug"], "original-title");
+}
+
+#[tokio::test]
+async fn former_slugs_stay_reserved_for_their_post() {
+    let app = common::spawn_app().await;
+    let post = create_post_titled(&app, "Shared Name").await;
+    rename_post(&app, &post, "Other Name").await;
+
+    // Another post cannot take over the redirecting slug
+    let other = create_post_titled(&app, "Shared Name").await;
+    assert_eq!(other.slug, "shared-name-2");
+
+    // But the original post can take it back
+    rename_post(&app, &post, "Shared Name").await;
+    let restored: GetPostResponse = app.get_post(post.id).await.json().await.unwrap();
+    assert_eq!(restored.slug, "shared-name");
+
+    let response = app.get_post_by_slug("other-name").await;
+    assert_eq!(response.status(), 308);
+}
+
+#[tokio::test]
+async fn slug_of_deleted_post_can_be_reused() {
+    let app = common::spawn_app().await;
+    let post = create_post_titled(&app, "Short Lived").await;
+
+    // Warm the slug cache before deleting
+    assert_eq!(app.get_post_by_slug("short-lived").await.status(), 200);
+    assert_eq!(app.delete_post(post.id).await.status(), 200);
+
+    let replacement = create_post_titled(&app, "Short Lived").await;
+    assert_eq!(replacement.slug, "short-lived");
+
+    let fetched: GetPostResponse = app
+        .get_post_by_slug("short-lived")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(fetched.id, replacement.id);
+}
+
+#[tokio::test]
+async fn concurrent_posts_with_the_same_title_get_distinct_slugs() {
+    let app = common::spawn_app().await;
+
+    let create = || app.create_post(serde_json::json!({ "title": "Race", "status": "draft" }));
+    let (a, b, c, d, e) = tokio::join!(create(), create(), create(), create(), create());
+
+    let mut slugs = Vec::new();
+    for id in [a, b, c, d, e] {
+        let post: GetPostResponse = app.get_post(id).await.json().await.unwrap();
+        slugs.push(post.slug);
+    }
+    slugs.sort();
+    assert_eq!(slugs, vec!["race", "race-2", "race-3", "race-4", "race-5"]);
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{CreatePostResponse, GetPostResponse, PaginatedResponse, TagResponse};
+
+#[tokio::test]
+async fn create_post_normalizes_and_returns_tags() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({
+            "title": "Tagged",
+            "tags": ["Rust", " web  dev ", "rust"],
+        }))
+        .await;
+
+    let fetched: GetPostResponse = app.get_post(id).await.json().await.unwrap();
+    assert_eq!(fetched.tags, vec!["rust", "web-dev"]);
+}
+
+#[tokio::test]
+async fn create_post_rejects_invalid_tags() {
+    let app = common::spawn_app().await;
+
+    let too_many: Vec<String> = (0..11).map(|i| format!("tag{}", i)).collect();
+    for tags in [vec!["  ".to_string()], vec!["x".repeat(33)], too_many] {
+        let response = app
+            .post_post(&serde_json::json!({
+                "title": "Invalid tags",
+                "author_id": uuid::Uuid::new_v4(),
+                "content": "Content",
+                "tags": tags,
+            }))
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+}
+
+#[tokio::test]
+async fn list_posts_filters_by_tag() {
+    let app = common::spawn_app().await;
+    app.create_post(serde_json::json!({ "title": "Rust post", "tags": ["rust"] }))
+        .await;
+    app.create_post(serde_json::json!({ "title": "Rust and web", "tags": ["rust", "web"] }))
+        .await;
+    app.create_post(serde_json::json!({ "title": "Web post", "tags": ["web"] }))
+        .await;
+
+    let listed: PaginatedResponse<GetPostResponse> = app
+        .list_posts_with_query(&[("tag", "Rust")])
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(listed.total, 2);
+    assert!(
+        listed
+            .data
+            .iter()
+            .all(|post| post.tags.contains(&"rust".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn list_tags_returns_published_post_counts() {
+    let app = common::spawn_app().await;
+    app.create_post(serde_json::json!({ "title": "One", "tags": ["rust", "web"] }))
+        .await;
+    app.create_post(serde_json::json!({ "title": "Two", "tags": ["rust"] }))
+        .await;
+
+    // Drafts do not count towards public tag counts
+    app.post_post(&serde_json::json!({
+        "title": "Draft",
+        "author_id": uuid::Uuid::new_v4(),
+        "content": "Draft content",
+        "tags": ["rust", "secret"],
+    }))
+    .await;
+
+    let response = app.list_tags().await;
+    assert_eq!(response.status(), 200);
+
+    let listed: PaginatedResponse<TagResponse> = response.json().await.unwrap();
+    let counts: Vec<(&str, i64)> = listed
+        .data
+        .iter()
+        .map(|tag| (tag.name.as_str(), tag.post_count))
+        .collect();
+    assert_eq!(counts, vec![("rust", 2), ("web", 1)]);
+    assert_eq!(listed.total, 2);
+}
+
+#[tokio::test]
+async fn update_post_replaces_tags_and_reports_changes() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({
+            "title": "Tagged",
+            "tags": ["rust", "web"],
+        }))
+        .await;
+
+    let response = app.get_post(id).await;
+    let etag = common::etag(&response);
+    let original: GetPostResponse = response.json().await.unwrap();
+
+    let body = serde_json::json!({
+        "id": id,
+        "title": original.title,
+        "content": original.content,
+        "tags": ["rust", "async"],
+        "created_at": original.created_at,
+        "updated_at": chrono::Utc::now(),
+    });
+    let response = app.update_post(id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let fetched: GetPostResponse = app.get_post(id).await.json().await.unwrap();
+    assert_eq!(fetched.tags, vec!["async", "rust"]);
+
+    let created = app.outbox_payloads("post_created").await;
+    assert_eq!(created[0]["tags"], serde_json::json!(["rust", "web"]));
+
+    let updated = app.outbox_payloads("post_updated").await;
+    assert_eq!(updated[0]["tags"], serde_json::json!(["async", "rust"]));
+    assert_eq!(updated[0]["tags_added"], serde_json::json!(["async"]));
+    assert_eq!(updated[0]["tags_removed"], serde_json::json!(["web"]));
+}
+
+#[tokio::test]
+async fn update_post_keeps_omitted_tags_and_format() {
+    let app = common::spawn_app().await;
+    let author_id = uuid::Uuid::new_v4();
+    let response = app
+        .post_post(&serde_json::json!({
+            "title": "Plain",
+            "author_id": author_id,
+            "content": "Plain *content*",
+            "content_format": "plain",
+            "status": "published",
+            "tags": ["rust"],
+        }))
+        .await;
+    let etag = common::etag(&response);
+    let created: CreatePostResponse = response.json().await.unwrap();
+
+    let body = serde_json::json!({
+        "title": "Still plain",
+        "content": "Still *plain*",
+    });
+    let response = app.update_post(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let fetched: serde_json::Value = app.get_post(created.id).await.json().await.unwrap();
+    assert_eq!(fetched["title"], "Still plain");
+    assert_eq!(fetched["tags"], serde_json::json!(["rust"]));
+    assert_eq!(fetched["content_format"], "plain");
+    assert_eq!(fetched["author_id"], author_id.to_string());
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{GetPostResponse, PaginatedResponse, SearchIndexResponse, TestApp};
+use uuid::Uuid;
+
+async fn indexed_total(app: &TestApp) -> u64 {
+    let response = app.search_index(&[("q", "rust")]).await;
+    assert_eq!(response.status(), 200);
+    let results: SearchIndexResponse = response.json().await.unwrap();
+    results.total
+}
+
+async fn trash(app: &TestApp, query: &[(&str, &str)]) -> PaginatedResponse<GetPostResponse> {
+    let response = app.list_trash(query).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn deleted_posts_move_to_the_trash() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let kept = app
+        .create_post(serde_json::json!({ "title": "Kept", "author_id": author }))
+        .await;
+    let deleted = app
+        .create_post(serde_json::json!({ "title": "Deleted", "author_id": author }))
+        .await;
+
+    assert_eq!(app.delete_post(deleted).await.status(), 200);
+
+    assert_eq!(app.get_post(deleted).await.status(), 404);
+    assert_eq!(app.delete_post(deleted).await.status(), 404);
+    let listed: PaginatedResponse<GetPostResponse> = app.list_posts().await.json().await.unwrap();
+    assert_eq!(listed.total, 1);
+    assert_eq!(listed.data[0].id, kept);
+
+    let trashed = trash(&app, &[]).await;
+    assert_eq!(trashed.total, 1);
+    assert_eq!(trashed.data[0].id, deleted);
+    assert!(trashed.data[0].deleted_at.is_some());
+
+    let payloads = app.outbox_payloads("post_deleted").await;
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["post_id"], deleted.to_string());
+    assert_eq!(payloads[0]["author_id"], author.to_string());
+}
+
+#[tokio::test]
+async fn restoring_a_post_takes_it_out_of_the_trash() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({ "title": "Restored" }))
+        .await;
+    assert_eq!(app.delete_post(id).await.status(), 200);
+
+    let response = app.restore_post(id).await;
+    assert_eq!(response.status(), 200);
+    let restored: GetPostResponse = response.json().await.unwrap();
+    assert_eq!(restored.deleted_at, None);
+    assert_eq!(restored.version, 3);
+    assert_eq!(restored.slug, "restored");
+
+    assert_eq!(app.get_post(id).await.status(), 200);
+    assert_eq!(trash(&app, &[]).await.total, 0);
+    assert_eq!(app.outbox_payloads("post_restored").await.len(), 1);
+
+    // Only trashed posts can be restored
+    assert_eq!(app.restore_post(id).await.status(), 404);
+    assert_eq!(app.restore_post(Uuid::new_v4()).await.status(), 404);
+}
+
+#[tokio::test]
+async fn restored_post_gets_a_new_slug_if_its_own_was_taken() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let original = app
+        .create_post(serde_json::json!({ "title": "Taken Over", "author_id": author }))
+        .await;
+    assert_eq!(app.delete_post(original).await.status(), 200);
+
+    let replacement = app
+        .create_post(serde_json::json!({ "title": "Taken Over", "author_id": author }))
+        .await;
+    let replacement: GetPostResponse = app.get_post(replacement).await.json().await.unwrap();
+    assert_eq!(replacement.slug, "taken-over");
+
+    let restored: GetPostResponse = app.restore_post(original).await.json().await.unwrap();
+    assert_eq!(restored.slug, "taken-over-2");
+}
+
+#[tokio::test]
+async fn restored_post_does_not_take_another_posts_former_slug() {
+    let app = common::spawn_app().await;
+    let author = Uuid::new_v4();
+    let original = app
+        .create_post(serde_json::json!({ "title": "Taken Over", "author_id": author }))
+        .await;
+    assert_eq!(app.delete_post(original).await.status(), 200);
+
+    // The replacement moves on, keeping the slug as a redirect
+    let replacement = app
+        .create_post(serde_json::json!({ "title": "Taken Over", "author_id": author }))
+        .await;
+    let etag = common::etag(&app.get_post(replacement).await);
+    let body = serde_json::json!({ "title": "Moved On", "content": "Content." });
+    let response = app.update_post(replacement, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let restored: GetPostResponse = app.restore_post(original).await.json().await.unwrap();
+    assert_eq!(restored.slug, "taken-over-2");
+}
+
+#[tokio::test]
+async fn trash_can_be_filtered_by_author() {
+    let app = common::spawn_app().await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    let alices = app
+        .create_post(serde_json::json!({ "title": "Alice's", "author_id": alice }))
+        .await;
+    let bobs = app
+        .create_post(serde_json::json!({ "title": "Bob's", "author_id": bob }))
+        .await;
+    app.delete_post(alices).await;
+    app.delete_post(bobs).await;
+
+    let alice_id = alice.to_string();
+    let trashed = trash(&app, &[("author_id", alice_id.as_str())]).await;
+    assert_eq!(trashed.total, 1);
+    assert_eq!(trashed.data[0].id, alices);
+    assert_eq!(trash(&app, &[]).await.total, 2);
+}
+
+#[tokio::test]
+async fn purge_removes_posts_past_the_retention_window() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({ "title": "Purged" }))
+        .await;
+    app.delete_post(id).await;
+
+    let posts = &app.repo_provider.posts;
+    let purged = posts
+        .purge_deleted_posts(Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+    assert_eq!(trash(&app, &[]).await.total, 1);
+
+    let purged = posts
+        .purge_deleted_posts(Utc::now() + Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert_eq!(trash(&app, &[]).await.total, 0);
+    assert_eq!(app.restore_post(id).await.status(), 404);
+}
+
+#[tokio::test]
+async fn trashed_posts_leave_the_search_index_until_restored() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({ "title": "Searchable rust post" }))
+        .await;
+    app.index_outbox_events().await;
+
+    assert_eq!(indexed_total(&app).await, 1);
+
+    app.delete_post(id).await;
+    app.index_outbox_events().await;
+    assert_eq!(indexed_total(&app).await, 0);
+
+    app.restore_post(id).await;
+    app.index_outbox_events().await;
+    assert_eq!(indexed_total(&app).await, 1);
+}
+
+#[tokio::test]
+async fn trashed_posts_do_not_accept_comments_or_reactions() {
+    let app = common::spawn_app().await;
+    let id = app
+        .create_post(serde_json::json!({ "title": "Closed" }))
+        .await;
+    app.delete_post(id).await;
+
+    let response = app
+        .post_comment(
+            id,
+            &serde_json::json!({ "author_id": Uuid::new_v4(), "content": "Hello" }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(
+        app.add_reaction(id, "like", Uuid::new_v4()).await.status(),
+        404
+    );
+}
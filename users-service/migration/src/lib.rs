@@ -2,6 +2,18 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
 mod m20220102_000002_create_outbox;
+mod m20220103_000003_add_user_version;
+mod m20220104_000004_add_user_deleted_at;
+mod m20220105_000005_add_user_profiles;
+mod m20220106_000006_create_follows;
+mod m20220107_000007_create_user_erasures;
+mod m20220108_000008_create_user_exports;
+mod m20220109_000009_normalize_user_identities;
+mod m20220110_000010_add_user_search_indexes;
+mod m20220111_000011_add_user_status;
+mod m20220112_000012_create_user_preferences;
+mod m20220113_000013_add_outbox_dead_letters;
+mod m20220114_000014_add_user_export_deadlines;
 
 pub struct Migrator;
 
@@ -11,6 +23,18 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20220101_000001_create_table::Migration),
             Box::new(m20220102_000002_create_outbox::Migration),
+            Box::new(m20220103_000003_add_user_version::Migration),
+            Box::new(m20220104_000004_add_user_deleted_at::Migration),
+            Box::new(m20220105_000005_add_user_profiles::Migration),
+            Box::new(m20220106_000006_create_follows::Migration),
+            Box::new(m20220107_000007_create_user_erasures::Migration),
+            Box::new(m20220108_000008_create_user_exports::Migration),
+            Box::new(m20220109_000009_normalize_user_identities::Migration),
+            Box::new(m20220110_000010_add_user_search_indexes::Migration),
+            Box::new(m20220111_000011_add_user_status::Migration),
+            Box::new(m20220112_000012_create_user_preferences::Migration),
+            Box::new(m20220113_000013_add_outbox_dead_letters::Migration),
+            Box::new(m20220114_000014_add_user_export_deadlines::Migration),
         ]
     }
 }
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(ColumnDef::new(User::DisplayName).text().null())
+                    .add_column(ColumnDef::new(User::Bio).text().null())
+                    .add_column(ColumnDef::new(User::AvatarUrl).text().null())
+                    .add_column(ColumnDef::new(User::Website).text().null())
+                    .add_column(ColumnDef::new(User::Location).text().null())
+                    .add_column(
+                        ColumnDef::new(User::SocialLinks)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'{}'::jsonb")),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::DisplayName)
+                    .drop_column(User::Bio)
+                    .drop_column(User::AvatarUrl)
+                    .drop_column(User::Website)
+                    .drop_column(User::Location)
+                    .drop_column(User::SocialLinks)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+    DisplayName,
+    Bio,
+    AvatarUrl,
+    Website,
+    Location,
+    SocialLinks,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Follow::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Follow::FollowerId).uuid().not_null())
+                    .col(ColumnDef::new(Follow::FolloweeId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Follow::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Follow::FollowerId)
+                            .col(Follow::FolloweeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_follower_id")
+                            .from(Follow::Table, Follow::FollowerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_followee_id")
+                            .from(Follow::Table, Follow::FolloweeId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(Expr::col(Follow::FollowerId).ne(Expr::col(Follow::FolloweeId)))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Follower lists are read newest first; the primary key covers `following`
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_follows_followee_id_created_at")
+                    .table(Follow::Table)
+                    .col(Follow::FolloweeId)
+                    .col(Follow::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Follow::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Follow {
+    #[sea_orm(iden = "follows")]
+    Table,
+    FollowerId,
+    FolloweeId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // No foreign key to users: the record outlives the user row once the trash is purged
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserErasure::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserErasure::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserErasure::UserId).uuid().not_null())
+                    .col(ColumnDef::new(UserErasure::Status).text().not_null())
+                    .col(
+                        ColumnDef::new(UserErasure::RequestedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserErasure::DeadlineAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserErasure::CompletedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_erasures_user_id")
+                    .table(UserErasure::Table)
+                    .col(UserErasure::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserErasureStep::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(UserErasureStep::ErasureId).uuid().not_null())
+                    .col(ColumnDef::new(UserErasureStep::Step).text().not_null())
+                    .col(ColumnDef::new(UserErasureStep::CompletedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(UserErasureStep::Detail).json_binary())
+                    .primary_key(
+                        Index::create()
+                            .col(UserErasureStep::ErasureId)
+                            .col(UserErasureStep::Step),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_erasure_steps_erasure_id")
+                            .from(UserErasureStep::Table, UserErasureStep::ErasureId)
+                            .to(UserErasure::Table, UserErasure::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserErasureStep::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserErasure::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserErasure {
+    #[sea_orm(iden = "user_erasures")]
+    Table,
+    Id,
+    UserId,
+    Status,
+    RequestedAt,
+    DeadlineAt,
+    CompletedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserErasureStep {
+    #[sea_orm(iden = "user_erasure_steps")]
+    Table,
+    ErasureId,
+    Step,
+    CompletedAt,
+    Detail,
+}
@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserExport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserExport::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserExport::UserId).uuid().not_null())
+                    .col(ColumnDef::new(UserExport::Status).text().not_null())
+                    .col(
+                        ColumnDef::new(UserExport::RequestedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserExport::CompletedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(UserExport::ArchivePath).text())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_exports_user_id")
+                            .from(UserExport::Table, UserExport::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_exports_user_id")
+                    .table(UserExport::Table)
+                    .col(UserExport::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserExportPart::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(UserExportPart::ExportId).uuid().not_null())
+                    .col(ColumnDef::new(UserExportPart::Part).text().not_null())
+                    .col(
+                        ColumnDef::new(UserExportPart::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExportPart::ReceivedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(UserExportPart::ExportId)
+                            .col(UserExportPart::Part),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_export_parts_export_id")
+                            .from(UserExportPart::Table, UserExportPart::ExportId)
+                            .to(UserExport::Table, UserExport::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserExportPart::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserExport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserExport {
+    #[sea_orm(iden = "user_exports")]
+    Table,
+    Id,
+    UserId,
+    Status,
+    RequestedAt,
+    CompletedAt,
+    ArchivePath,
+}
+
+#[derive(DeriveIden)]
+enum UserExportPart {
+    #[sea_orm(iden = "user_export_parts")]
+    Table,
+    ExportId,
+    Part,
+    Data,
+    ReceivedAt,
+}
@@ -0,0 +1,181 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UsernameHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UsernameHistory::Username)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UsernameHistory::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UsernameHistory::ChangedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_username_history_user_id")
+                            .from(UsernameHistory::Table, UsernameHistory::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_username_history_user_id")
+                    .table(UsernameHistory::Table)
+                    .col(UsernameHistory::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+
+        // Emails cannot be made up, so addresses that only differed in case or
+        // width have to be merged by hand before the unique index can exist
+        let collisions = conn
+            .query_all_raw(Statement::from_string(
+                manager.get_database_backend(),
+                "SELECT lower(normalize(btrim(email), NFKC)) AS email, \
+                 string_agg(id::text, ', ' ORDER BY created_at, id) AS ids \
+                 FROM users GROUP BY 1 HAVING count(*) > 1 ORDER BY 1",
+            ))
+            .await?;
+        if !collisions.is_empty() {
+            let mut report = Vec::with_capacity(collisions.len());
+            for row in collisions {
+                let email: String = row.try_get("", "email")?;
+                let ids: String = row.try_get("", "ids")?;
+                report.push(format!("{email} (users {ids})"));
+            }
+            return Err(DbErr::Migration(format!(
+                "Users share an email once normalized; resolve these before migrating: {}",
+                report.join("; ")
+            )));
+        }
+
+        // Bring existing rows into the form the service now writes, so the
+        // indexes below catch names that only differed in case or width. The
+        // oldest account keeps a contested username; newer ones get their id
+        // appended and keep their old name as a former one.
+        let ranked = "SELECT id, username AS original, \
+             lower(normalize(btrim(username), NFKC)) AS username, \
+             row_number() OVER ( \
+                 PARTITION BY lower(normalize(btrim(username), NFKC)) \
+                 ORDER BY created_at, id \
+             ) AS rank \
+             FROM users";
+        conn.execute_unprepared(&format!(
+            "INSERT INTO username_history (username, user_id, changed_at) \
+             SELECT original, id, now() FROM ({ranked}) ranked WHERE rank > 1 \
+             ON CONFLICT (username) DO NOTHING"
+        ))
+        .await?;
+        conn.execute_unprepared(&format!(
+            "UPDATE users SET \
+             username = CASE WHEN ranked.rank = 1 THEN ranked.username \
+                 ELSE ranked.username || '-' || left(replace(users.id::text, '-', ''), 8) END, \
+             email = lower(normalize(btrim(email), NFKC)) \
+             FROM ({ranked}) ranked WHERE ranked.id = users.id"
+        ))
+        .await?;
+        conn.execute_unprepared(
+            "CREATE UNIQUE INDEX idx_users_username ON users (lower(username))",
+        )
+        .await?;
+        conn.execute_unprepared("CREATE UNIQUE INDEX idx_users_email ON users (lower(email))")
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailChange::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmailChange::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EmailChange::NewEmail).text().not_null())
+                    .col(ColumnDef::new(EmailChange::Token).text().not_null())
+                    .col(
+                        ColumnDef::new(EmailChange::RequestedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailChange::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_email_changes_user_id")
+                            .from(EmailChange::Table, EmailChange::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EmailChange::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UsernameHistory::Table).to_owned())
+            .await?;
+
+        for index in ["idx_users_email", "idx_users_username"] {
+            manager
+                .drop_index(Index::drop().name(index).table(User::Table).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UsernameHistory {
+    #[sea_orm(iden = "username_history")]
+    Table,
+    Username,
+    UserId,
+    ChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum EmailChange {
+    #[sea_orm(iden = "email_changes")]
+    Table,
+    UserId,
+    NewEmail,
+    Token,
+    RequestedAt,
+    ExpiresAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+    Id,
+}
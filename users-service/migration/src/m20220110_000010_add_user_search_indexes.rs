@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        conn.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .await?;
+
+        // Serve both prefix (`LIKE 'ab%'`) and similarity matches on live users
+        conn.execute_unprepared(
+            "CREATE INDEX idx_users_username_trgm ON users \
+             USING gin (username gin_trgm_ops) WHERE deleted_at IS NULL",
+        )
+        .await?;
+        conn.execute_unprepared(
+            "CREATE INDEX idx_users_display_name_trgm ON users \
+             USING gin (lower(display_name) gin_trgm_ops) WHERE deleted_at IS NULL",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for index in ["idx_users_display_name_trgm", "idx_users_username_trgm"] {
+            manager
+                .drop_index(Index::drop().name(index).table(User::Table).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+}
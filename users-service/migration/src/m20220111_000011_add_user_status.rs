@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(
+                        ColumnDef::new(User::Status)
+                            .text()
+                            .not_null()
+                            .default("active"),
+                    )
+                    .add_column(ColumnDef::new(User::StatusReason).text().null())
+                    .add_column(
+                        ColumnDef::new(User::StatusExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Only suspensions expire, and the monitor looks them up by expiry
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX idx_users_status_expires_at ON users (status_expires_at) \
+                 WHERE status_expires_at IS NOT NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_status_expires_at")
+                    .table(User::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::Status)
+                    .drop_column(User::StatusReason)
+                    .drop_column(User::StatusExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    #[sea_orm(iden = "users")]
+    Table,
+    Status,
+    StatusReason,
+    StatusExpiresAt,
+}
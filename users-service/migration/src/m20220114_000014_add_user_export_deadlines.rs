@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserExport::Table)
+                    .add_column(
+                        ColumnDef::new(UserExport::DeadlineAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Exports requested so far get the default hour to finish
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE user_exports SET deadline_at = requested_at + interval '1 hour'",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserExport::Table)
+                    .modify_column(
+                        ColumnDef::new(UserExport::DeadlineAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserExport::Table)
+                    .drop_column(UserExport::DeadlineAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserExport {
+    #[sea_orm(iden = "user_exports")]
+    Table,
+    DeadlineAt,
+}
@@ -1 +1,9 @@
+pub mod email_change;
+pub mod follow;
 pub mod user;
+pub mod user_erasure;
+pub mod user_erasure_step;
+pub mod user_export;
+pub mod user_export_part;
+pub mod user_preference;
+pub mod username_history;
@@ -1,22 +1,98 @@
-use sea_orm::entity::prelude::*;
+use std::collections::BTreeMap;
+
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
 use uuid::Uuid;
 
+use common::etag::Versioned;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 #[sea_orm::model]
-#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, Validate)]
 #[sea_orm(table_name = "users")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     #[sea_orm(column_type = "Text")]
+    #[validate(email(message = "Email must be a valid email address"))]
     pub email: String,
     #[sea_orm(column_type = "Text")]
+    #[validate(
+        length(
+            min = 3,
+            max = 64,
+            message = "Username must be between 3-64 characters"
+        ),
+        custom(function = "common::extract::not_blank")
+    )]
     pub username: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub website: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub location: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    pub social_links: SocialLinks,
+    /// Only changed through `change_status`; ignored by `update_user`.
+    #[serde(default)]
+    pub status: UserStatus,
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default)]
+    pub status_reason: Option<String>,
+    /// When a suspension lapses and the user becomes active again.
+    #[serde(default)]
+    pub status_expires_at: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    pub version: i32,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    /// Set while the user is in the trash; repository reads skip such users.
+    #[serde(default)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    #[sea_orm(string_value = "active")]
+    Active,
+    /// Closed by the user; they keep their data and can come back.
+    #[sea_orm(string_value = "deactivated")]
+    Deactivated,
+    /// Barred by a moderator, usually until `status_expires_at`.
+    #[sea_orm(string_value = "suspended")]
+    Suspended,
+    /// Barred for good.
+    #[sea_orm(string_value = "banned")]
+    Banned,
 }
 
+/// Links to a user's other profiles, keyed by a short label such as `github`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct SocialLinks(pub BTreeMap<String, String>);
+
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
+
 pub type User = Model;
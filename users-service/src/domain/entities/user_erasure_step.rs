@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+/// One service's part of an erasure; `completed_at` is set once it acknowledges.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_erasure_steps")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub erasure_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub step: String,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub detail: Option<Json>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub type UserErasureStep = Model;
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    /// Waiting for other services to send their part.
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    /// The archive is written and can be downloaded.
+    #[sea_orm(string_value = "ready")]
+    Ready,
+    /// Some parts did not arrive before the deadline; the user can ask again.
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_exports")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: ExportStatus,
+    pub requested_at: DateTimeWithTimeZone,
+    pub deadline_at: DateTimeWithTimeZone,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub archive_path: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub type UserExport = Model;
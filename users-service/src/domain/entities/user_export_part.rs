@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use sea_orm::entity::prelude::*;
+
+/// Data one service sent for an export.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_export_parts")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub export_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub part: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub data: Json,
+    pub received_at: DateTimeWithTimeZone,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub type UserExportPart = Model;
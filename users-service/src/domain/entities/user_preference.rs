@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use common::etag::Versioned;
+use sea_orm::entity::prelude::*;
+
+use crate::domain::preferences::Preferences;
+
+/// A user's stored preferences. Users who never saved any have no row and
+/// get the defaults.
+#[sea_orm::model]
+#[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub document: Preferences,
+    pub version: i32,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Versioned for Model {
+    fn version(&self) -> i32 {
+        self.version
+    }
+}
+
+pub type UserPreferences = Model;
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    user_erasure::{ErasureStatus, UserErasure},
+    user_erasure_step::UserErasureStep,
+};
+
+/// Services that must acknowledge an erasure before it is complete. Each one
+/// answers `user_deleted` events carrying an `erasure_id` with a
+/// `user_erasure_step_completed` event naming its step.
+pub const ERASURE_STEPS: [&str; 2] = ["posts", "notifications"];
+
+/// Progress of erasing a user's data across services.
+#[derive(Debug, Clone)]
+pub struct Erasure {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: ErasureStatus,
+    pub requested_at: DateTime<Utc>,
+    pub deadline_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps: Vec<ErasureStep>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ErasureStep {
+    pub step: String,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// What the service reported doing, e.g. how many posts it deleted.
+    pub detail: Option<serde_json::Value>,
+}
+
+impl Erasure {
+    pub fn new(erasure: UserErasure, steps: Vec<UserErasureStep>) -> Self {
+        Self {
+            id: erasure.id,
+            user_id: erasure.user_id,
+            status: erasure.status,
+            requested_at: erasure.requested_at.into(),
+            deadline_at: erasure.deadline_at.into(),
+            completed_at: erasure.completed_at.map(Into::into),
+            steps: steps
+                .into_iter()
+                .map(|step| ErasureStep {
+                    step: step.step,
+                    completed_at: step.completed_at.map(Into::into),
+                    detail: step.detail,
+                })
+                .collect(),
+        }
+    }
+}
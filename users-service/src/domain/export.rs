@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        user::User,
+        user_export::{ExportStatus, UserExport},
+    },
+    follow::FollowEdge,
+    preferences::Preferences,
+};
+
+/// Services that send a part for every export. Each one answers
+/// `user_export_requested` by storing its part and sending a
+/// `user_export_part_ready` event with the `path` to fetch it from.
+pub const EXPORT_PARTS: [&str; 2] = ["posts", "notifications"];
+
+/// Where the archive of an export can be downloaded from.
+pub fn download_path(user_id: Uuid, export_id: Uuid) -> String {
+    format!("/users/{}/export/{}/download", user_id, export_id)
+}
+
+/// A request for a copy of everything stored about a user.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: ExportStatus,
+    pub requested_at: DateTime<Utc>,
+    pub deadline_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub archive_path: Option<String>,
+    /// Parts received so far.
+    pub parts: Vec<String>,
+}
+
+impl Export {
+    pub fn new(export: UserExport, parts: Vec<String>) -> Self {
+        Self {
+            id: export.id,
+            user_id: export.user_id,
+            status: export.status,
+            requested_at: export.requested_at.into(),
+            deadline_at: export.deadline_at.into(),
+            completed_at: export.completed_at.map(Into::into),
+            archive_path: export.archive_path,
+            parts,
+        }
+    }
+
+    pub fn has_all_parts(&self) -> bool {
+        self.missing_parts().is_empty()
+    }
+
+    pub fn missing_parts(&self) -> Vec<&'static str> {
+        EXPORT_PARTS
+            .into_iter()
+            .filter(|part| !self.parts.iter().any(|received| received == part))
+            .collect()
+    }
+}
+
+/// The document written to disk and handed to the user.
+#[derive(Debug, Serialize)]
+pub struct ExportArchive {
+    pub export_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub profile: User,
+    pub preferences: Preferences,
+    pub following: Vec<ExportedFollow>,
+    pub followers: Vec<ExportedFollow>,
+    /// What the other services sent, keyed by part name.
+    #[serde(flatten)]
+    pub parts: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedFollow {
+    pub user_id: Uuid,
+    pub username: String,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<FollowEdge> for ExportedFollow {
+    fn from(edge: FollowEdge) -> Self {
+        Self {
+            user_id: edge.user.id,
+            username: edge.user.username,
+            followed_at: edge.followed_at,
+        }
+    }
+}
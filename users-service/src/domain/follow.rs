@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::user::User;
+
+/// A user on one side of a follow, with when the follow started.
+#[derive(Debug, Clone)]
+pub struct FollowEdge {
+    pub user: User,
+    pub followed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FollowCounts {
+    pub followers: u64,
+    pub following: u64,
+}
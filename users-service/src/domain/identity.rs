@@ -0,0 +1,56 @@
+use chrono::Duration;
+use common::error::{AppError, Result};
+use unicode_normalization::UnicodeNormalization;
+
+/// How long the token sent to a new email address stays valid.
+pub const EMAIL_CHANGE_TTL: Duration = Duration::hours(24);
+
+/// Names nobody may register or rename to, because they would be mistaken for
+/// the service itself or clash with routes.
+pub const RESERVED_USERNAMES: [&str; 24] = [
+    "admin",
+    "administrator",
+    "anonymous",
+    "api",
+    "batch",
+    "by-username",
+    "help",
+    "login",
+    "logout",
+    "me",
+    "moderator",
+    "null",
+    "official",
+    "root",
+    "search",
+    "security",
+    "settings",
+    "signup",
+    "staff",
+    "support",
+    "system",
+    "trash",
+    "undefined",
+    "users",
+];
+
+/// Prefix of the usernames given to erased users.
+const ERASED_PREFIX: &str = "erased-";
+
+/// Canonical form of a username or email: trimmed, NFKC-normalized and
+/// lowercased, so that visually identical names compare equal.
+pub fn normalize(raw: &str) -> String {
+    raw.trim().nfkc().collect::<String>().to_lowercase()
+}
+
+/// Rejects reserved names. Only checked when a name is taken, so users who
+/// already had one before it was reserved keep it.
+pub fn ensure_username_allowed(username: &str) -> Result<()> {
+    if RESERVED_USERNAMES.contains(&username) || username.starts_with(ERASED_PREFIX) {
+        return Err(AppError::ValidationError(format!(
+            "Username '{}' is reserved",
+            username
+        )));
+    }
+    Ok(())
+}
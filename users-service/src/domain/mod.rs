@@ -1,3 +1,11 @@
 pub mod entities;
+pub mod erasure;
+pub mod export;
+pub mod follow;
+pub mod identity;
+pub mod preferences;
+pub mod profile;
 pub mod repository;
+pub mod search;
+pub mod status;
 pub mod types;
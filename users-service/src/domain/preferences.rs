@@ -0,0 +1,134 @@
+use std::{borrow::Cow, collections::BTreeMap, sync::LazyLock};
+
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+/// The JSON schema `PUT /users/{id}/preferences` bodies are checked against.
+pub const PREFERENCES_SCHEMA: &str = include_str!("../../schemas/preferences.json");
+
+static VALIDATOR: LazyLock<jsonschema::Validator> = LazyLock::new(|| {
+    let schema = serde_json::from_str(PREFERENCES_SCHEMA).expect("Preferences schema is not JSON");
+    jsonschema::validator_for(&schema).expect("Preferences schema is not a valid JSON schema")
+});
+
+/// Checks `document` against [`PREFERENCES_SCHEMA`], reporting each failure
+/// under its dotted path, or `document` for the top level.
+pub fn check_document(document: &serde_json::Value) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    for error in VALIDATOR.iter_errors(document) {
+        let path = error.instance_path.to_string();
+        let field = match path.trim_start_matches('/') {
+            "" => "document".to_string(),
+            path => path.replace('/', "."),
+        };
+        let error = ValidationError::new("schema").with_message(Cow::Owned(error.to_string()));
+        match errors
+            .errors_mut()
+            .entry(Cow::Owned(field))
+            .or_insert_with(|| ValidationErrorsKind::Field(Vec::new()))
+        {
+            ValidationErrorsKind::Field(field_errors) => field_errors.push(error),
+            _ => unreachable!("Only field errors are recorded"),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Notifications a user can opt in or out of. Account and security notices
+/// are always delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    PostPublished,
+    CommentCreated,
+    PostLiked,
+    UserFollowed,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 4] = [
+        NotificationKind::PostPublished,
+        NotificationKind::CommentCreated,
+        NotificationKind::PostLiked,
+        NotificationKind::UserFollowed,
+    ];
+}
+
+/// Where notifications of one kind are delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Channels {
+    #[serde(default = "enabled")]
+    pub in_app: bool,
+    #[serde(default)]
+    pub email: bool,
+    #[serde(default)]
+    pub push: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self {
+            in_app: true,
+            email: false,
+            push: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// A user's display and notification settings. Every notification kind is
+/// always present, so consumers never have to guess a default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Preferences {
+    pub locale: String,
+    pub timezone: String,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub notifications: BTreeMap<NotificationKind, Channels>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            locale: "en".to_string(),
+            timezone: "UTC".to_string(),
+            theme: Theme::default(),
+            notifications: BTreeMap::new(),
+        }
+        .complete()
+    }
+}
+
+impl Preferences {
+    /// Reads a document that passed [`check_document`].
+    pub fn from_document(document: serde_json::Value) -> serde_json::Result<Self> {
+        serde_json::from_value::<Self>(document).map(Self::complete)
+    }
+
+    /// Fills in the kinds the document left out with their default channels.
+    fn complete(mut self) -> Self {
+        for kind in NotificationKind::ALL {
+            self.notifications.entry(kind).or_default();
+        }
+        self
+    }
+}
@@ -0,0 +1,19 @@
+use crate::domain::entities::user::SocialLinks;
+
+/// Partial change to a user's profile. `None` leaves a field untouched and
+/// `Some(None)` clears it; social links are always replaced as a whole.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileUpdate {
+    pub display_name: Option<Option<String>>,
+    pub bio: Option<Option<String>>,
+    pub avatar_url: Option<Option<String>>,
+    pub website: Option<Option<String>>,
+    pub location: Option<Option<String>>,
+    pub social_links: Option<SocialLinks>,
+}
+
+impl ProfileUpdate {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
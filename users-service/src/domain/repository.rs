@@ -1,18 +1,160 @@
 use async_trait::async_trait;
-use common::{error::Result, pagination::Pagination};
-use std::{fmt::Debug, sync::Arc};
+use chrono::{DateTime, Utc};
+use common::{error::Result, etag::IfMatch, pagination::Pagination};
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
 use uuid::Uuid;
 
-use crate::domain::entities::user::User;
+use crate::domain::{
+    entities::{
+        email_change::EmailChange,
+        user::{User, UserStatus},
+        user_preference::UserPreferences,
+    },
+    erasure::Erasure,
+    export::Export,
+    follow::{FollowCounts, FollowEdge},
+    preferences::Preferences,
+    profile::ProfileUpdate,
+    search::UserSearchHit,
+    status::StatusChange,
+};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync + Debug {
     async fn create_user(&self, user: User) -> Result<User>;
     async fn get_user_by_id(&self, id: Uuid) -> Result<Option<User>>;
     async fn get_user_by_name(&self, username: String) -> Result<Option<User>>;
-    async fn update_user(&self, user: User) -> Result<()>;
+    /// The live user who used to go by `username`, so old links can follow them.
+    async fn get_user_by_former_name(&self, username: String) -> Result<Option<User>>;
+    /// Live users among `ids`, in no particular order; unknown ids are left out.
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>>;
+    /// Renames are recorded in the username history; the email cannot change
+    /// here and has to go through `request_email_change`. Returns the updated
+    /// user along with the username they had before.
+    async fn update_user(&self, user: User, if_match: &IfMatch) -> Result<(User, String)>;
+    /// Starts moving a user to `new_email`, replacing any change still pending.
+    /// The address only changes once the token sent to it is confirmed.
+    async fn request_email_change(
+        &self,
+        id: Uuid,
+        new_email: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<EmailChange>;
+    /// Applies the user's pending email change if `token` matches and has not expired.
+    async fn confirm_email_change(&self, id: Uuid, token: String) -> Result<User>;
+    /// Applies the fields set in `update`, leaving the rest of the profile as is.
+    async fn update_profile(&self, id: Uuid, update: ProfileUpdate) -> Result<User>;
+    /// The user's preferences, or the defaults at version 0 if they never saved any.
+    async fn get_preferences(&self, id: Uuid) -> Result<UserPreferences>;
+    /// Replaces the user's preferences and announces them with a
+    /// `user_preferences_updated` event. Saving the same document again is a no-op.
+    async fn update_preferences(
+        &self,
+        id: Uuid,
+        preferences: Preferences,
+    ) -> Result<UserPreferences>;
+    /// Moves a user to the trash, from where they can be restored until purged.
     async fn delete_user(&self, id: Uuid) -> Result<()>;
     async fn list_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)>;
+    /// Live users whose username or display name starts with or resembles the
+    /// normalized `query`. Exact matches come first, then prefix matches, each
+    /// ordered by follower count.
+    async fn search_users(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<UserSearchHit>, u64)>;
+    /// Trashed users, most recently deleted first.
+    async fn list_deleted_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)>;
+    async fn restore_user(&self, id: Uuid) -> Result<User>;
+    /// Live users with the given status, most recently changed first.
+    async fn list_users_by_status(
+        &self,
+        status: UserStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<User>, u64)>;
+    /// Sets a live user's status, replacing any earlier reason and expiry.
+    async fn change_status(&self, id: Uuid, change: StatusChange) -> Result<User>;
+    /// Lifts suspensions that expired before `now` and returns the reactivated users.
+    async fn reactivate_expired(&self, now: DateTime<Utc>) -> Result<Vec<User>>;
+    /// Erases users trashed before `before` whose erasure has not started yet,
+    /// giving the other services until `deadline`, and returns their ids.
+    async fn purge_deleted_users(
+        &self,
+        before: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>>;
+    /// Scrubs a user's personal data, moves them to the trash for good and
+    /// asks the other services to erase theirs, which they must acknowledge
+    /// before `deadline`. Asking again restarts an erasure that timed out.
+    async fn erase_user(&self, id: Uuid, deadline: DateTime<Utc>) -> Result<Erasure>;
 }
 
 pub type DynUserRepository = Arc<dyn UserRepository>;
+
+#[async_trait]
+pub trait FollowRepository: Send + Sync + Debug {
+    /// Returns `false` if `follower_id` already followed `followee_id`.
+    async fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool>;
+    /// Returns `false` if there was no such follow.
+    async fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool>;
+    /// Users following `user_id`, most recent first.
+    async fn list_followers(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)>;
+    /// Users `user_id` follows, most recent first.
+    async fn list_following(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)>;
+    async fn follow_counts(&self, user_id: Uuid) -> Result<FollowCounts>;
+}
+
+pub type DynFollowRepository = Arc<dyn FollowRepository>;
+
+#[async_trait]
+pub trait ErasureRepository: Send + Sync + Debug {
+    async fn get_erasure(&self, user_id: Uuid) -> Result<Option<Erasure>>;
+    /// Records a service's acknowledgement and completes the erasure once every
+    /// step is done. Returns `None` for unknown erasures.
+    async fn complete_step(
+        &self,
+        erasure_id: Uuid,
+        step: &str,
+        detail: serde_json::Value,
+    ) -> Result<Option<Erasure>>;
+    /// Marks pending erasures whose deadline passed before `now` as timed out
+    /// and returns them.
+    async fn expire_erasures(&self, now: DateTime<Utc>) -> Result<Vec<Erasure>>;
+}
+
+pub type DynErasureRepository = Arc<dyn ErasureRepository>;
+
+#[async_trait]
+pub trait ExportRepository: Send + Sync + Debug {
+    /// Starts an export of a live user's data and asks the other services for
+    /// their parts, which must arrive before `deadline`. While one is pending,
+    /// asking again returns it.
+    async fn request_export(&self, user_id: Uuid, deadline: DateTime<Utc>) -> Result<Export>;
+    async fn get_export(&self, user_id: Uuid, export_id: Uuid) -> Result<Option<Export>>;
+    /// Stores a service's part; a part that was already received is kept as
+    /// is. Returns `None` for unknown exports.
+    async fn store_part(
+        &self,
+        export_id: Uuid,
+        part: &str,
+        data: serde_json::Value,
+    ) -> Result<Option<Export>>;
+    async fn export_parts(&self, export_id: Uuid) -> Result<BTreeMap<String, serde_json::Value>>;
+    /// Marks the export ready for download at `archive_path` and lets the
+    /// user know. Exports that are no longer pending are returned as they are.
+    async fn complete_export(&self, export_id: Uuid, archive_path: &str) -> Result<Export>;
+    /// Fails pending exports whose deadline passed before `now`, lets their
+    /// users know and returns them.
+    async fn expire_exports(&self, now: DateTime<Utc>) -> Result<Vec<Export>>;
+}
+
+pub type DynExportRepository = Arc<dyn ExportRepository>;
@@ -0,0 +1,11 @@
+use crate::domain::entities::user::User;
+
+/// Longest query accepted by the user directory search, in characters.
+pub const MAX_QUERY_LENGTH: usize = 64;
+
+/// A live user matching a directory search.
+#[derive(Debug, Clone)]
+pub struct UserSearchHit {
+    pub user: User,
+    pub followers: u64,
+}
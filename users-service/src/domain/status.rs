@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use common::error::{AppError, Result};
+
+use crate::domain::entities::user::{User, UserStatus};
+
+/// A moderator's change to a user's status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    pub status: UserStatus,
+    pub reason: Option<String>,
+    /// Only suspensions expire; `None` suspends until lifted by hand.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StatusChange {
+    /// Rejects changes that make no sense at `now`: an expiry on anything but
+    /// a suspension or one already in the past, and a suspension or ban
+    /// without a reason.
+    pub fn check(&self, now: DateTime<Utc>) -> Result<()> {
+        match (self.status, self.expires_at) {
+            (UserStatus::Suspended, Some(expires_at)) if expires_at <= now => {
+                return Err(AppError::ValidationError(
+                    "Suspension must expire in the future".into(),
+                ));
+            }
+            (UserStatus::Suspended, _) | (_, None) => {}
+            (_, Some(_)) => {
+                return Err(AppError::ValidationError(
+                    "Only suspensions can expire".into(),
+                ));
+            }
+        }
+
+        let moderated = matches!(self.status, UserStatus::Suspended | UserStatus::Banned);
+        if moderated && self.reason.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            return Err(AppError::ValidationError(
+                "A reason is required to suspend or ban a user".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Fails for users who may not write anything until they are active again.
+pub fn ensure_active(user: &User) -> Result<()> {
+    match user.status {
+        UserStatus::Active => Ok(()),
+        UserStatus::Deactivated => Err(AppError::ForbiddenError("Account is deactivated".into())),
+        UserStatus::Suspended => Err(AppError::ForbiddenError("Account is suspended".into())),
+        UserStatus::Banned => Err(AppError::ForbiddenError("Account is banned".into())),
+    }
+}
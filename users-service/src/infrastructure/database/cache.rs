@@ -1,10 +1,28 @@
 use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use common::{cache::CacheExt, error::Result, pagination::Pagination};
+use chrono::{DateTime, Utc};
+use common::{
+    cache::CacheExt,
+    error::{AppError, Result},
+    etag::IfMatch,
+    pagination::Pagination,
+};
 use uuid::Uuid;
 
-use crate::domain::{entities::user::User, repository::UserRepository};
+use crate::domain::{
+    entities::{
+        email_change::EmailChange,
+        user::{User, UserStatus},
+        user_preference::UserPreferences,
+    },
+    erasure::Erasure,
+    preferences::Preferences,
+    profile::ProfileUpdate,
+    repository::UserRepository,
+    search::UserSearchHit,
+    status::StatusChange,
+};
 
 #[derive(Debug)]
 pub struct CachedUserRepository<C: CacheExt + Send + Sync + Debug> {
@@ -25,6 +43,28 @@ impl<C: CacheExt + Send + Sync + Debug> CachedUserRepository<C> {
     fn username_key(username: &str) -> String {
         format!("user:name:{}", username)
     }
+
+    fn preferences_key(id: &Uuid) -> String {
+        format!("user:preferences:{}", id)
+    }
+
+    /// Holds the generation of the cached `list_users` pages.
+    const LIST_GENERATION_KEY: &str = "user:list:generation";
+
+    fn list_key(generation: &str, pagination: &Pagination) -> String {
+        format!(
+            "user:list:{}:{}:{}",
+            generation, pagination.page, pagination.page_size
+        )
+    }
+
+    /// Orphans every cached list page. Called after any write that can add,
+    /// remove, reorder or change a live user.
+    async fn invalidate_lists(&self) {
+        self.cache
+            .bump_generation(Self::LIST_GENERATION_KEY, self.ttl)
+            .await;
+    }
 }
 
 #[async_trait]
@@ -35,8 +75,9 @@ impl<C: CacheExt + Send + Sync + Debug + 'static> UserRepository for CachedUserR
 
         let user = self.inner.create_user(user).await?;
 
-        self.cache.set(&id_key, &user, self.ttl).await;
-        self.cache.set(&name_key, &user, self.ttl).await;
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
 
         Ok(user)
     }
@@ -51,12 +92,39 @@ impl<C: CacheExt + Send + Sync + Debug + 'static> UserRepository for CachedUserR
         let user = self.inner.get_user_by_id(id).await?;
 
         if let Some(ref u) = user {
-            self.cache.set(&key, u, self.ttl).await;
+            self.cache.set_versioned(&key, u, self.ttl).await;
         }
 
         Ok(user)
     }
 
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        let keys: Vec<String> = ids.iter().map(Self::cache_key).collect();
+        let cached = self.cache.get_many::<User>(&keys).await;
+
+        let mut users = Vec::with_capacity(ids.len());
+        let mut misses = Vec::new();
+        for (id, user) in ids.iter().zip(cached) {
+            match user {
+                Some(user) => users.push(user),
+                None => misses.push(*id),
+            }
+        }
+        if misses.is_empty() {
+            return Ok(users);
+        }
+
+        let fetched = self.inner.get_users_by_ids(&misses).await?;
+        for user in &fetched {
+            self.cache
+                .set_versioned(Self::cache_key(&user.id), user, self.ttl)
+                .await;
+        }
+        users.extend(fetched);
+
+        Ok(users)
+    }
+
     async fn get_user_by_name(&self, username: String) -> Result<Option<User>> {
         let key = Self::username_key(&username);
 
@@ -68,23 +136,66 @@ impl<C: CacheExt + Send + Sync + Debug + 'static> UserRepository for CachedUserR
 
         if let Some(ref u) = user {
             let id_key = Self::cache_key(&u.id);
-            self.cache.set(&key, u, self.ttl).await;
-            self.cache.set(&id_key, u, self.ttl).await;
+            self.cache.set_versioned(&key, u, self.ttl).await;
+            self.cache.set_versioned(&id_key, u, self.ttl).await;
         }
 
         Ok(user)
     }
 
-    async fn update_user(&self, user: User) -> Result<()> {
+    async fn get_user_by_former_name(&self, username: String) -> Result<Option<User>> {
+        self.inner.get_user_by_former_name(username).await
+    }
+
+    async fn update_user(&self, user: User, if_match: &IfMatch) -> Result<(User, String)> {
         let id_key = Self::cache_key(&user.id);
+
+        let (user, previous_username) = match self.inner.update_user(user, if_match).await {
+            Ok(updated) => updated,
+            Err(e @ AppError::PreconditionFailedError(_)) => {
+                // The client saw a stale ETag, possibly served from here
+                self.cache.delete(&id_key).await;
+                return Err(e);
+            }
+            Err(e) => return Err(e),
+        };
+
+        // The old name may be cached even when the user by id is not
+        if previous_username != user.username {
+            self.cache
+                .delete(Self::username_key(&previous_username))
+                .await;
+        }
+
         let name_key = Self::username_key(&user.username);
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
 
-        self.inner.update_user(user.clone()).await?;
+        Ok((user, previous_username))
+    }
 
-        self.cache.set(&id_key, &user, self.ttl).await;
-        self.cache.set(&name_key, &user, self.ttl).await;
+    async fn request_email_change(
+        &self,
+        id: Uuid,
+        new_email: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<EmailChange> {
+        self.inner
+            .request_email_change(id, new_email, expires_at)
+            .await
+    }
 
-        Ok(())
+    async fn confirm_email_change(&self, id: Uuid, token: String) -> Result<User> {
+        let user = self.inner.confirm_email_change(id, token).await?;
+
+        let id_key = Self::cache_key(&user.id);
+        let name_key = Self::username_key(&user.username);
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
+
+        Ok(user)
     }
 
     async fn delete_user(&self, id: Uuid) -> Result<()> {
@@ -98,11 +209,157 @@ impl<C: CacheExt + Send + Sync + Debug + 'static> UserRepository for CachedUserR
         } else {
             self.inner.delete_user(id).await?;
         }
+        self.cache.delete(Self::preferences_key(&id)).await;
+        self.invalidate_lists().await;
 
         Ok(())
     }
 
     async fn list_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)> {
-        self.inner.list_users(pagination).await
+        // Read before the query, so a write landing in between orphans the page
+        let generation = self
+            .cache
+            .generation(Self::LIST_GENERATION_KEY, self.ttl)
+            .await;
+        let key = Self::list_key(&generation, pagination);
+
+        if let Some(page) = self.cache.get::<_, (Vec<User>, u64)>(&key).await {
+            return Ok(page);
+        }
+
+        let page = self.inner.list_users(pagination).await?;
+        self.cache.set(&key, &page, self.ttl).await;
+
+        Ok(page)
+    }
+
+    async fn search_users(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<UserSearchHit>, u64)> {
+        self.inner.search_users(query, pagination).await
+    }
+
+    async fn update_profile(&self, id: Uuid, update: ProfileUpdate) -> Result<User> {
+        let user = self.inner.update_profile(id, update).await?;
+
+        let id_key = Self::cache_key(&user.id);
+        let name_key = Self::username_key(&user.username);
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
+
+        Ok(user)
+    }
+
+    async fn get_preferences(&self, id: Uuid) -> Result<UserPreferences> {
+        let key = Self::preferences_key(&id);
+
+        if let Some(preferences) = self.cache.get::<_, UserPreferences>(&key).await {
+            return Ok(preferences);
+        }
+
+        let preferences = self.inner.get_preferences(id).await?;
+        self.cache.set_versioned(&key, &preferences, self.ttl).await;
+
+        Ok(preferences)
+    }
+
+    async fn update_preferences(
+        &self,
+        id: Uuid,
+        preferences: Preferences,
+    ) -> Result<UserPreferences> {
+        let preferences = self.inner.update_preferences(id, preferences).await?;
+
+        self.cache
+            .set_versioned(Self::preferences_key(&id), &preferences, self.ttl)
+            .await;
+
+        Ok(preferences)
+    }
+
+    async fn list_deleted_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)> {
+        self.inner.list_deleted_users(pagination).await
+    }
+
+    async fn restore_user(&self, id: Uuid) -> Result<User> {
+        let user = self.inner.restore_user(id).await?;
+
+        let id_key = Self::cache_key(&user.id);
+        let name_key = Self::username_key(&user.username);
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
+
+        Ok(user)
+    }
+
+    async fn list_users_by_status(
+        &self,
+        status: UserStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<User>, u64)> {
+        self.inner.list_users_by_status(status, pagination).await
+    }
+
+    async fn change_status(&self, id: Uuid, change: StatusChange) -> Result<User> {
+        let user = self.inner.change_status(id, change).await?;
+
+        let id_key = Self::cache_key(&user.id);
+        let name_key = Self::username_key(&user.username);
+        self.cache.set_versioned(&id_key, &user, self.ttl).await;
+        self.cache.set_versioned(&name_key, &user, self.ttl).await;
+        self.invalidate_lists().await;
+
+        Ok(user)
+    }
+
+    async fn reactivate_expired(&self, now: DateTime<Utc>) -> Result<Vec<User>> {
+        let users = self.inner.reactivate_expired(now).await?;
+        for user in &users {
+            self.cache
+                .set_versioned(Self::cache_key(&user.id), user, self.ttl)
+                .await;
+            self.cache
+                .set_versioned(Self::username_key(&user.username), user, self.ttl)
+                .await;
+        }
+        if !users.is_empty() {
+            self.invalidate_lists().await;
+        }
+
+        Ok(users)
+    }
+
+    async fn purge_deleted_users(
+        &self,
+        before: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>> {
+        // Trashed users are evicted on deletion, but erasing them renames them
+        // in the trash listing
+        let purged = self.inner.purge_deleted_users(before, deadline).await?;
+        if !purged.is_empty() {
+            self.invalidate_lists().await;
+        }
+
+        Ok(purged)
+    }
+
+    async fn erase_user(&self, id: Uuid, deadline: DateTime<Utc>) -> Result<Erasure> {
+        // Trashed users are already evicted, so only a live user can be cached
+        let user = self.inner.get_user_by_id(id).await?;
+        let erasure = self.inner.erase_user(id, deadline).await?;
+
+        self.cache.delete(Self::cache_key(&id)).await;
+        self.cache.delete(Self::preferences_key(&id)).await;
+        if let Some(user) = user {
+            self.cache.delete(Self::username_key(&user.username)).await;
+        }
+        self.invalidate_lists().await;
+
+        Ok(erasure)
     }
 }
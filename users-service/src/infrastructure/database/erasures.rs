@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::error::Result;
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        user_erasure::{self, ErasureStatus, UserErasure},
+        user_erasure_step,
+    },
+    erasure::Erasure,
+    repository::ErasureRepository,
+};
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmErasureRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmErasureRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+/// Loads the steps of `erasure`.
+pub(crate) async fn with_steps<C: ConnectionTrait>(
+    conn: &C,
+    erasure: UserErasure,
+) -> Result<Erasure> {
+    let steps = user_erasure_step::Entity::find()
+        .filter(user_erasure_step::Column::ErasureId.eq(erasure.id))
+        .order_by_asc(user_erasure_step::Column::Step)
+        .all(conn)
+        .await?;
+
+    Ok(Erasure::new(erasure, steps))
+}
+
+#[async_trait]
+impl ErasureRepository for SeaOrmErasureRepository {
+    async fn get_erasure(&self, user_id: Uuid) -> Result<Option<Erasure>> {
+        let erasure = user_erasure::Entity::find()
+            .filter(user_erasure::Column::UserId.eq(user_id))
+            .one(&self.conn)
+            .await?;
+
+        match erasure {
+            Some(erasure) => Ok(Some(with_steps(&self.conn, erasure).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn complete_step(
+        &self,
+        erasure_id: Uuid,
+        step: &str,
+        detail: serde_json::Value,
+    ) -> Result<Option<Erasure>> {
+        let tx = self.conn.begin().await?;
+
+        let Some(erasure) = user_erasure::Entity::find_by_id(erasure_id)
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let now = Utc::now();
+
+        // Redelivered acknowledgements keep the time of the first one
+        user_erasure_step::Entity::update_many()
+            .col_expr(
+                user_erasure_step::Column::CompletedAt,
+                sea_orm::sea_query::Expr::value(now),
+            )
+            .col_expr(
+                user_erasure_step::Column::Detail,
+                sea_orm::sea_query::Expr::value(detail),
+            )
+            .filter(user_erasure_step::Column::ErasureId.eq(erasure_id))
+            .filter(user_erasure_step::Column::Step.eq(step))
+            .filter(user_erasure_step::Column::CompletedAt.is_null())
+            .exec(&tx)
+            .await?;
+
+        let outstanding = user_erasure_step::Entity::find()
+            .filter(user_erasure_step::Column::ErasureId.eq(erasure_id))
+            .filter(user_erasure_step::Column::CompletedAt.is_null())
+            .one(&tx)
+            .await?;
+
+        // A late acknowledgement still completes an erasure that timed out
+        let erasure = if outstanding.is_none() && erasure.status != ErasureStatus::Completed {
+            user_erasure::ActiveModel {
+                id: Unchanged(erasure.id),
+                status: Set(ErasureStatus::Completed),
+                completed_at: Set(Some(now.into())),
+                ..Default::default()
+            }
+            .update(&tx)
+            .await?
+        } else {
+            erasure
+        };
+
+        let erasure = with_steps(&tx, erasure).await?;
+        tx.commit().await?;
+        Ok(Some(erasure))
+    }
+
+    async fn expire_erasures(&self, now: DateTime<Utc>) -> Result<Vec<Erasure>> {
+        let tx = self.conn.begin().await?;
+
+        let expired = user_erasure::Entity::find()
+            .filter(user_erasure::Column::Status.eq(ErasureStatus::Pending))
+            .filter(user_erasure::Column::DeadlineAt.lt(now))
+            .lock_exclusive()
+            .all(&tx)
+            .await?;
+
+        let mut erasures = Vec::with_capacity(expired.len());
+        for erasure in expired {
+            let erasure = user_erasure::ActiveModel {
+                id: Unchanged(erasure.id),
+                status: Set(ErasureStatus::TimedOut),
+                ..Default::default()
+            }
+            .update(&tx)
+            .await?;
+            erasures.push(with_steps(&tx, erasure).await?);
+        }
+
+        tx.commit().await?;
+        Ok(erasures)
+    }
+}
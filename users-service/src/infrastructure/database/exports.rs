@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{
+    error::{AppError, Result},
+    outbox,
+};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        user,
+        user_export::{self, ExportStatus, UserExport},
+        user_export_part,
+    },
+    export::{Export, download_path},
+    repository::ExportRepository,
+};
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmExportRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmExportRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+}
+
+/// Loads the names of the parts received for `export`.
+async fn with_parts<C: ConnectionTrait>(conn: &C, export: UserExport) -> Result<Export> {
+    let parts = user_export_part::Entity::find()
+        .select_only()
+        .column(user_export_part::Column::Part)
+        .filter(user_export_part::Column::ExportId.eq(export.id))
+        .order_by_asc(user_export_part::Column::Part)
+        .into_tuple::<String>()
+        .all(conn)
+        .await?;
+
+    Ok(Export::new(export, parts))
+}
+
+#[async_trait]
+impl ExportRepository for SeaOrmExportRepository {
+    async fn request_export(&self, user_id: Uuid, deadline: DateTime<Utc>) -> Result<Export> {
+        let tx = self.conn.begin().await?;
+
+        // Locking the user serializes concurrent requests for the same user
+        user::Entity::find_by_id(user_id)
+            .filter(user::Column::DeletedAt.is_null())
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+
+        let pending = user_export::Entity::find()
+            .filter(user_export::Column::UserId.eq(user_id))
+            .filter(user_export::Column::Status.eq(ExportStatus::Pending))
+            .one(&tx)
+            .await?;
+        if let Some(export) = pending {
+            let export = with_parts(&tx, export).await?;
+            tx.commit().await?;
+            return Ok(export);
+        }
+
+        let export = user_export::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            status: Set(ExportStatus::Pending),
+            requested_at: Set(Utc::now().into()),
+            deadline_at: Set(deadline.into()),
+            completed_at: Set(None),
+            archive_path: Set(None),
+        }
+        .insert(&tx)
+        .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user_id,
+            "user_export_requested",
+            serde_json::json!({ "export_id": export.id, "user_id": user_id }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Export::new(export, Vec::new()))
+    }
+
+    async fn get_export(&self, user_id: Uuid, export_id: Uuid) -> Result<Option<Export>> {
+        let export = user_export::Entity::find_by_id(export_id)
+            .filter(user_export::Column::UserId.eq(user_id))
+            .one(&self.conn)
+            .await?;
+
+        match export {
+            Some(export) => Ok(Some(with_parts(&self.conn, export).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn store_part(
+        &self,
+        export_id: Uuid,
+        part: &str,
+        data: serde_json::Value,
+    ) -> Result<Option<Export>> {
+        let Some(export) = user_export::Entity::find_by_id(export_id)
+            .one(&self.conn)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        user_export_part::Entity::insert(user_export_part::ActiveModel {
+            export_id: Set(export_id),
+            part: Set(part.to_string()),
+            data: Set(data),
+            received_at: Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                user_export_part::Column::ExportId,
+                user_export_part::Column::Part,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&self.conn)
+        .await?;
+
+        Ok(Some(with_parts(&self.conn, export).await?))
+    }
+
+    async fn export_parts(&self, export_id: Uuid) -> Result<BTreeMap<String, serde_json::Value>> {
+        let parts = user_export_part::Entity::find()
+            .filter(user_export_part::Column::ExportId.eq(export_id))
+            .all(&self.conn)
+            .await?;
+
+        Ok(parts
+            .into_iter()
+            .map(|part| (part.part, part.data))
+            .collect())
+    }
+
+    async fn complete_export(&self, export_id: Uuid, archive_path: &str) -> Result<Export> {
+        let tx = self.conn.begin().await?;
+
+        let export = user_export::Entity::find_by_id(export_id)
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Export not found".into()))?;
+        // Redelivered parts can finish an export twice, and late ones can turn
+        // up after it failed; only a pending export is completed and notifies
+        if export.status != ExportStatus::Pending {
+            let export = with_parts(&tx, export).await?;
+            tx.commit().await?;
+            return Ok(export);
+        }
+
+        let export = user_export::ActiveModel {
+            id: Unchanged(export_id),
+            status: Set(ExportStatus::Ready),
+            completed_at: Set(Some(Utc::now().into())),
+            archive_path: Set(Some(archive_path.to_string())),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            export.user_id,
+            "user_export_ready",
+            serde_json::json!({
+                "export_id": export.id,
+                "user_id": export.user_id,
+                "download_path": download_path(export.user_id, export.id),
+            }),
+        )
+        .await?;
+
+        let export = with_parts(&tx, export).await?;
+        tx.commit().await?;
+        Ok(export)
+    }
+
+    async fn expire_exports(&self, now: DateTime<Utc>) -> Result<Vec<Export>> {
+        let tx = self.conn.begin().await?;
+
+        let expired = user_export::Entity::find()
+            .filter(user_export::Column::Status.eq(ExportStatus::Pending))
+            .filter(user_export::Column::DeadlineAt.lt(now))
+            .lock_exclusive()
+            .all(&tx)
+            .await?;
+
+        let mut exports = Vec::with_capacity(expired.len());
+        for export in expired {
+            let export = user_export::ActiveModel {
+                id: Unchanged(export.id),
+                status: Set(ExportStatus::Failed),
+                ..Default::default()
+            }
+            .update(&tx)
+            .await?;
+            let export = with_parts(&tx, export).await?;
+
+            outbox::insert_outbox_event(
+                &tx,
+                "user",
+                export.user_id,
+                "user_export_failed",
+                serde_json::json!({
+                    "export_id": export.id,
+                    "user_id": export.user_id,
+                    "missing_parts": export.missing_parts(),
+                }),
+            )
+            .await?;
+            exports.push(export);
+        }
+
+        tx.commit().await?;
+        Ok(exports)
+    }
+}
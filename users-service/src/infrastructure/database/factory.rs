@@ -5,12 +5,17 @@ use common::config::CacheSettings;
 use common::error::Result;
 use migration::{Migrator, MigratorTrait};
 
-use crate::domain::repository::DynUserRepository;
+use crate::domain::repository::{
+    DynErasureRepository, DynExportRepository, DynFollowRepository, DynUserRepository,
+};
 use sea_orm::DatabaseConnection;
 
 #[derive(Debug, Clone)]
 pub struct RepoProvider {
     pub users: DynUserRepository,
+    pub follows: DynFollowRepository,
+    pub erasures: DynErasureRepository,
+    pub exports: DynExportRepository,
 }
 
 impl RepoProvider {
@@ -20,13 +25,24 @@ impl RepoProvider {
     ) -> Result<RepoProvider> {
         Migrator::up(&conn, None).await.unwrap();
 
-        let db_repo: DynUserRepository = Arc::new(super::seaorm::SeaOrmUserRepository::new(conn));
+        let db_repo: DynUserRepository =
+            Arc::new(super::seaorm::SeaOrmUserRepository::new(conn.clone()));
+        let follows: DynFollowRepository = Arc::new(super::logger::LoggedFollowRepository::new(
+            Arc::new(super::follows::SeaOrmFollowRepository::new(conn.clone())),
+        ));
+        let erasures: DynErasureRepository = Arc::new(super::logger::LoggedErasureRepository::new(
+            Arc::new(super::erasures::SeaOrmErasureRepository::new(conn.clone())),
+        ));
+        let exports: DynExportRepository = Arc::new(super::logger::LoggedExportRepository::new(
+            Arc::new(super::exports::SeaOrmExportRepository::new(conn)),
+        ));
 
         let local_cache = LocalCache::new(cache_config);
 
         let cached: DynUserRepository = if let Some(ref redis_cfg) = cache_config.redis {
             let redis_cache = RedisCache::new(&redis_cfg.url())
-                .map_err(|e| common::error::AppError::InvalidConfiguration(e.to_string()))?;
+                .map_err(|e| common::error::AppError::InvalidConfiguration(e.to_string()))?
+                .with_key_prefix(&redis_cfg.key_prefix);
             let tiered = TieredCache::new(local_cache, cache_config.ttl()).add_l2(redis_cache);
             Arc::new(super::cache::CachedUserRepository::new(
                 db_repo,
@@ -42,6 +58,11 @@ impl RepoProvider {
         };
 
         let users_repo = Arc::new(super::logger::LoggedUserRepository::new(cached));
-        Ok(RepoProvider { users: users_repo })
+        Ok(RepoProvider {
+            users: users_repo,
+            follows,
+            erasures,
+            exports,
+        })
     }
 }
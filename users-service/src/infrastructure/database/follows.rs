@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use common::{
+    error::{AppError, Result},
+    outbox,
+    pagination::Pagination,
+};
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, TransactionTrait, TryInsertResult,
+    sea_query::{OnConflict, Query, SelectStatement},
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        follow::{self, Follow},
+        user::{self, User},
+    },
+    follow::{FollowCounts, FollowEdge},
+    repository::FollowRepository,
+    status,
+};
+
+#[derive(Debug, Clone)]
+pub struct SeaOrmFollowRepository {
+    conn: DatabaseConnection,
+}
+
+impl SeaOrmFollowRepository {
+    pub fn new(conn: DatabaseConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Ids of users that are not in the trash; follows of trashed users are hidden.
+    fn live_user_ids() -> SelectStatement {
+        Query::select()
+            .column(user::Column::Id)
+            .from(user::Entity)
+            .and_where(user::Column::DeletedAt.is_null())
+            .to_owned()
+    }
+
+    async fn find_live_user(tx: &DatabaseTransaction, id: Uuid) -> Result<Option<User>> {
+        Ok(user::Entity::find_by_id(id)
+            .filter(user::Column::DeletedAt.is_null())
+            .one(tx)
+            .await?)
+    }
+
+    /// Pages through the follows where `by` is `user_id`, resolving the users
+    /// on the `other` side.
+    async fn list_edges(
+        &self,
+        by: follow::Column,
+        other: follow::Column,
+        other_id: fn(&Follow) -> Uuid,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)> {
+        let paginator = follow::Entity::find()
+            .filter(by.eq(user_id))
+            .filter(other.in_subquery(Self::live_user_ids()))
+            .order_by_desc(follow::Column::CreatedAt)
+            .order_by_asc(other)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total = paginator.num_items().await?;
+        let follows = paginator.fetch_page(pagination.page - 1).await?;
+
+        let ids: Vec<Uuid> = follows.iter().map(other_id).collect();
+        let mut users: HashMap<Uuid, User> = user::Entity::find()
+            .filter(user::Column::Id.is_in(ids))
+            .all(&self.conn)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+
+        let edges = follows
+            .iter()
+            .filter_map(|follow| {
+                users.remove(&other_id(follow)).map(|user| FollowEdge {
+                    user,
+                    followed_at: follow.created_at.into(),
+                })
+            })
+            .collect();
+
+        Ok((edges, total))
+    }
+
+    async fn count_live(
+        &self,
+        by: follow::Column,
+        other: follow::Column,
+        user_id: Uuid,
+    ) -> Result<u64> {
+        Ok(follow::Entity::find()
+            .filter(by.eq(user_id))
+            .filter(other.in_subquery(Self::live_user_ids()))
+            .count(&self.conn)
+            .await?)
+    }
+}
+
+#[async_trait]
+impl FollowRepository for SeaOrmFollowRepository {
+    async fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        if follower_id == followee_id {
+            return Err(AppError::ValidationError(
+                "Users cannot follow themselves".to_string(),
+            ));
+        }
+
+        let tx = self.conn.begin().await?;
+
+        let followee = Self::find_live_user(&tx, followee_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".to_string()))?;
+        let follower = Self::find_live_user(&tx, follower_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("Follower not found".to_string()))?;
+        status::ensure_active(&follower)?;
+
+        let result = follow::Entity::insert(follow::ActiveModel {
+            follower_id: Set(follower.id),
+            followee_id: Set(followee.id),
+            created_at: Set(Utc::now().into()),
+        })
+        .on_conflict(
+            OnConflict::columns([follow::Column::FollowerId, follow::Column::FolloweeId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec_without_returning(&tx)
+        .await?;
+        let inserted = matches!(result, TryInsertResult::Inserted(rows) if rows > 0);
+
+        if inserted {
+            outbox::insert_outbox_event(
+                &tx,
+                "user",
+                followee.id,
+                "user_followed",
+                serde_json::json!({
+                    "follower_id": follower.id,
+                    "follower_username": follower.username,
+                    "followee_id": followee.id,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        let tx = self.conn.begin().await?;
+
+        let result = follow::Entity::delete_many()
+            .filter(follow::Column::FollowerId.eq(follower_id))
+            .filter(follow::Column::FolloweeId.eq(followee_id))
+            .exec(&tx)
+            .await?;
+        let removed = result.rows_affected > 0;
+
+        if removed {
+            outbox::insert_outbox_event(
+                &tx,
+                "user",
+                followee_id,
+                "user_unfollowed",
+                serde_json::json!({
+                    "follower_id": follower_id,
+                    "followee_id": followee_id,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    async fn list_followers(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)> {
+        self.list_edges(
+            follow::Column::FolloweeId,
+            follow::Column::FollowerId,
+            |follow| follow.follower_id,
+            user_id,
+            pagination,
+        )
+        .await
+    }
+
+    async fn list_following(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)> {
+        self.list_edges(
+            follow::Column::FollowerId,
+            follow::Column::FolloweeId,
+            |follow| follow.followee_id,
+            user_id,
+            pagination,
+        )
+        .await
+    }
+
+    async fn follow_counts(&self, user_id: Uuid) -> Result<FollowCounts> {
+        let (followers, following) = tokio::try_join!(
+            self.count_live(
+                follow::Column::FolloweeId,
+                follow::Column::FollowerId,
+                user_id
+            ),
+            self.count_live(
+                follow::Column::FollowerId,
+                follow::Column::FolloweeId,
+                user_id
+            ),
+        )?;
+
+        Ok(FollowCounts {
+            followers,
+            following,
+        })
+    }
+}
@@ -1,11 +1,26 @@
-use std::{sync::Arc, time::Instant};
+use std::{collections::BTreeMap, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use common::{error::Result, pagination::Pagination};
+use common::{error::Result, etag::IfMatch, pagination::Pagination};
 
-use crate::domain::{entities::user::User, repository::UserRepository};
+use crate::domain::{
+    entities::{
+        email_change::EmailChange,
+        user::{User, UserStatus},
+        user_preference::UserPreferences,
+    },
+    erasure::Erasure,
+    export::Export,
+    follow::{FollowCounts, FollowEdge},
+    preferences::Preferences,
+    profile::ProfileUpdate,
+    repository::{ErasureRepository, ExportRepository, FollowRepository, UserRepository},
+    search::UserSearchHit,
+    status::StatusChange,
+};
 
 #[derive(Debug)]
 pub struct LoggedUserRepository {
@@ -48,6 +63,19 @@ impl UserRepository for LoggedUserRepository {
         result
     }
 
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        let start = Instant::now();
+        let result = self.inner.get_users_by_ids(ids).await;
+
+        match &result {
+            Ok(users) => {
+                tracing::info!(requested = ids.len(), found = users.len(), elapsed_ms = %start.elapsed().as_millis(), "Users found")
+            }
+            Err(e) => tracing::error!(requested = ids.len(), error = %e, "Failed to get users"),
+        }
+        result
+    }
+
     async fn get_user_by_name(&self, username: String) -> Result<Option<User>> {
         let start = Instant::now();
         let name = username.clone();
@@ -63,19 +91,127 @@ impl UserRepository for LoggedUserRepository {
         result
     }
 
-    async fn update_user(&self, user: User) -> Result<()> {
+    async fn get_user_by_former_name(&self, username: String) -> Result<Option<User>> {
+        let start = Instant::now();
+        let name = username.clone();
+        let result = self.inner.get_user_by_former_name(username).await;
+
+        match &result {
+            Ok(Some(u)) => {
+                tracing::info!(former_username = %name, user_id = %u.id, username = %u.username, elapsed_ms = %start.elapsed().as_millis(), "User found by former name")
+            }
+            Ok(None) => tracing::warn!(former_username = %name, "User not found by former name"),
+            Err(e) => {
+                tracing::error!(former_username = %name, error = %e, "Failed to get user by former name")
+            }
+        }
+        result
+    }
+
+    async fn update_user(&self, user: User, if_match: &IfMatch) -> Result<(User, String)> {
         let start = Instant::now();
-        tracing::info!(user_id = %user.id, username = %user.username, "Updating user");
+        tracing::info!(user_id = %user.id, username = %user.username, if_match = ?if_match, "Updating user");
 
-        let result = self.inner.update_user(user).await;
+        let result = self.inner.update_user(user, if_match).await;
 
         match &result {
-            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "User updated"),
+            Ok((u, _)) => {
+                tracing::info!(version = u.version, elapsed_ms = %start.elapsed().as_millis(), "User updated")
+            }
             Err(e) => tracing::error!(error = %e, "Failed to update user"),
         }
         result
     }
 
+    async fn request_email_change(
+        &self,
+        id: Uuid,
+        new_email: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<EmailChange> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, "Requesting email change");
+
+        let result = self
+            .inner
+            .request_email_change(id, new_email, expires_at)
+            .await;
+
+        match &result {
+            Ok(change) => {
+                tracing::info!(user_id = %id, expires_at = %change.expires_at, elapsed_ms = %start.elapsed().as_millis(), "Email change requested")
+            }
+            Err(e) => tracing::error!(user_id = %id, error = %e, "Failed to request email change"),
+        }
+        result
+    }
+
+    async fn confirm_email_change(&self, id: Uuid, token: String) -> Result<User> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, "Confirming email change");
+
+        let result = self.inner.confirm_email_change(id, token).await;
+
+        match &result {
+            Ok(u) => {
+                tracing::info!(user_id = %id, version = u.version, elapsed_ms = %start.elapsed().as_millis(), "Email change confirmed")
+            }
+            Err(e) => tracing::error!(user_id = %id, error = %e, "Failed to confirm email change"),
+        }
+        result
+    }
+
+    async fn update_profile(&self, id: Uuid, update: ProfileUpdate) -> Result<User> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, update = ?update, "Updating user profile");
+
+        let result = self.inner.update_profile(id, update).await;
+
+        match &result {
+            Ok(u) => {
+                tracing::info!(version = u.version, elapsed_ms = %start.elapsed().as_millis(), "User profile updated")
+            }
+            Err(e) => tracing::error!(user_id = %id, error = %e, "Failed to update user profile"),
+        }
+        result
+    }
+
+    async fn get_preferences(&self, id: Uuid) -> Result<UserPreferences> {
+        let start = Instant::now();
+        let result = self.inner.get_preferences(id).await;
+
+        match &result {
+            Ok(p) => {
+                tracing::info!(user_id = %id, version = p.version, elapsed_ms = %start.elapsed().as_millis(), "Fetched user preferences")
+            }
+            Err(e) => {
+                tracing::error!(user_id = %id, error = %e, "Failed to fetch user preferences")
+            }
+        }
+        result
+    }
+
+    async fn update_preferences(
+        &self,
+        id: Uuid,
+        preferences: Preferences,
+    ) -> Result<UserPreferences> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, preferences = ?preferences, "Updating user preferences");
+
+        let result = self.inner.update_preferences(id, preferences).await;
+
+        match &result {
+            Ok(p) => {
+                tracing::info!(version = p.version, elapsed_ms = %start.elapsed().as_millis(), "User preferences updated")
+            }
+            Err(e) => {
+                tracing::error!(user_id = %id, error = %e, "Failed to update user preferences")
+            }
+        }
+        result
+    }
+
     async fn delete_user(&self, id: Uuid) -> Result<()> {
         let start = Instant::now();
         let id_str = id.to_string();
@@ -102,4 +238,379 @@ impl UserRepository for LoggedUserRepository {
         }
         result
     }
+
+    async fn search_users(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<UserSearchHit>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.search_users(query, pagination).await;
+
+        match &result {
+            Ok((hits, total)) => {
+                tracing::info!(query = %query, count = hits.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Users searched")
+            }
+            Err(e) => tracing::error!(query = %query, error = %e, "Failed to search users"),
+        }
+        result
+    }
+
+    async fn list_deleted_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_deleted_users(pagination).await;
+
+        match &result {
+            Ok((users, total)) => {
+                tracing::info!(count = users.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Trashed users listed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to list trashed users"),
+        }
+        result
+    }
+
+    async fn restore_user(&self, id: Uuid) -> Result<User> {
+        let start = Instant::now();
+        let id_str = id.to_string();
+        tracing::info!(user_id = %id_str, "Restoring user");
+
+        let result = self.inner.restore_user(id).await;
+
+        match &result {
+            Ok(_) => tracing::info!(elapsed_ms = %start.elapsed().as_millis(), "User restored"),
+            Err(e) => tracing::error!(user_id = %id_str, error = %e, "Failed to restore user"),
+        }
+        result
+    }
+
+    async fn list_users_by_status(
+        &self,
+        status: UserStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<User>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_users_by_status(status, pagination).await;
+
+        match &result {
+            Ok((users, total)) => {
+                tracing::info!(status = ?status, count = users.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Users listed by status")
+            }
+            Err(e) => {
+                tracing::error!(status = ?status, error = %e, "Failed to list users by status")
+            }
+        }
+        result
+    }
+
+    async fn change_status(&self, id: Uuid, change: StatusChange) -> Result<User> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, change = ?change, "Changing user status");
+
+        let result = self.inner.change_status(id, change).await;
+
+        match &result {
+            Ok(u) => {
+                tracing::info!(user_id = %id, status = ?u.status, version = u.version, elapsed_ms = %start.elapsed().as_millis(), "User status changed")
+            }
+            Err(e) => tracing::error!(user_id = %id, error = %e, "Failed to change user status"),
+        }
+        result
+    }
+
+    async fn reactivate_expired(&self, now: DateTime<Utc>) -> Result<Vec<User>> {
+        let start = Instant::now();
+        let result = self.inner.reactivate_expired(now).await;
+
+        match &result {
+            Ok(users) if users.is_empty() => {}
+            Ok(users) => {
+                tracing::info!(reactivated = users.len(), elapsed_ms = %start.elapsed().as_millis(), "Lapsed suspensions lifted")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to lift lapsed suspensions"),
+        }
+        result
+    }
+
+    async fn purge_deleted_users(
+        &self,
+        before: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>> {
+        let start = Instant::now();
+        let result = self.inner.purge_deleted_users(before, deadline).await;
+
+        match &result {
+            Ok(purged) if purged.is_empty() => {}
+            Ok(purged) => {
+                tracing::info!(purged = purged.len(), before = %before, elapsed_ms = %start.elapsed().as_millis(), "Trashed users purged")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to purge trashed users"),
+        }
+        result
+    }
+
+    async fn erase_user(&self, id: Uuid, deadline: DateTime<Utc>) -> Result<Erasure> {
+        let start = Instant::now();
+        tracing::info!(user_id = %id, "Erasing user");
+
+        let result = self.inner.erase_user(id, deadline).await;
+
+        match &result {
+            Ok(erasure) => {
+                tracing::info!(erasure_id = %erasure.id, status = ?erasure.status, elapsed_ms = %start.elapsed().as_millis(), "User erasure requested")
+            }
+            Err(e) => tracing::error!(user_id = %id, error = %e, "Failed to erase user"),
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedFollowRepository {
+    inner: Arc<dyn FollowRepository>,
+}
+
+impl LoggedFollowRepository {
+    pub fn new(inner: Arc<dyn FollowRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl FollowRepository for LoggedFollowRepository {
+    async fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        let start = Instant::now();
+        tracing::info!(follower_id = %follower_id, followee_id = %followee_id, "Following user");
+
+        let result = self.inner.follow(follower_id, followee_id).await;
+
+        match &result {
+            Ok(created) => {
+                tracing::info!(created = created, elapsed_ms = %start.elapsed().as_millis(), "User followed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to follow user"),
+        }
+        result
+    }
+
+    async fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        let start = Instant::now();
+        tracing::info!(follower_id = %follower_id, followee_id = %followee_id, "Unfollowing user");
+
+        let result = self.inner.unfollow(follower_id, followee_id).await;
+
+        match &result {
+            Ok(removed) => {
+                tracing::info!(removed = removed, elapsed_ms = %start.elapsed().as_millis(), "User unfollowed")
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to unfollow user"),
+        }
+        result
+    }
+
+    async fn list_followers(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_followers(user_id, pagination).await;
+
+        match &result {
+            Ok((edges, total)) => {
+                tracing::info!(user_id = %user_id, count = edges.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Followers listed")
+            }
+            Err(e) => tracing::error!(user_id = %user_id, error = %e, "Failed to list followers"),
+        }
+        result
+    }
+
+    async fn list_following(
+        &self,
+        user_id: Uuid,
+        pagination: &Pagination,
+    ) -> Result<(Vec<FollowEdge>, u64)> {
+        let start = Instant::now();
+        let result = self.inner.list_following(user_id, pagination).await;
+
+        match &result {
+            Ok((edges, total)) => {
+                tracing::info!(user_id = %user_id, count = edges.len(), total = total, elapsed_ms = %start.elapsed().as_millis(), "Followed users listed")
+            }
+            Err(e) => {
+                tracing::error!(user_id = %user_id, error = %e, "Failed to list followed users")
+            }
+        }
+        result
+    }
+
+    async fn follow_counts(&self, user_id: Uuid) -> Result<FollowCounts> {
+        let result = self.inner.follow_counts(user_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(user_id = %user_id, error = %e, "Failed to count follows");
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedErasureRepository {
+    inner: Arc<dyn ErasureRepository>,
+}
+
+impl LoggedErasureRepository {
+    pub fn new(inner: Arc<dyn ErasureRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl ErasureRepository for LoggedErasureRepository {
+    async fn get_erasure(&self, user_id: Uuid) -> Result<Option<Erasure>> {
+        let result = self.inner.get_erasure(user_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(user_id = %user_id, error = %e, "Failed to get erasure");
+        }
+        result
+    }
+
+    async fn complete_step(
+        &self,
+        erasure_id: Uuid,
+        step: &str,
+        detail: serde_json::Value,
+    ) -> Result<Option<Erasure>> {
+        let start = Instant::now();
+        let result = self.inner.complete_step(erasure_id, step, detail).await;
+
+        match &result {
+            Ok(Some(erasure)) => {
+                tracing::info!(erasure_id = %erasure_id, step = step, status = ?erasure.status, elapsed_ms = %start.elapsed().as_millis(), "Erasure step completed")
+            }
+            Ok(None) => tracing::warn!(erasure_id = %erasure_id, step = step, "Erasure not found"),
+            Err(e) => {
+                tracing::error!(erasure_id = %erasure_id, step = step, error = %e, "Failed to complete erasure step")
+            }
+        }
+        result
+    }
+
+    async fn expire_erasures(&self, now: DateTime<Utc>) -> Result<Vec<Erasure>> {
+        let result = self.inner.expire_erasures(now).await;
+
+        match &result {
+            Ok(expired) => {
+                for erasure in expired {
+                    let outstanding: Vec<_> = erasure
+                        .steps
+                        .iter()
+                        .filter(|step| step.completed_at.is_none())
+                        .map(|step| step.step.as_str())
+                        .collect();
+                    tracing::warn!(erasure_id = %erasure.id, user_id = %erasure.user_id, outstanding = ?outstanding, "Erasure timed out");
+                }
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to expire erasures"),
+        }
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct LoggedExportRepository {
+    inner: Arc<dyn ExportRepository>,
+}
+
+impl LoggedExportRepository {
+    pub fn new(inner: Arc<dyn ExportRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl ExportRepository for LoggedExportRepository {
+    async fn request_export(&self, user_id: Uuid, deadline: DateTime<Utc>) -> Result<Export> {
+        let start = Instant::now();
+        tracing::info!(user_id = %user_id, "Requesting export");
+
+        let result = self.inner.request_export(user_id, deadline).await;
+
+        match &result {
+            Ok(export) => {
+                tracing::info!(export_id = %export.id, status = ?export.status, elapsed_ms = %start.elapsed().as_millis(), "Export requested")
+            }
+            Err(e) => tracing::error!(user_id = %user_id, error = %e, "Failed to request export"),
+        }
+        result
+    }
+
+    async fn get_export(&self, user_id: Uuid, export_id: Uuid) -> Result<Option<Export>> {
+        let result = self.inner.get_export(user_id, export_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(user_id = %user_id, export_id = %export_id, error = %e, "Failed to get export");
+        }
+        result
+    }
+
+    async fn store_part(
+        &self,
+        export_id: Uuid,
+        part: &str,
+        data: serde_json::Value,
+    ) -> Result<Option<Export>> {
+        let start = Instant::now();
+        let result = self.inner.store_part(export_id, part, data).await;
+
+        match &result {
+            Ok(Some(_)) => {
+                tracing::info!(export_id = %export_id, part = part, elapsed_ms = %start.elapsed().as_millis(), "Export part stored")
+            }
+            Ok(None) => tracing::warn!(export_id = %export_id, part = part, "Export not found"),
+            Err(e) => {
+                tracing::error!(export_id = %export_id, part = part, error = %e, "Failed to store export part")
+            }
+        }
+        result
+    }
+
+    async fn export_parts(&self, export_id: Uuid) -> Result<BTreeMap<String, serde_json::Value>> {
+        let result = self.inner.export_parts(export_id).await;
+
+        if let Err(e) = &result {
+            tracing::error!(export_id = %export_id, error = %e, "Failed to load export parts");
+        }
+        result
+    }
+
+    async fn complete_export(&self, export_id: Uuid, archive_path: &str) -> Result<Export> {
+        let start = Instant::now();
+        let result = self.inner.complete_export(export_id, archive_path).await;
+
+        match &result {
+            Ok(_) => {
+                tracing::info!(export_id = %export_id, archive_path = archive_path, elapsed_ms = %start.elapsed().as_millis(), "Export ready")
+            }
+            Err(e) => {
+                tracing::error!(export_id = %export_id, error = %e, "Failed to complete export")
+            }
+        }
+        result
+    }
+
+    async fn expire_exports(&self, now: DateTime<Utc>) -> Result<Vec<Export>> {
+        let result = self.inner.expire_exports(now).await;
+
+        match &result {
+            Ok(expired) => {
+                for export in expired {
+                    tracing::warn!(export_id = %export.id, user_id = %export.user_id, missing = ?export.missing_parts(), "Export timed out");
+                }
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to expire exports"),
+        }
+        result
+    }
 }
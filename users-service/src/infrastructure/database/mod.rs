@@ -1,6 +1,9 @@
 pub mod bootstrap;
 mod cache;
+pub mod erasures;
+pub mod exports;
 pub mod factory;
+pub mod follows;
 mod logger;
 pub mod seaorm;
 mod url;
@@ -8,5 +11,7 @@ mod url;
 pub use bootstrap::bootstrap_db;
 pub use cache::CachedUserRepository;
 pub use factory::RepoProvider;
-pub use logger::LoggedUserRepository;
+pub use logger::{
+    LoggedErasureRepository, LoggedExportRepository, LoggedFollowRepository, LoggedUserRepository,
+};
 pub use url::build_db_url;
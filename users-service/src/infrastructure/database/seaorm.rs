@@ -1,19 +1,47 @@
 use uuid::Uuid;
 
 use async_trait::async_trait;
-use common::{error::Result, outbox, pagination::Pagination};
+use chrono::{DateTime, Utc};
+use common::{
+    db::escape_like,
+    error::{AppError, Result},
+    etag::IfMatch,
+    outbox,
+    pagination::Pagination,
+};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{Set, Unchanged},
-    ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, TransactionTrait, TryIntoModel,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Statement,
+    TransactionTrait, TryIntoModel,
+    sea_query::{self, LockBehavior, LockType, OnConflict},
 };
 
 use crate::domain::{
-    entities::{self, user::User},
+    entities::{
+        self,
+        email_change::{self, EmailChange},
+        user::{SocialLinks, User, UserStatus},
+        user_erasure::{self, ErasureStatus},
+        user_erasure_step,
+        user_preference::{self, UserPreferences},
+        username_history,
+    },
+    erasure::{ERASURE_STEPS, Erasure},
+    identity,
+    preferences::Preferences,
+    profile::ProfileUpdate,
     repository::UserRepository,
+    search::UserSearchHit,
+    status::{self, StatusChange},
 };
 
+/// Live users matching `$1` exactly, by prefix (`$2`) or by trigram similarity.
+const SEARCH_MATCHES: &str = "deleted_at IS NULL AND (\
+    username LIKE $2 OR lower(display_name) LIKE $2 \
+    OR username % $1 OR lower(display_name) % $1)";
+
 #[derive(Debug, Clone)]
 pub struct SeaOrmUserRepository {
     conn: DatabaseConnection,
@@ -23,6 +51,135 @@ impl SeaOrmUserRepository {
     pub fn new(conn: DatabaseConnection) -> Self {
         Self { conn }
     }
+
+    /// Users that are not in the trash.
+    fn live() -> Select<entities::user::Entity> {
+        entities::user::Entity::find().filter(entities::user::Column::DeletedAt.is_null())
+    }
+
+    /// Fails unless `username` is free for `user_id` to take: no other user may
+    /// have it, now or in their history. Taking back one of the user's own
+    /// former names drops it from their history.
+    async fn claim_username(tx: &DatabaseTransaction, username: &str, user_id: Uuid) -> Result<()> {
+        identity::ensure_username_allowed(username)?;
+
+        let taken = entities::user::Entity::find()
+            .filter(entities::user::Column::Username.eq(username))
+            .filter(entities::user::Column::Id.ne(user_id))
+            .count(tx)
+            .await?;
+        if taken > 0 {
+            return Err(AppError::ConflictError("Username is already taken".into()));
+        }
+
+        match username_history::Entity::find_by_id(username)
+            .one(tx)
+            .await?
+        {
+            Some(former) if former.user_id == user_id => {
+                username_history::Entity::delete_by_id(username)
+                    .exec(tx)
+                    .await?;
+            }
+            Some(_) => {
+                return Err(AppError::ConflictError("Username is already taken".into()));
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    async fn ensure_email_available(
+        tx: &DatabaseTransaction,
+        email: &str,
+        user_id: Uuid,
+    ) -> Result<()> {
+        let taken = entities::user::Entity::find()
+            .filter(entities::user::Column::Email.eq(email))
+            .filter(entities::user::Column::Id.ne(user_id))
+            .count(tx)
+            .await?;
+        if taken > 0 {
+            return Err(AppError::ConflictError("Email is already in use".into()));
+        }
+        Ok(())
+    }
+
+    /// Writes a `user_status_changed` event so other services can hide the
+    /// user's content or reject their writes. `lapsed` marks suspensions lifted
+    /// because they expired.
+    async fn insert_status_changed_event(
+        tx: &DatabaseTransaction,
+        previous: UserStatus,
+        user: &User,
+        lapsed: bool,
+    ) -> Result<()> {
+        outbox::insert_outbox_event(
+            tx,
+            "user",
+            user.id,
+            "user_status_changed",
+            serde_json::json!({
+                "id": user.id,
+                "username": user.username,
+                "status": user.status,
+                "previous_status": previous,
+                "reason": user.status_reason,
+                "expires_at": user.status_expires_at,
+                "lapsed": lapsed,
+                "version": user.version,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Writes a `user_updated` event listing each changed field with its value
+    /// before and after the update. Bookkeeping columns are left out, and
+    /// nothing is written when no other field changed.
+    async fn insert_updated_event(
+        tx: &DatabaseTransaction,
+        before: &User,
+        after: &User,
+    ) -> Result<()> {
+        const IGNORED: [&str; 4] = ["version", "created_at", "updated_at", "deleted_at"];
+
+        let (serde_json::Value::Object(before_fields), serde_json::Value::Object(after_fields)) =
+            (serde_json::to_value(before)?, serde_json::to_value(after)?)
+        else {
+            return Ok(());
+        };
+
+        let changes: serde_json::Map<_, _> = after_fields
+            .into_iter()
+            .filter(|(field, _)| !IGNORED.contains(&field.as_str()))
+            .filter_map(|(field, after)| {
+                let before = before_fields.get(&field).cloned().unwrap_or_default();
+                (before != after).then(|| {
+                    let change = serde_json::json!({ "before": before, "after": after });
+                    (field, change)
+                })
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        outbox::insert_outbox_event(
+            tx,
+            "user",
+            after.id,
+            "user_updated",
+            serde_json::json!({
+                "id": after.id,
+                "username": after.username,
+                "version": after.version,
+                "changes": changes,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -30,6 +187,9 @@ impl UserRepository for SeaOrmUserRepository {
     async fn create_user(&self, user: User) -> Result<User> {
         let tx = self.conn.begin().await?;
 
+        Self::claim_username(&tx, &user.username, user.id).await?;
+        Self::ensure_email_available(&tx, &user.email, user.id).await?;
+
         let active_model = entities::user::ActiveModel::from(user);
         let user_model = active_model.insert(&tx).await?;
 
@@ -53,15 +213,29 @@ impl UserRepository for SeaOrmUserRepository {
     }
 
     async fn get_user_by_id(&self, id: Uuid) -> Result<Option<User>> {
-        let user = entities::user::Entity::find_by_id(id)
+        let user = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
             .one(&self.conn)
             .await?;
 
         Ok(user)
     }
 
+    async fn get_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let users = Self::live()
+            .filter(entities::user::Column::Id.is_in(ids.iter().copied()))
+            .all(&self.conn)
+            .await?;
+
+        Ok(users)
+    }
+
     async fn get_user_by_name(&self, username: String) -> Result<Option<User>> {
-        let user = entities::user::Entity::find()
+        let user = Self::live()
             .filter(entities::user::Column::Username.eq(username.clone()))
             .one(&self.conn)
             .await?;
@@ -69,40 +243,700 @@ impl UserRepository for SeaOrmUserRepository {
         Ok(user)
     }
 
-    async fn update_user(&self, user: User) -> Result<()> {
+    async fn get_user_by_former_name(&self, username: String) -> Result<Option<User>> {
+        let Some(former) = username_history::Entity::find_by_id(username)
+            .one(&self.conn)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let user = Self::live()
+            .filter(entities::user::Column::Id.eq(former.user_id))
+            .one(&self.conn)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn update_user(&self, user: User, if_match: &IfMatch) -> Result<(User, String)> {
+        let tx = self.conn.begin().await?;
+
+        let current = Self::live()
+            .filter(entities::user::Column::Id.eq(user.id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+        if_match.check(current.version)?;
+        status::ensure_active(&current)?;
+
+        if user.email != current.email {
+            return Err(AppError::ValidationError(format!(
+                "Email changes must be verified through /users/{}/email-change",
+                current.id
+            )));
+        }
+        let now = chrono::Utc::now();
+        if user.username != current.username {
+            Self::claim_username(&tx, &user.username, current.id).await?;
+            username_history::Entity::insert(username_history::ActiveModel {
+                username: Set(current.username.clone()),
+                user_id: Set(current.id),
+                changed_at: Set(now.into()),
+            })
+            .on_conflict(
+                OnConflict::column(username_history::Column::Username)
+                    .update_columns([
+                        username_history::Column::UserId,
+                        username_history::Column::ChangedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&tx)
+            .await?;
+        }
+
         let user = entities::user::ActiveModel {
             id: Unchanged(user.id),
             username: Set(user.username),
-            email: Set(user.email),
-            created_at: Unchanged(user.created_at),
+            version: Set(current.version + 1),
+            created_at: Unchanged(current.created_at),
+            updated_at: Set(now.into()),
+            deleted_at: Unchanged(current.deleted_at),
+            // Profile fields are only changed through `update_profile`
+            ..Default::default()
+        };
+
+        let user = entities::user::Entity::update(user).exec(&tx).await?;
+        Self::insert_updated_event(&tx, &current, &user).await?;
+
+        tx.commit().await?;
+        Ok((user, current.username))
+    }
+
+    async fn request_email_change(
+        &self,
+        id: Uuid,
+        new_email: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<EmailChange> {
+        let tx = self.conn.begin().await?;
+
+        let user = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+        status::ensure_active(&user)?;
+        if new_email == user.email {
+            return Err(AppError::ValidationError(
+                "New email is the same as the current one".into(),
+            ));
+        }
+        Self::ensure_email_available(&tx, &new_email, id).await?;
+
+        let change = email_change::ActiveModel {
+            user_id: Set(id),
+            new_email: Set(new_email),
+            token: Set(Uuid::new_v4().simple().to_string()),
+            requested_at: Set(Utc::now().into()),
+            expires_at: Set(expires_at.into()),
+        };
+        email_change::Entity::insert(change.clone())
+            .on_conflict(
+                OnConflict::column(email_change::Column::UserId)
+                    .update_columns([
+                        email_change::Column::NewEmail,
+                        email_change::Column::Token,
+                        email_change::Column::RequestedAt,
+                        email_change::Column::ExpiresAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&tx)
+            .await?;
+        let change: EmailChange = change.try_into_model()?;
+
+        // The token goes to the new address only; the notification service mails it there
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            id,
+            "user_email_change_requested",
+            serde_json::json!({
+                "id": id,
+                "username": user.username,
+                "new_email": change.new_email,
+                "token": change.token,
+                "expires_at": change.expires_at,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(change)
+    }
+
+    async fn confirm_email_change(&self, id: Uuid, token: String) -> Result<User> {
+        let tx = self.conn.begin().await?;
+
+        let current = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+        let change = email_change::Entity::find_by_id(id)
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("No pending email change".into()))?;
+        if change.token != token {
+            return Err(AppError::ValidationError(
+                "Invalid email change token".into(),
+            ));
+        }
+
+        email_change::Entity::delete_by_id(id).exec(&tx).await?;
+        let now = Utc::now();
+        if change.expires_at < now {
+            tx.commit().await?;
+            return Err(AppError::ValidationError(
+                "Email change token has expired".into(),
+            ));
+        }
+        Self::ensure_email_available(&tx, &change.new_email, id).await?;
+
+        let user = entities::user::ActiveModel {
+            id: Unchanged(id),
+            email: Set(change.new_email),
+            version: Set(current.version + 1),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+        Self::insert_updated_event(&tx, &current, &user).await?;
+
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn update_profile(&self, id: Uuid, update: ProfileUpdate) -> Result<User> {
+        let tx = self.conn.begin().await?;
+
+        let current = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+        status::ensure_active(&current)?;
+
+        if update.is_empty() {
+            return Ok(current);
+        }
+
+        let mut user = entities::user::ActiveModel {
+            id: Unchanged(current.id),
+            version: Set(current.version + 1),
             updated_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
         };
+        if let Some(display_name) = update.display_name {
+            user.display_name = Set(display_name);
+        }
+        if let Some(bio) = update.bio {
+            user.bio = Set(bio);
+        }
+        if let Some(avatar_url) = update.avatar_url {
+            user.avatar_url = Set(avatar_url);
+        }
+        if let Some(website) = update.website {
+            user.website = Set(website);
+        }
+        if let Some(location) = update.location {
+            user.location = Set(location);
+        }
+        if let Some(social_links) = update.social_links {
+            user.social_links = Set(social_links);
+        }
+
+        let user = user.update(&tx).await?;
+        Self::insert_updated_event(&tx, &current, &user).await?;
 
-        entities::user::Entity::update(user)
-            .exec(&self.conn)
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn get_preferences(&self, id: Uuid) -> Result<UserPreferences> {
+        let user = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .one(&self.conn)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+
+        let stored = user_preference::Entity::find_by_id(id)
+            .one(&self.conn)
             .await?;
+        Ok(stored.unwrap_or_else(|| UserPreferences {
+            user_id: id,
+            document: Preferences::default(),
+            version: 0,
+            updated_at: user.created_at,
+        }))
+    }
 
-        Ok(())
+    async fn update_preferences(
+        &self,
+        id: Uuid,
+        preferences: Preferences,
+    ) -> Result<UserPreferences> {
+        let tx = self.conn.begin().await?;
+
+        // Restricted accounts may still change these; opting out of
+        // notifications should never be blocked
+        Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+
+        let current = user_preference::Entity::find_by_id(id).one(&tx).await?;
+        if let Some(current) = &current
+            && current.document == preferences
+        {
+            return Ok(current.clone());
+        }
+
+        let stored = user_preference::ActiveModel {
+            user_id: Set(id),
+            document: Set(preferences),
+            version: Set(current.as_ref().map_or(1, |current| current.version + 1)),
+            updated_at: Set(chrono::Utc::now().into()),
+        };
+        let stored = match current {
+            Some(_) => stored.update(&tx).await?,
+            None => stored.insert(&tx).await?,
+        };
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            id,
+            "user_preferences_updated",
+            serde_json::json!({
+                "id": id,
+                "preferences": stored.document,
+                "version": stored.version,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(stored)
     }
 
     async fn delete_user(&self, id: Uuid) -> Result<()> {
+        let tx = self.conn.begin().await?;
+
+        let user = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+
+        let now = chrono::Utc::now();
+        let user = entities::user::ActiveModel {
+            id: Unchanged(user.id),
+            version: Set(user.version + 1),
+            updated_at: Set(now.into()),
+            deleted_at: Set(Some(now.into())),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user.id,
+            "user_deleted",
+            serde_json::json!({
+                "id": user.id,
+                "username": user.username,
+                "email": user.email,
+                "deleted_at": user.deleted_at,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_deleted_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)> {
+        use entities::user::Column;
+
+        let paginator = entities::user::Entity::find()
+            .filter(Column::DeletedAt.is_not_null())
+            .order_by_desc(Column::DeletedAt)
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_users = paginator.num_items().await?;
+        let users = paginator.fetch_page(pagination.page - 1).await?;
+
+        Ok((users, total_users))
+    }
+
+    async fn restore_user(&self, id: Uuid) -> Result<User> {
+        let tx = self.conn.begin().await?;
+
         let user = entities::user::Entity::find_by_id(id)
-            .one(&self.conn)
-            .await?;
+            .filter(entities::user::Column::DeletedAt.is_not_null())
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found in trash".into()))?;
 
-        if user.is_none() {
-            return Err(common::error::AppError::NotFoundError(
-                "User not found".into(),
+        let erased = user_erasure::Entity::find()
+            .filter(user_erasure::Column::UserId.eq(id))
+            .one(&tx)
+            .await?;
+        if erased.is_some() {
+            return Err(AppError::ConflictError(
+                "User has been erased and cannot be restored".into(),
             ));
         }
 
-        user.unwrap().delete(&self.conn).await?;
+        let user = entities::user::ActiveModel {
+            id: Unchanged(user.id),
+            version: Set(user.version + 1),
+            updated_at: Set(chrono::Utc::now().into()),
+            deleted_at: Set(None),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
 
-        Ok(())
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            user.id,
+            "user_restored",
+            serde_json::json!({
+                "id": user.id,
+                "username": user.username,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn erase_user(&self, id: Uuid, deadline: DateTime<Utc>) -> Result<Erasure> {
+        let tx = self.conn.begin().await?;
+
+        let user = entities::user::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+        let now = Utc::now();
+
+        let existing = user_erasure::Entity::find()
+            .filter(user_erasure::Column::UserId.eq(id))
+            .one(&tx)
+            .await?;
+        let erasure = match existing {
+            Some(erasure) if erasure.status != ErasureStatus::TimedOut => {
+                tx.commit().await?;
+                return super::erasures::with_steps(&self.conn, erasure).await;
+            }
+            // Steps that were acknowledged stay done; the others get another go
+            Some(erasure) => {
+                user_erasure::ActiveModel {
+                    id: Unchanged(erasure.id),
+                    status: Set(ErasureStatus::Pending),
+                    deadline_at: Set(deadline.into()),
+                    ..Default::default()
+                }
+                .update(&tx)
+                .await?
+            }
+            None => {
+                let erasure = user_erasure::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    user_id: Set(id),
+                    status: Set(ErasureStatus::Pending),
+                    requested_at: Set(now.into()),
+                    deadline_at: Set(deadline.into()),
+                    completed_at: Set(None),
+                }
+                .insert(&tx)
+                .await?;
+                user_erasure_step::Entity::insert_many(ERASURE_STEPS.map(|step| {
+                    user_erasure_step::ActiveModel {
+                        erasure_id: Set(erasure.id),
+                        step: Set(step.to_string()),
+                        completed_at: Set(None),
+                        detail: Set(None),
+                    }
+                }))
+                .exec(&tx)
+                .await?;
+                erasure
+            }
+        };
+
+        // Keep the row so ids stay resolvable, but nothing that identifies the person
+        let marker = id.simple();
+        let deleted_at = user.deleted_at.unwrap_or_else(|| now.into());
+        entities::user::ActiveModel {
+            id: Unchanged(id),
+            username: Set(format!("erased-{}", marker)),
+            email: Set(format!("erased-{}@erased.invalid", marker)),
+            display_name: Set(None),
+            bio: Set(None),
+            avatar_url: Set(None),
+            website: Set(None),
+            location: Set(None),
+            social_links: Set(SocialLinks::default()),
+            version: Set(user.version + 1),
+            updated_at: Set(now.into()),
+            deleted_at: Set(Some(deleted_at)),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+
+        entities::follow::Entity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(entities::follow::Column::FollowerId.eq(id))
+                    .add(entities::follow::Column::FolloweeId.eq(id)),
+            )
+            .exec(&tx)
+            .await?;
+
+        username_history::Entity::delete_many()
+            .filter(username_history::Column::UserId.eq(id))
+            .exec(&tx)
+            .await?;
+        email_change::Entity::delete_by_id(id).exec(&tx).await?;
+        user_preference::Entity::delete_by_id(id).exec(&tx).await?;
+
+        // Archives on disk are removed by the caller; see `ExportAssembler::user_dir`
+        entities::user_export::Entity::delete_many()
+            .filter(entities::user_export::Column::UserId.eq(id))
+            .exec(&tx)
+            .await?;
+
+        outbox::insert_outbox_event(
+            &tx,
+            "user",
+            id,
+            "user_deleted",
+            serde_json::json!({
+                "id": id,
+                "deleted_at": deleted_at,
+                "erasure_id": erasure.id,
+            }),
+        )
+        .await?;
+
+        let erasure = super::erasures::with_steps(&tx, erasure).await?;
+        tx.commit().await?;
+        Ok(erasure)
+    }
+
+    async fn list_users_by_status(
+        &self,
+        status: UserStatus,
+        pagination: &Pagination,
+    ) -> Result<(Vec<User>, u64)> {
+        use entities::user::Column;
+
+        let paginator = Self::live()
+            .filter(Column::Status.eq(status))
+            .order_by_desc(Column::UpdatedAt)
+            .order_by_asc(Column::Id)
+            .paginate(&self.conn, pagination.page_size);
+
+        let total_users = paginator.num_items().await?;
+        let users = paginator.fetch_page(pagination.page - 1).await?;
+
+        Ok((users, total_users))
+    }
+
+    async fn change_status(&self, id: Uuid, change: StatusChange) -> Result<User> {
+        let tx = self.conn.begin().await?;
+
+        let current = Self::live()
+            .filter(entities::user::Column::Id.eq(id))
+            .lock_exclusive()
+            .one(&tx)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError("User not found".into()))?;
+
+        let unchanged = current.status == change.status
+            && current.status_reason == change.reason
+            // Postgres keeps microseconds
+            && current.status_expires_at.map(|at| at.timestamp_micros())
+                == change.expires_at.map(|at| at.timestamp_micros());
+        if unchanged {
+            return Ok(current);
+        }
+
+        let user = entities::user::ActiveModel {
+            id: Unchanged(id),
+            status: Set(change.status),
+            status_reason: Set(change.reason),
+            status_expires_at: Set(change.expires_at.map(Into::into)),
+            version: Set(current.version + 1),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        }
+        .update(&tx)
+        .await?;
+        Self::insert_status_changed_event(&tx, current.status, &user, false).await?;
+
+        tx.commit().await?;
+        Ok(user)
+    }
+
+    async fn reactivate_expired(&self, now: DateTime<Utc>) -> Result<Vec<User>> {
+        use entities::user::Column;
+
+        let tx = self.conn.begin().await?;
+
+        // Skip rows another instance is already reactivating
+        let lapsed = entities::user::Entity::find()
+            .filter(Column::Status.eq(UserStatus::Suspended))
+            .filter(Column::StatusExpiresAt.lte(now))
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .all(&tx)
+            .await?;
+
+        let mut users = Vec::with_capacity(lapsed.len());
+        for current in lapsed {
+            let user = entities::user::ActiveModel {
+                id: Unchanged(current.id),
+                status: Set(UserStatus::Active),
+                status_reason: Set(None),
+                status_expires_at: Set(None),
+                version: Set(current.version + 1),
+                updated_at: Set(now.into()),
+                ..Default::default()
+            }
+            .update(&tx)
+            .await?;
+            Self::insert_status_changed_event(&tx, current.status, &user, true).await?;
+            users.push(user);
+        }
+
+        tx.commit().await?;
+        Ok(users)
+    }
+
+    async fn purge_deleted_users(
+        &self,
+        before: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>> {
+        use entities::user::Column;
+
+        // Erased users keep their row, so skip the ones already on their way out
+        let ids: Vec<Uuid> = entities::user::Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::DeletedAt.lt(before))
+            .filter(
+                Column::Id.not_in_subquery(
+                    sea_query::Query::select()
+                        .column(user_erasure::Column::UserId)
+                        .from(user_erasure::Entity)
+                        .to_owned(),
+                ),
+            )
+            .into_tuple()
+            .all(&self.conn)
+            .await?;
+
+        for &id in &ids {
+            self.erase_user(id, deadline).await?;
+        }
+
+        Ok(ids)
+    }
+
+    async fn search_users(
+        &self,
+        query: &str,
+        pagination: &Pagination,
+    ) -> Result<(Vec<UserSearchHit>, u64)> {
+        let backend = self.conn.get_database_backend();
+        let prefix = format!("{}%", escape_like(query));
+
+        let total_hits: i64 = self
+            .conn
+            .query_one_raw(Statement::from_sql_and_values(
+                backend,
+                format!("SELECT COUNT(*) AS total FROM users WHERE {SEARCH_MATCHES}"),
+                [query.into(), prefix.clone().into()],
+            ))
+            .await?
+            .map(|row| row.try_get("", "total"))
+            .transpose()?
+            .unwrap_or(0);
+
+        let rows = self
+            .conn
+            .query_all_raw(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "SELECT users.*, \
+                        (username = $1 OR coalesce(lower(display_name), '') = $1) AS exact, \
+                        (username LIKE $2 OR coalesce(lower(display_name), '') LIKE $2) AS prefix, \
+                        greatest(similarity(username, $1), \
+                            coalesce(similarity(lower(display_name), $1), 0)) AS score, \
+                        (SELECT COUNT(*) FROM follows \
+                            JOIN users AS follower ON follower.id = follows.follower_id \
+                            WHERE follows.followee_id = users.id \
+                            AND follower.deleted_at IS NULL) AS followers \
+                     FROM users WHERE {SEARCH_MATCHES} \
+                     ORDER BY exact DESC, prefix DESC, followers DESC, score DESC, username \
+                     LIMIT $3 OFFSET $4"
+                ),
+                [
+                    query.into(),
+                    prefix.into(),
+                    (pagination.page_size as i64).into(),
+                    (((pagination.page - 1) * pagination.page_size) as i64).into(),
+                ],
+            ))
+            .await?;
+
+        let hits = rows
+            .iter()
+            .map(|row| {
+                Ok(UserSearchHit {
+                    user: User::from_query_result(row, "")?,
+                    followers: row.try_get::<i64>("", "followers")? as u64,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok((hits, total_hits as u64))
     }
 
     async fn list_users(&self, pagination: &Pagination) -> Result<(Vec<User>, u64)> {
-        let paginator = entities::user::Entity::find()
+        let paginator = Self::live()
             .order_by_desc(crate::domain::entities::user::Column::CreatedAt)
             .paginate(&self.conn, pagination.page_size);
 
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use common::{error::Result, outbox::OutBoxEvent, pubsub::PubSubSubscriber};
+use uuid::Uuid;
+
+use crate::domain::repository::DynErasureRepository;
+
+/// Records the acknowledgements other services send once they have erased
+/// their copy of a user's data.
+#[derive(Debug, Clone)]
+pub struct ErasureTracker {
+    erasures: DynErasureRepository,
+}
+
+impl ErasureTracker {
+    pub fn new(erasures: DynErasureRepository) -> Self {
+        Self { erasures }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        if (event.aggregate_type.as_str(), event.event_type.as_str())
+            != ("user", "user_erasure_step_completed")
+        {
+            return Ok(());
+        }
+
+        let erasure_id = event.payload["erasure_id"]
+            .as_str()
+            .and_then(|id| id.parse::<Uuid>().ok());
+        let (Some(erasure_id), Some(step)) = (erasure_id, event.payload["step"].as_str()) else {
+            tracing::warn!("Ignoring malformed erasure acknowledgement {}", event.id);
+            return Ok(());
+        };
+
+        self.erasures
+            .complete_step(erasure_id, step, event.payload.clone())
+            .await?;
+        Ok(())
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Erasure tracker started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let tracker = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match tracker.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to track event {}: {:?}", event.id, e);
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start erasure tracker: {}", e);
+            }
+        })
+    }
+}
+
+/// Marks erasures that were not acknowledged in time as timed out, so they
+/// can be looked into and requested again.
+pub struct ErasureMonitor {
+    erasures: DynErasureRepository,
+    interval: Duration,
+}
+
+impl ErasureMonitor {
+    pub fn new(erasures: DynErasureRepository, interval: Duration) -> Self {
+        Self { erasures, interval }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Erasure monitor started");
+            loop {
+                if let Err(e) = self.erasures.expire_erasures(Utc::now()).await {
+                    tracing::error!("Erasure monitor error: {:?}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+}
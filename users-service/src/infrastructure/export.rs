@@ -0,0 +1,261 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use chrono::Utc;
+use common::{
+    error::{AppError, Result},
+    outbox::OutBoxEvent,
+    pagination::Pagination,
+    pubsub::PubSubSubscriber,
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::user_export::ExportStatus,
+    export::{Export, ExportArchive, ExportedFollow},
+    follow::FollowEdge,
+    repository::{DynExportRepository, DynFollowRepository, DynUserRepository},
+};
+
+/// Collects the parts other services send for an export and, once all of them
+/// are in, writes the archive to disk.
+#[derive(Debug, Clone)]
+pub struct ExportAssembler {
+    users: DynUserRepository,
+    follows: DynFollowRepository,
+    exports: DynExportRepository,
+    dir: PathBuf,
+    sources: BTreeMap<String, String>,
+    client: reqwest::Client,
+}
+
+impl ExportAssembler {
+    pub fn new(
+        users: DynUserRepository,
+        follows: DynFollowRepository,
+        exports: DynExportRepository,
+        dir: impl Into<PathBuf>,
+        sources: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            users,
+            follows,
+            exports,
+            dir: dir.into(),
+            sources,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Archives of a user live in their own directory, so erasing the user
+    /// can remove all of them at once.
+    pub fn user_dir(dir: impl Into<PathBuf>, user_id: Uuid) -> PathBuf {
+        dir.into().join(user_id.to_string())
+    }
+
+    /// Removes every archive of the user; a user without any is fine.
+    pub async fn remove_user_dir(dir: impl Into<PathBuf>, user_id: Uuid) -> Result<()> {
+        let exports = Self::user_dir(dir, user_id);
+        match tokio::fs::remove_dir_all(&exports).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalServerError(anyhow::anyhow!(
+                "Failed to remove export archives {}: {}",
+                exports.display(),
+                e
+            ))),
+        }
+    }
+
+    pub async fn handle_event(&self, event: &OutBoxEvent) -> Result<()> {
+        if (event.aggregate_type.as_str(), event.event_type.as_str())
+            != ("user", "user_export_part_ready")
+        {
+            return Ok(());
+        }
+
+        let export_id = event.payload["export_id"]
+            .as_str()
+            .and_then(|id| id.parse::<Uuid>().ok());
+        let (Some(export_id), Some(part), Some(path)) = (
+            export_id,
+            event.payload["part"].as_str(),
+            event.payload["path"].as_str(),
+        ) else {
+            tracing::warn!("Ignoring malformed export part {}", event.id);
+            return Ok(());
+        };
+        let Some(source) = self.sources.get(part) else {
+            tracing::warn!(
+                "Ignoring export part {} from unknown source {}",
+                event.id,
+                part
+            );
+            return Ok(());
+        };
+
+        let Some(data) = self.fetch_part(source, path).await? else {
+            tracing::warn!("Export part {} at {}{} is gone", event.id, source, path);
+            return Ok(());
+        };
+        let Some(export) = self.exports.store_part(export_id, part, data).await? else {
+            return Ok(());
+        };
+        if export.status != ExportStatus::Pending || !export.has_all_parts() {
+            return Ok(());
+        }
+        self.assemble(export).await
+    }
+
+    /// Parts travel by reference, so each one is fetched from the service that
+    /// stored it. `None` when it is no longer there, e.g. after an erasure.
+    async fn fetch_part(&self, source: &str, path: &str) -> Result<Option<serde_json::Value>> {
+        let url = format!("{}{}", source.trim_end_matches('/'), path);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch export part {}", url))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let data = response
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch export part {}", url))?
+            .json()
+            .await
+            .with_context(|| format!("Failed to read export part {}", url))?;
+        Ok(Some(data))
+    }
+
+    async fn assemble(&self, export: Export) -> Result<()> {
+        let Some(profile) = self.users.get_user_by_id(export.user_id).await? else {
+            tracing::warn!(
+                "User {} of export {} is gone, not writing an archive",
+                export.user_id,
+                export.id
+            );
+            return Ok(());
+        };
+
+        let archive = ExportArchive {
+            export_id: export.id,
+            generated_at: Utc::now(),
+            profile,
+            preferences: self.users.get_preferences(export.user_id).await?.document,
+            following: self.all_edges(export.user_id, true).await?,
+            followers: self.all_edges(export.user_id, false).await?,
+            parts: self.exports.export_parts(export.id).await?,
+        };
+
+        let dir = Self::user_dir(&self.dir, export.user_id);
+        let path = dir.join(format!("{}.json", export.id));
+        let partial = dir.join(format!("{}.json.partial", export.id));
+        let contents = serde_json::to_vec_pretty(&archive)?;
+
+        // Written aside and renamed, so a download never sees half an archive
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+        tokio::fs::write(&partial, contents)
+            .await
+            .with_context(|| format!("Failed to write export archive {}", partial.display()))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .with_context(|| format!("Failed to move export archive to {}", path.display()))?;
+
+        self.exports
+            .complete_export(export.id, &path.to_string_lossy())
+            .await?;
+        Ok(())
+    }
+
+    /// Every follow of the user in one direction, walking through the pages.
+    async fn all_edges(&self, user_id: Uuid, following: bool) -> Result<Vec<ExportedFollow>> {
+        let mut edges = Vec::new();
+        let mut pagination = Pagination {
+            page: 1,
+            page_size: common::pagination::MAX_PAGE_SIZE,
+        };
+        loop {
+            let (page, total): (Vec<FollowEdge>, u64) = if following {
+                self.follows.list_following(user_id, &pagination).await?
+            } else {
+                self.follows.list_followers(user_id, &pagination).await?
+            };
+            let last = page.is_empty() || pagination.page * pagination.page_size >= total;
+            edges.extend(page.into_iter().map(ExportedFollow::from));
+            if last {
+                return Ok(edges);
+            }
+            pagination.page += 1;
+        }
+    }
+
+    pub fn spawn(self, subscriber: PubSubSubscriber) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Export assembler started");
+
+            if let Err(e) = subscriber
+                .listen(move |msg| {
+                    let assembler = self.clone();
+                    async move {
+                        let data = String::from_utf8_lossy(&msg.message.data);
+                        let event = match serde_json::from_str::<OutBoxEvent>(&data) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to parse event: {}", e);
+                                let _ = msg.ack().await;
+                                return;
+                            }
+                        };
+
+                        match assembler.handle_event(&event).await {
+                            Ok(()) => {
+                                let _ = msg.ack().await;
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    "Failed to assemble export for event {}: {:?}",
+                                    event.id,
+                                    e
+                                );
+                                let _ = msg.nack().await;
+                            }
+                        }
+                    }
+                })
+                .await
+            {
+                tracing::error!("Failed to start export assembler: {}", e);
+            }
+        })
+    }
+}
+
+/// Marks exports whose parts did not all arrive in time as failed, so their
+/// users hear back and can ask again.
+pub struct ExportMonitor {
+    exports: DynExportRepository,
+    interval: Duration,
+}
+
+impl ExportMonitor {
+    pub fn new(exports: DynExportRepository, interval: Duration) -> Self {
+        Self { exports, interval }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Export monitor started");
+            loop {
+                if let Err(e) = self.exports.expire_exports(Utc::now()).await {
+                    tracing::error!("Export monitor error: {:?}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+}
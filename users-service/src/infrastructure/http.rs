@@ -3,7 +3,7 @@ use std::sync::Arc;
 use axum::Router;
 
 use crate::presentation::{
-    routes::{health::health_check_router, users::users_router},
+    routes::{admin::admin_router, health::health_check_router, users::users_router},
     state::AppState,
 };
 
@@ -13,4 +13,5 @@ pub fn create_router(state: AppState) -> Router {
     Router::new()
         .merge(health_check_router(state.clone()))
         .nest("/users", users_router(state.clone()))
+        .nest("/admin", admin_router(state.clone()))
 }
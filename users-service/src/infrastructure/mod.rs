@@ -1,2 +1,6 @@
 pub mod database;
+pub mod erasure;
+pub mod export;
 pub mod http;
+pub mod purge;
+pub mod suspension;
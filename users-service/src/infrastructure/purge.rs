@@ -0,0 +1,57 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::Utc;
+
+use crate::{domain::repository::DynUserRepository, infrastructure::export::ExportAssembler};
+
+/// Erases users that have been in the trash longer than the retention window,
+/// which also erases their data in the other services.
+pub struct TrashPurger {
+    users: DynUserRepository,
+    retention: chrono::Duration,
+    erasure_timeout: chrono::Duration,
+    export_dir: PathBuf,
+    interval: Duration,
+}
+
+impl TrashPurger {
+    pub fn new(
+        users: DynUserRepository,
+        retention: chrono::Duration,
+        erasure_timeout: chrono::Duration,
+        export_dir: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            users,
+            retention,
+            erasure_timeout,
+            export_dir: export_dir.into(),
+            interval,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Trash purger started");
+            loop {
+                if let Err(e) = self.purge().await {
+                    tracing::error!("Trash purger error: {:?}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+
+    pub async fn purge(&self) -> common::error::Result<Vec<uuid::Uuid>> {
+        let now = Utc::now();
+        let purged = self
+            .users
+            .purge_deleted_users(now - self.retention, now + self.erasure_timeout)
+            .await?;
+        for &id in &purged {
+            ExportAssembler::remove_user_dir(&self.export_dir, id).await?;
+        }
+        Ok(purged)
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::domain::repository::DynUserRepository;
+
+/// Reactivates suspended users once their suspension expires.
+pub struct SuspensionMonitor {
+    users: DynUserRepository,
+    interval: Duration,
+}
+
+impl SuspensionMonitor {
+    pub fn new(users: DynUserRepository, interval: Duration) -> Self {
+        Self { users, interval }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Suspension monitor started");
+            loop {
+                if let Err(e) = self.users.reactivate_expired(Utc::now()).await {
+                    tracing::error!("Suspension monitor error: {:?}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+}
@@ -1,11 +1,16 @@
 use common::{
-    config::get_configuration,
+    config::{PubSubSettings, get_configuration},
+    pubsub::PubSubSubscriber,
     telemetry::{get_subscriber, init_subscriber},
 };
 use users_service::{
     infrastructure::{
         database::{bootstrap::bootstrap_outbox, bootstrap_db, factory::RepoProvider},
+        erasure::{ErasureMonitor, ErasureTracker},
+        export::{ExportAssembler, ExportMonitor},
         http::create_router,
+        purge::TrashPurger,
+        suspension::SuspensionMonitor,
     },
     presentation::state::AppState,
 };
@@ -30,7 +35,58 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let outbox_poller = bootstrap_outbox(conn, &config.pubsub).await?;
     outbox_poller.spawn();
 
-    let state = AppState::new(repo_provider);
+    let purger = TrashPurger::new(
+        repo_provider.users.clone(),
+        config.trash.retention(),
+        config.erasure.timeout(),
+        &config.export.dir,
+        config.trash.purge_interval(),
+    );
+    purger.spawn();
+
+    let monitor = ErasureMonitor::new(
+        repo_provider.erasures.clone(),
+        config.erasure.check_interval(),
+    );
+    monitor.spawn();
+
+    let exports = ExportMonitor::new(
+        repo_provider.exports.clone(),
+        config.export.check_interval(),
+    );
+    exports.spawn();
+
+    let suspensions = SuspensionMonitor::new(
+        repo_provider.users.clone(),
+        config.suspension.check_interval(),
+    );
+    suspensions.spawn();
+
+    if let Some(subscription) = config.erasure.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        ErasureTracker::new(repo_provider.erasures.clone())
+            .spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    if let Some(subscription) = config.export.subscription.clone() {
+        let pubsub = PubSubSettings {
+            subscription: Some(subscription),
+            ..config.pubsub.clone()
+        };
+        ExportAssembler::new(
+            repo_provider.users.clone(),
+            repo_provider.follows.clone(),
+            repo_provider.exports.clone(),
+            &config.export.dir,
+            config.export.sources.clone(),
+        )
+        .spawn(PubSubSubscriber::new(&pubsub).await?);
+    }
+
+    let state = AppState::new(repo_provider, config.erasure.clone(), config.export.clone());
     let router = create_router(state);
 
     tracing::info!("server starting on port: {}...", config.application.port);
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use common::{
+    error::Result,
+    etag::{self, ETagHeader},
+    extract::ValidatedJson,
+    pagination::{PaginatedResponse, Pagination},
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::status::StatusChange,
+    presentation::{
+        handlers::types::{ChangeStatusRequest, ListByStatusQuery, UserResponse},
+        responses::ListUserResponse,
+        state::AppState,
+    },
+};
+
+/// Users with a given status, e.g. everyone currently suspended.
+pub async fn list_users_by_status(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListByStatusQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListUserResponse>> {
+    let pagination = pagination.normalize();
+    let (users, total_users) = state
+        .repos
+        .users
+        .list_users_by_status(query.status, &pagination)
+        .await?;
+
+    let count = users.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        users,
+        count,
+        total_users,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
+
+/// Deactivates, suspends, bans or reactivates a user. Suspensions with an
+/// expiry are lifted automatically once it passes.
+pub async fn change_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<ChangeStatusRequest>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    let change = StatusChange::from(payload);
+    change.check(Utc::now())?;
+
+    let user = state.repos.users.change_status(id, change).await?;
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use common::{
+    error::Result,
+    etag::{self, ETagHeader},
+    extract::ValidatedJson,
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::identity::{self, EMAIL_CHANGE_TTL},
+    presentation::{
+        handlers::types::{
+            EmailChangeRequest, EmailChangeResponse, UserResponse, VerifyEmailChangeRequest,
+        },
+        state::AppState,
+    },
+};
+
+/// Starts moving the user to a new email address. A token is sent to that
+/// address, and the email only changes once it comes back through `verify`.
+pub async fn request_email_change(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<EmailChangeRequest>,
+) -> Result<(StatusCode, Json<EmailChangeResponse>)> {
+    let email = identity::normalize(&payload.email);
+    let expires_at = Utc::now() + EMAIL_CHANGE_TTL;
+    let change = state
+        .repos
+        .users
+        .request_email_change(id, email, expires_at)
+        .await?;
+    Ok((StatusCode::ACCEPTED, Json(change.into())))
+}
+
+pub async fn verify_email_change(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<VerifyEmailChangeRequest>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    let user = state
+        .repos
+        .users
+        .confirm_email_change(id, payload.token.trim().to_string())
+        .await?;
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
+}
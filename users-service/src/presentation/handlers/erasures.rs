@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use common::error::{AppError, Result};
+use uuid::Uuid;
+
+use crate::{
+    infrastructure::export::ExportAssembler,
+    presentation::{handlers::types::ErasureResponse, state::AppState},
+};
+
+/// Scrubs the user and starts erasing their data in the other services. The
+/// request is accepted once the user is scrubbed; poll the erasure for the rest.
+pub async fn erase_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ErasureResponse>)> {
+    let deadline = Utc::now() + state.erasure.timeout();
+    let erasure = state.repos.users.erase_user(id, deadline).await?;
+
+    ExportAssembler::remove_user_dir(&state.export.dir, id).await?;
+    Ok((StatusCode::ACCEPTED, Json(erasure.into())))
+}
+
+pub async fn get_erasure(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ErasureResponse>> {
+    let erasure = state
+        .repos
+        .erasures
+        .get_erasure(id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Erasure not found".to_string()))?;
+    Ok(Json(erasure.into()))
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use common::error::{AppError, Result};
+use uuid::Uuid;
+
+use crate::{
+    domain::{entities::user_export::ExportStatus, export::Export},
+    presentation::{handlers::types::ExportResponse, state::AppState},
+};
+
+async fn find_export(state: &AppState, id: Uuid, export_id: Uuid) -> Result<Export> {
+    state
+        .repos
+        .exports
+        .get_export(id, export_id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("Export not found".to_string()))
+}
+
+/// Starts collecting the user's data from every service. Poll the export
+/// until it is ready, then download the archive; one that failed can be
+/// requested again.
+pub async fn request_export(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ExportResponse>)> {
+    let deadline = Utc::now() + state.export.timeout();
+    let export = state.repos.exports.request_export(id, deadline).await?;
+    Ok((StatusCode::ACCEPTED, Json(export.into())))
+}
+
+pub async fn get_export(
+    State(state): State<Arc<AppState>>,
+    Path((id, export_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ExportResponse>> {
+    Ok(Json(find_export(&state, id, export_id).await?.into()))
+}
+
+pub async fn download_export(
+    State(state): State<Arc<AppState>>,
+    Path((id, export_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response> {
+    let export = find_export(&state, id, export_id).await?;
+    let path = match (export.status, export.archive_path) {
+        (ExportStatus::Ready, Some(path)) => path,
+        _ => {
+            return Err(AppError::ConflictError(
+                "Export is not ready yet".to_string(),
+            ));
+        }
+    };
+
+    let archive = tokio::fs::read(&path).await.map_err(|e| {
+        AppError::InternalServerError(anyhow::anyhow!(
+            "Failed to read export archive {}: {}",
+            path,
+            e
+        ))
+    })?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"export-{}.json\"", export_id),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
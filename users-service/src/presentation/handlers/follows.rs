@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use common::{
+    error::{AppError, Result},
+    extract::ValidatedJson,
+    pagination::{PaginatedResponse, Pagination},
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::entities::user::User,
+    presentation::{
+        handlers::types::{FollowRequest, FollowResponse, PublicProfileResponse},
+        responses::ListFollowResponse,
+        state::AppState,
+    },
+};
+
+async fn find_user(state: &AppState, id: Uuid) -> Result<User> {
+    state
+        .repos
+        .users
+        .get_user_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("User not found".to_string()))
+}
+
+/// The followed user's profile with fresh counts, returned after a change.
+async fn profile_with_counts(state: &AppState, id: Uuid) -> Result<PublicProfileResponse> {
+    let user = find_user(state, id).await?;
+    let counts = state.repos.follows.follow_counts(id).await?;
+    Ok(PublicProfileResponse::from(user).with_counts(counts))
+}
+
+/// Follows `id`; repeating the request leaves the follow in place.
+pub async fn follow_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<FollowRequest>,
+) -> Result<Json<PublicProfileResponse>> {
+    state.repos.follows.follow(payload.follower_id, id).await?;
+    Ok(Json(profile_with_counts(&state, id).await?))
+}
+
+/// Unfollows `id`; unfollowing someone not followed is not an error.
+pub async fn unfollow_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<FollowRequest>,
+) -> Result<Json<PublicProfileResponse>> {
+    state.repos.follows.unfollow(query.follower_id, id).await?;
+    Ok(Json(profile_with_counts(&state, id).await?))
+}
+
+pub async fn list_followers(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListFollowResponse>> {
+    find_user(&state, id).await?;
+
+    let pagination = pagination.normalize();
+    let (edges, total) = state.repos.follows.list_followers(id, &pagination).await?;
+
+    let followers: Vec<FollowResponse> = edges.into_iter().map(FollowResponse::from).collect();
+    let count = followers.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        followers,
+        count,
+        total,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
+
+pub async fn list_following(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListFollowResponse>> {
+    find_user(&state, id).await?;
+
+    let pagination = pagination.normalize();
+    let (edges, total) = state.repos.follows.list_following(id, &pagination).await?;
+
+    let following: Vec<FollowResponse> = edges.into_iter().map(FollowResponse::from).collect();
+    let count = following.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        following,
+        count,
+        total,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
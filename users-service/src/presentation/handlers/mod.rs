@@ -1,4 +1,10 @@
+pub mod admin;
+pub mod email_changes;
+pub mod erasures;
+pub mod exports;
+pub mod follows;
 pub mod health;
+pub mod preferences;
 pub mod types;
 pub mod users;
 
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use common::{
+    error::Result,
+    etag::{self, ETagHeader},
+    extract::ValidatedJson,
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::preferences::Preferences,
+    presentation::{handlers::types::UpdatePreferencesRequest, state::AppState},
+};
+
+pub async fn get_preferences(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(ETagHeader, Json<Preferences>)> {
+    let preferences = state.repos.users.get_preferences(id).await?;
+    Ok((
+        etag::header(preferences.version),
+        Json(preferences.document),
+    ))
+}
+
+/// Replaces the whole document; kinds and channels left out fall back to
+/// their defaults.
+pub async fn update_preferences(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdatePreferencesRequest>,
+) -> Result<(ETagHeader, Json<Preferences>)> {
+    let preferences = state
+        .repos
+        .users
+        .update_preferences(id, payload.try_into()?)
+        .await?;
+    Ok((
+        etag::header(preferences.version),
+        Json(preferences.document),
+    ))
+}
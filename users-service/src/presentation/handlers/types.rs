@@ -1,20 +1,77 @@
+use std::borrow::Cow;
+
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use common::error::AppError;
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
+use validator::{Validate, ValidateUrl, ValidationError, ValidationErrors};
 
-use crate::domain::entities::user::User;
+use crate::domain::{
+    entities::{
+        email_change::EmailChange,
+        user::{SocialLinks, User, UserStatus},
+        user_erasure::ErasureStatus,
+        user_export::ExportStatus,
+    },
+    erasure::{Erasure, ErasureStep},
+    export::{Export, download_path},
+    follow::{FollowCounts, FollowEdge},
+    identity,
+    preferences::{self, Preferences},
+    profile::ProfileUpdate,
+    search::UserSearchHit,
+    status::StatusChange,
+};
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+const MAX_SOCIAL_LINKS: usize = 10;
+/// Most users that can be looked up in one batch request.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Validate)]
 pub struct CreateUserRequest {
+    #[validate(
+        length(
+            min = 3,
+            max = 64,
+            message = "Username must be between 3-64 characters"
+        ),
+        custom(function = "common::extract::not_blank")
+    )]
     pub username: String,
+    #[validate(email(message = "Email must be a valid email address"))]
     pub email: String,
 }
 
+impl CreateUserRequest {
+    /// Puts the username and email in canonical form and validates the result,
+    /// since trimming and normalizing can shorten them.
+    pub fn normalized(self) -> Result<Self, AppError> {
+        let request = Self {
+            username: identity::normalize(&self.username),
+            email: identity::normalize(&self.email),
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
     pub email: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub social_links: SocialLinks,
+    pub status: UserStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_expires_at: Option<DateTime<Utc>>,
+    pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -25,8 +82,395 @@ impl From<User> for UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            display_name: user.display_name,
+            bio: user.bio,
+            avatar_url: user.avatar_url,
+            website: user.website,
+            location: user.location,
+            social_links: user.social_links,
+            status: user.status,
+            status_reason: user.status_reason,
+            status_expires_at: user.status_expires_at.map(Into::into),
+            version: user.version,
             created_at: user.created_at.into(),
             updated_at: user.updated_at.into(),
         }
     }
 }
+
+/// What anyone may see about a user, e.g. on author cards. Leaves out the email.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PublicProfileResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub location: Option<String>,
+    pub social_links: SocialLinks,
+    pub created_at: DateTime<Utc>,
+    /// Only filled in on single-profile responses, not in lists; search
+    /// results carry the follower count they were ranked by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub followers_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub following_count: Option<u64>,
+}
+
+impl PublicProfileResponse {
+    pub fn with_counts(mut self, counts: FollowCounts) -> Self {
+        self.followers_count = Some(counts.followers);
+        self.following_count = Some(counts.following);
+        self
+    }
+}
+
+impl From<User> for PublicProfileResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            bio: user.bio,
+            avatar_url: user.avatar_url,
+            website: user.website,
+            location: user.location,
+            social_links: user.social_links,
+            created_at: user.created_at.into(),
+            followers_count: None,
+            following_count: None,
+        }
+    }
+}
+
+impl From<UserSearchHit> for PublicProfileResponse {
+    fn from(hit: UserSearchHit) -> Self {
+        Self {
+            followers_count: Some(hit.followers),
+            ..hit.user.into()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BatchUsersRequest {
+    #[validate(custom(function = "batch_size"))]
+    pub ids: Vec<Uuid>,
+}
+
+fn batch_size(ids: &[Uuid]) -> Result<(), ValidationError> {
+    if ids.is_empty() || ids.len() > MAX_BATCH_SIZE {
+        return Err(
+            ValidationError::new("length").with_message(Cow::Owned(format!(
+                "Between 1 and {} ids must be given",
+                MAX_BATCH_SIZE
+            ))),
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchUsersQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// Query of `GET /users`; `ids` is a comma-separated list that turns the
+/// listing into a batch lookup.
+#[derive(Debug, Deserialize)]
+pub struct ListUsersQuery {
+    pub ids: Option<String>,
+}
+
+impl ListUsersQuery {
+    pub fn batch(&self) -> Option<Result<BatchUsersRequest, AppError>> {
+        let ids = self.ids.as_deref()?;
+        let ids = ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse()
+                    .map_err(|_| AppError::ValidationError(format!("Invalid user id: {}", id)))
+            })
+            .collect::<Result<_, _>>();
+        Some(ids.map(|ids| BatchUsersRequest { ids }))
+    }
+}
+
+/// Users found by a batch lookup in the order they were asked for, plus the
+/// ids that matched no live user.
+#[derive(Debug, Serialize)]
+pub struct BatchUsersResponse {
+    pub data: Vec<PublicProfileResponse>,
+    pub missing: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct FollowRequest {
+    pub follower_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FollowResponse {
+    pub user: PublicProfileResponse,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<FollowEdge> for FollowResponse {
+    fn from(edge: FollowEdge) -> Self {
+        Self {
+            user: edge.user.into(),
+            followed_at: edge.followed_at,
+        }
+    }
+}
+
+/// Body of `PATCH /users/{id}/profile`. Omitted fields are left alone, while
+/// `null` or an empty string clears a field.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateProfileRequest {
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(max = 100, message = "Display name cannot exceed 100 characters"))]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(max = 1000, message = "Bio cannot exceed 1000 characters"))]
+    pub bio: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(custom(function = "http_url"))]
+    pub avatar_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(custom(function = "http_url"))]
+    pub website: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[validate(length(max = 100, message = "Location cannot exceed 100 characters"))]
+    pub location: Option<Option<String>>,
+    #[validate(custom(function = "validate_social_links"))]
+    pub social_links: Option<SocialLinks>,
+}
+
+impl From<UpdateProfileRequest> for ProfileUpdate {
+    fn from(request: UpdateProfileRequest) -> Self {
+        fn clean(field: Option<Option<String>>) -> Option<Option<String>> {
+            field.map(|value| {
+                value
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            })
+        }
+
+        Self {
+            display_name: clean(request.display_name),
+            bio: clean(request.bio),
+            avatar_url: clean(request.avatar_url),
+            website: clean(request.website),
+            location: clean(request.location),
+            social_links: request.social_links,
+        }
+    }
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Accepts empty strings (which clear the field) and absolute http(s) URLs,
+/// so profiles cannot carry `javascript:` or other schemes into pages.
+fn http_url(value: &str) -> Result<(), ValidationError> {
+    let value = value.trim();
+    let is_http = value.starts_with("https://") || value.starts_with("http://");
+    if value.is_empty() || (is_http && value.validate_url()) {
+        return Ok(());
+    }
+    Err(ValidationError::new("url").with_message(Cow::Borrowed("Must be an http(s) URL")))
+}
+
+fn validate_social_links(links: &SocialLinks) -> Result<(), ValidationError> {
+    if links.0.len() > MAX_SOCIAL_LINKS {
+        return Err(
+            ValidationError::new("social_links").with_message(Cow::Owned(format!(
+                "At most {} social links are allowed",
+                MAX_SOCIAL_LINKS
+            ))),
+        );
+    }
+
+    for (label, url) in &links.0 {
+        let valid_label = (1..=32).contains(&label.len())
+            && label
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if !valid_label {
+            return Err(
+                ValidationError::new("social_links").with_message(Cow::Borrowed(
+                    "Link labels must be 1-32 lowercase letters, digits, '-' or '_'",
+                )),
+            );
+        }
+        if url.trim().is_empty() {
+            return Err(ValidationError::new("social_links")
+                .with_message(Cow::Borrowed("Links cannot be empty")));
+        }
+        http_url(url)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErasureResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: ErasureStatus,
+    pub requested_at: DateTime<Utc>,
+    pub deadline_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps: Vec<ErasureStepResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErasureStepResponse {
+    pub step: String,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<Erasure> for ErasureResponse {
+    fn from(erasure: Erasure) -> Self {
+        Self {
+            id: erasure.id,
+            user_id: erasure.user_id,
+            status: erasure.status,
+            requested_at: erasure.requested_at,
+            deadline_at: erasure.deadline_at,
+            completed_at: erasure.completed_at,
+            steps: erasure.steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ErasureStep> for ErasureStepResponse {
+    fn from(step: ErasureStep) -> Self {
+        Self {
+            step: step.step,
+            completed_at: step.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: ExportStatus,
+    pub requested_at: DateTime<Utc>,
+    /// When the export fails unless every part has arrived.
+    pub deadline_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Parts the other services have sent so far.
+    pub parts: Vec<String>,
+    /// Set once the archive is ready.
+    pub download_url: Option<String>,
+}
+
+impl From<Export> for ExportResponse {
+    fn from(export: Export) -> Self {
+        Self {
+            download_url: (export.status == ExportStatus::Ready)
+                .then(|| download_path(export.user_id, export.id)),
+            id: export.id,
+            user_id: export.user_id,
+            status: export.status,
+            requested_at: export.requested_at,
+            deadline_at: export.deadline_at,
+            completed_at: export.completed_at,
+            parts: export.parts,
+        }
+    }
+}
+
+/// Body of `POST /users/{id}/email-change`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct EmailChangeRequest {
+    #[validate(email(message = "Email must be a valid email address"))]
+    pub email: String,
+}
+
+/// Body of `POST /users/{id}/email-change/verify`, with the token sent to the
+/// new address.
+#[derive(Debug, Deserialize, Validate)]
+pub struct VerifyEmailChangeRequest {
+    #[validate(custom(function = "common::extract::not_blank"))]
+    pub token: String,
+}
+
+/// A pending email change. The token is only ever sent to the new address.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailChangeResponse {
+    pub new_email: String,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<EmailChange> for EmailChangeResponse {
+    fn from(change: EmailChange) -> Self {
+        Self {
+            new_email: change.new_email,
+            requested_at: change.requested_at.into(),
+            expires_at: change.expires_at.into(),
+        }
+    }
+}
+
+/// Body of `PUT /admin/users/{id}/status`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeStatusRequest {
+    pub status: UserStatus,
+    #[validate(length(max = 500, message = "Reason cannot exceed 500 characters"))]
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<ChangeStatusRequest> for StatusChange {
+    fn from(request: ChangeStatusRequest) -> Self {
+        Self {
+            status: request.status,
+            reason: request
+                .reason
+                .map(|reason| reason.trim().to_string())
+                .filter(|reason| !reason.is_empty()),
+            expires_at: request.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListByStatusQuery {
+    pub status: UserStatus,
+}
+
+/// Body of `PUT /users/{id}/preferences`, validated against
+/// [`preferences::PREFERENCES_SCHEMA`] before it is read.
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub struct UpdatePreferencesRequest(pub serde_json::Value);
+
+impl Validate for UpdatePreferencesRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        preferences::check_document(&self.0)
+    }
+}
+
+impl TryFrom<UpdatePreferencesRequest> for Preferences {
+    type Error = AppError;
+
+    fn try_from(request: UpdatePreferencesRequest) -> Result<Self, Self::Error> {
+        Preferences::from_document(request.0)
+            .map_err(|e| AppError::ValidationError(format!("Invalid preferences: {}", e)))
+    }
+}
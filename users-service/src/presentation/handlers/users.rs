@@ -1,20 +1,33 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use axum::{
     Json,
     extract::{Path, Query, State},
+    response::{IntoResponse, Redirect, Response},
 };
 use common::{
     error::{AppError, Result},
+    etag::{self, ETagHeader, IfMatch},
+    extract::ValidatedJson,
     pagination::{PaginatedResponse, Pagination},
 };
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    domain::entities::user::User,
+    domain::{entities::user::User, identity, search::MAX_QUERY_LENGTH},
     presentation::{
-        handlers::{CreateUserRequest, types::UserResponse},
-        responses::ListUserResponse,
+        handlers::{
+            CreateUserRequest,
+            types::{
+                BatchUsersRequest, BatchUsersResponse, ListUsersQuery, PublicProfileResponse,
+                SearchUsersQuery, UpdateProfileRequest, UserResponse,
+            },
+        },
+        responses::{ListUserResponse, ListUserSearchResponse},
         state::AppState,
     },
 };
@@ -22,7 +35,14 @@ use crate::{
 pub async fn list_users(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<Pagination>,
-) -> Result<Json<ListUserResponse>> {
+    Query(query): Query<ListUsersQuery>,
+) -> Result<Response> {
+    if let Some(request) = query.batch() {
+        let request = request?;
+        request.validate()?;
+        return Ok(Json(batch_users(&state, &request.ids).await?).into_response());
+    }
+
     let pagination = pagination.normalize();
     let (users, total_users): (Vec<User>, u64) = state.repos.users.list_users(&pagination).await?;
 
@@ -34,43 +54,148 @@ pub async fn list_users(
         pagination.page,
         pagination.page_size,
     );
-    Ok(Json(paginated_response))
+    Ok(Json::<ListUserResponse>(paginated_response).into_response())
+}
+
+/// Directory search over usernames and display names, e.g. for @-mention
+/// autocomplete. Only public profile fields are returned.
+pub async fn search_users(
+    State(state): State<Arc<AppState>>,
+    Query(search): Query<SearchUsersQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListUserSearchResponse>> {
+    let query = identity::normalize(&search.q);
+    if query.is_empty() {
+        return Err(AppError::ValidationError(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Search query must be at most {} characters",
+            MAX_QUERY_LENGTH
+        )));
+    }
+
+    let pagination = pagination.normalize();
+    let (hits, total_hits) = state.repos.users.search_users(&query, &pagination).await?;
+
+    let hits: Vec<PublicProfileResponse> = hits.into_iter().map(Into::into).collect();
+    let count = hits.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        hits,
+        count,
+        total_hits,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
+
+pub async fn get_users_batch(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(payload): ValidatedJson<BatchUsersRequest>,
+) -> Result<Json<BatchUsersResponse>> {
+    Ok(Json(batch_users(&state, &payload.ids).await?))
+}
+
+async fn batch_users(state: &AppState, ids: &[Uuid]) -> Result<BatchUsersResponse> {
+    let mut seen = HashSet::new();
+    let ids: Vec<Uuid> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+
+    let mut found: HashMap<Uuid, User> = state
+        .repos
+        .users
+        .get_users_by_ids(&ids)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+
+    let mut response = BatchUsersResponse {
+        data: Vec::with_capacity(found.len()),
+        missing: Vec::new(),
+    };
+    for id in ids {
+        match found.remove(&id) {
+            Some(user) => response.data.push(user.into()),
+            None => response.missing.push(id),
+        }
+    }
+    Ok(response)
 }
 
 pub async fn create_user(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<CreateUserRequest>,
-) -> Result<Json<UserResponse>> {
+    ValidatedJson(payload): ValidatedJson<CreateUserRequest>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    let payload = payload.normalized()?;
     let user = User {
         id: Uuid::new_v4(),
         username: payload.username,
         email: payload.email,
+        display_name: None,
+        bio: None,
+        avatar_url: None,
+        website: None,
+        location: None,
+        social_links: Default::default(),
+        status: Default::default(),
+        status_reason: None,
+        status_expires_at: None,
+        version: 1,
         created_at: chrono::Utc::now().into(),
         updated_at: chrono::Utc::now().into(),
+        deleted_at: None,
     };
 
     let user = state.repos.users.create_user(user).await?;
-    Ok(Json(UserResponse::from(user)))
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
 }
 
 pub async fn get_user_by_id(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<User>> {
+) -> Result<(ETagHeader, Json<User>)> {
     let user = state.repos.users.get_user_by_id(id).await?;
     match user {
-        Some(u) => Ok(Json(u)),
+        Some(u) => Ok((etag::header(u.version), Json(u))),
+        None => Err(AppError::NotFoundError("User not found".to_string())),
+    }
+}
+
+/// Looks a user up by username. A name the user has since given up redirects
+/// to their current one, so old profile links keep working.
+pub async fn get_user_by_username(
+    State(state): State<Arc<AppState>>,
+    Path(username): Path<String>,
+) -> Result<Response> {
+    let username = identity::normalize(&username);
+    if let Some(user) = state.repos.users.get_user_by_name(username.clone()).await? {
+        return Ok((etag::header(user.version), Json(UserResponse::from(user))).into_response());
+    }
+
+    match state.repos.users.get_user_by_former_name(username).await? {
+        Some(user) => Ok(Redirect::permanent(&format!(
+            "/users/by-username/{}",
+            urlencoding::encode(&user.username)
+        ))
+        .into_response()),
         None => Err(AppError::NotFoundError("User not found".to_string())),
     }
 }
 
 pub async fn update_user(
     State(state): State<Arc<AppState>>,
-    Path(_id): Path<Uuid>,
-    Json(user): Json<User>,
-) -> Result<Json<()>> {
-    state.repos.users.update_user(user).await?;
-    Ok(Json(()))
+    Path(id): Path<Uuid>,
+    if_match: IfMatch,
+    ValidatedJson(mut user): ValidatedJson<User>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    user.id = id;
+    user.username = identity::normalize(&user.username);
+    user.email = identity::normalize(&user.email);
+    user.validate()?;
+    let (user, _) = state.repos.users.update_user(user, &if_match).await?;
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
 }
 
 pub async fn delete_user(
@@ -80,3 +205,54 @@ pub async fn delete_user(
     state.repos.users.delete_user(id).await?;
     Ok(Json(()))
 }
+
+pub async fn list_trash(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ListUserResponse>> {
+    let pagination = pagination.normalize();
+    let (users, total_users) = state.repos.users.list_deleted_users(&pagination).await?;
+
+    let count = users.len() as u64;
+    Ok(Json(PaginatedResponse::new(
+        users,
+        count,
+        total_users,
+        pagination.page,
+        pagination.page_size,
+    )))
+}
+
+pub async fn restore_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    let user = state.repos.users.restore_user(id).await?;
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
+}
+
+pub async fn get_profile(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<(ETagHeader, Json<PublicProfileResponse>)> {
+    let user = state
+        .repos
+        .users
+        .get_user_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError("User not found".to_string()))?;
+    let counts = state.repos.follows.follow_counts(id).await?;
+    Ok((
+        etag::header(user.version),
+        Json(PublicProfileResponse::from(user).with_counts(counts)),
+    ))
+}
+
+pub async fn update_profile(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateProfileRequest>,
+) -> Result<(ETagHeader, Json<UserResponse>)> {
+    let user = state.repos.users.update_profile(id, payload.into()).await?;
+    Ok((etag::header(user.version), Json(UserResponse::from(user))))
+}
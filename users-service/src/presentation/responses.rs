@@ -1,5 +1,10 @@
 use common::pagination::PaginatedResponse;
 
-use crate::domain::entities::user::User;
+use crate::{
+    domain::entities::user::User,
+    presentation::handlers::types::{FollowResponse, PublicProfileResponse},
+};
 
 pub type ListUserResponse = PaginatedResponse<User>;
+pub type ListUserSearchResponse = PaginatedResponse<PublicProfileResponse>;
+pub type ListFollowResponse = PaginatedResponse<FollowResponse>;
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{get, put},
+};
+
+use crate::presentation::{
+    handlers::admin::{change_status, list_users_by_status},
+    state::AppState,
+};
+
+pub fn admin_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/users", get(list_users_by_status))
+        .route("/users/{id}/status", put(change_status))
+        .with_state(state)
+}
@@ -2,17 +2,45 @@ use std::sync::Arc;
 
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    routing::{get, post},
 };
 
 use crate::presentation::{
-    handlers::users::{create_user, delete_user, get_user_by_id, list_users, update_user},
+    handlers::email_changes::{request_email_change, verify_email_change},
+    handlers::erasures::{erase_user, get_erasure},
+    handlers::exports::{download_export, get_export, request_export},
+    handlers::follows::{follow_user, list_followers, list_following, unfollow_user},
+    handlers::preferences::{get_preferences, update_preferences},
+    handlers::users::{
+        create_user, delete_user, get_profile, get_user_by_id, get_user_by_username,
+        get_users_batch, list_trash, list_users, restore_user, search_users, update_profile,
+        update_user,
+    },
     state::AppState,
 };
 
 pub fn users_router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", get(list_users).post(create_user))
+        .route("/batch", post(get_users_batch))
+        .route("/trash", get(list_trash))
+        .route("/search", get(search_users))
+        .route("/by-username/{username}", get(get_user_by_username))
+        .route("/{id}/restore", post(restore_user))
+        .route("/{id}/email-change", post(request_email_change))
+        .route("/{id}/email-change/verify", post(verify_email_change))
+        .route("/{id}/erasure", get(get_erasure).post(erase_user))
+        .route("/{id}/export", post(request_export))
+        .route("/{id}/export/{export_id}", get(get_export))
+        .route("/{id}/export/{export_id}/download", get(download_export))
+        .route("/{id}/profile", get(get_profile).patch(update_profile))
+        .route(
+            "/{id}/preferences",
+            get(get_preferences).put(update_preferences),
+        )
+        .route("/{id}/follow", post(follow_user).delete(unfollow_user))
+        .route("/{id}/followers", get(list_followers))
+        .route("/{id}/following", get(list_following))
         .route(
             "/{id}",
             get(get_user_by_id).put(update_user).delete(delete_user),
@@ -1,12 +1,20 @@
+use common::config::{ErasureSettings, ExportSettings};
+
 use crate::infrastructure::database::factory::RepoProvider;
 
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub repos: RepoProvider,
+    pub erasure: ErasureSettings,
+    pub export: ExportSettings,
 }
 
 impl AppState {
-    pub fn new(repos: RepoProvider) -> Self {
-        Self { repos }
+    pub fn new(repos: RepoProvider, erasure: ErasureSettings, export: ExportSettings) -> Self {
+        Self {
+            repos,
+            erasure,
+            export,
+        }
     }
 }
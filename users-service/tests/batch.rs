@@ -0,0 +1,119 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+async fn batch_ids(response: reqwest::Response) -> (Vec<Uuid>, Vec<Uuid>) {
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let ids = |field: &str| -> Vec<Uuid> {
+        body[field]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| {
+                let id = value.get("id").unwrap_or(value);
+                id.as_str().unwrap().parse().unwrap()
+            })
+            .collect()
+    };
+    (ids("data"), ids("missing"))
+}
+
+#[tokio::test]
+async fn batch_returns_users_in_requested_order() {
+    let app = common::spawn_app().await;
+    let first = create_user(&app).await;
+    let second = create_user(&app).await;
+    let unknown = Uuid::new_v4();
+
+    let response = app
+        .batch_users(&serde_json::json!({ "ids": [second.id, unknown, first.id, second.id] }))
+        .await;
+    let (found, missing) = batch_ids(response).await;
+    assert_eq!(found, vec![second.id, first.id]);
+    assert_eq!(missing, vec![unknown]);
+
+    // Served from the cache the second time round
+    let response = app
+        .batch_users(&serde_json::json!({ "ids": [first.id, second.id] }))
+        .await;
+    assert_eq!(batch_ids(response).await.0, vec![first.id, second.id]);
+}
+
+#[tokio::test]
+async fn batch_returns_public_profiles_only() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app
+        .batch_users(&serde_json::json!({ "ids": [user.id] }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["data"][0]["username"], user.username);
+    assert!(body["data"][0].get("email").is_none());
+}
+
+#[tokio::test]
+async fn batch_leaves_out_deleted_users() {
+    let app = common::spawn_app().await;
+    let kept = create_user(&app).await;
+    let deleted = create_user(&app).await;
+    // Warm the cache so the deletion has to evict the entry
+    app.batch_users(&serde_json::json!({ "ids": [kept.id, deleted.id] }))
+        .await;
+    assert_eq!(app.delete_user(deleted.id).await.status(), 200);
+
+    let response = app
+        .batch_users(&serde_json::json!({ "ids": [kept.id, deleted.id] }))
+        .await;
+    let (found, missing) = batch_ids(response).await;
+    assert_eq!(found, vec![kept.id]);
+    assert_eq!(missing, vec![deleted.id]);
+}
+
+#[tokio::test]
+async fn list_users_accepts_ids() {
+    let app = common::spawn_app().await;
+    let first = create_user(&app).await;
+    let second = create_user(&app).await;
+
+    let response = app
+        .list_users_by_ids(&format!("{},{}", first.id, second.id))
+        .await;
+    assert_eq!(batch_ids(response).await.0, vec![first.id, second.id]);
+
+    let response = app.list_users_by_ids("not-a-uuid").await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn batch_rejects_empty_and_oversized_requests() {
+    let app = common::spawn_app().await;
+
+    let response = app.batch_users(&serde_json::json!({ "ids": [] })).await;
+    assert_eq!(response.status(), 400);
+
+    let ids: Vec<Uuid> = (0..101).map(|_| Uuid::new_v4()).collect();
+    let response = app.batch_users(&serde_json::json!({ "ids": ids })).await;
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["fields"]["ids"].is_array());
+    assert!(
+        body.to_string()
+            .contains("Between 1 and 100 ids must be given")
+    );
+}
@@ -1,12 +1,16 @@
-use std::sync::LazyLock;
+#![allow(dead_code)]
+
+use std::{collections::BTreeMap, path::PathBuf, sync::LazyLock};
 
 use anyhow::Context;
 use common::telemetry;
 use tracing::info;
 use users_service::{
     infrastructure::{
-        database::{RepoProvider, bootstrap_db, build_db_url},
+        database::{RepoProvider, bootstrap_db, build_db_url, seaorm::SeaOrmUserRepository},
+        export::ExportAssembler,
         http::create_router,
+        purge::TrashPurger,
     },
     presentation::state::AppState,
 };
@@ -24,10 +28,14 @@ pub struct TestApp {
     pub db_name: String,
     pub db_config: common::config::DatabaseSettings,
     pub api_client: reqwest::Client,
+    pub export_dir: PathBuf,
+    pub export_sources: BTreeMap<String, String>,
 }
 
 impl Drop for TestApp {
     fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.export_dir);
+
         let db_config = self.db_config.clone();
         let db_name = self.db_name.clone();
         std::thread::spawn(move || {
@@ -53,6 +61,14 @@ pub async fn spawn_app() -> TestApp {
         common::config::get_configuration::<common::config::Settings>("config").unwrap();
     // Randomize database name
     config.database.database_name = Uuid::new_v4().to_string();
+    // Apps share the test Redis, so keep their cache entries apart
+    if let Some(redis) = config.cache.redis.as_mut() {
+        redis.key_prefix = format!("{}:", config.database.database_name);
+    }
+    let export_dir =
+        std::env::temp_dir().join(format!("users-exports-{}", config.database.database_name));
+    config.export.dir = export_dir.to_string_lossy().into_owned();
+    config.export.sources = spawn_export_sources(&config.application.host).await;
 
     configure_database(&config.database).await;
 
@@ -68,7 +84,11 @@ pub async fn spawn_app() -> TestApp {
     let repo_provider = RepoProvider::from_connection(conn, &config.cache)
         .await
         .unwrap();
-    let state = AppState::new(repo_provider.clone());
+    let state = AppState::new(
+        repo_provider.clone(),
+        config.erasure.clone(),
+        config.export.clone(),
+    );
     let router = create_router(state);
 
     tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
@@ -84,9 +104,34 @@ pub async fn spawn_app() -> TestApp {
         db_name: config.database.database_name.clone(),
         db_config: config.database.clone(),
         api_client: client,
+        export_dir,
+        export_sources: config.export.sources.clone(),
     }
 }
 
+/// Stands in for the services export parts are fetched from: every part is
+/// served as `{"source": <part>}` under `/{part}/exports/{export_id}`.
+async fn spawn_export_sources(host: &str) -> BTreeMap<String, String> {
+    use axum::{Json, Router, extract::Path, routing::get};
+
+    let listener = tokio::net::TcpListener::bind(format!("{}:0", host))
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = Router::new().route(
+        "/{part}/exports/{export_id}",
+        get(|Path((part, _)): Path<(String, Uuid)>| async move {
+            Json(serde_json::json!({ "source": part }))
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    users_service::domain::export::EXPORT_PARTS
+        .iter()
+        .map(|part| (part.to_string(), format!("http://{}/{}", addr, part)))
+        .collect()
+}
+
 async fn configure_database(config: &common::config::DatabaseSettings) {
     use sea_orm::{ConnectionTrait, Database};
 
@@ -172,8 +217,27 @@ pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
     pub email: String,
+    pub version: i32,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListUsersResponse {
+    pub data: Vec<UserResponse>,
+    pub total: u64,
+}
+
+pub fn etag(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .expect("Missing ETag header")
+        .to_str()
+        .unwrap()
+        .to_string()
 }
 
 impl TestApp {
@@ -202,13 +266,36 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn batch_users<T: Serialize + ?Sized>(&self, body: &T) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/batch", self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_users_by_ids(&self, ids: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users?ids={}", self.address, ids))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn update_user<T: Serialize + ?Sized>(
         &self,
         id: Uuid,
         body: &T,
+        if_match: Option<&str>,
     ) -> reqwest::Response {
-        self.api_client
-            .put(format!("http://{}/users/{}", self.address, id))
+        let mut request = self
+            .api_client
+            .put(format!("http://{}/users/{}", self.address, id));
+        if let Some(if_match) = if_match {
+            request = request.header(reqwest::header::IF_MATCH, if_match);
+        }
+        request
             .json(body)
             .send()
             .await
@@ -222,4 +309,246 @@ impl TestApp {
             .await
             .expect("Failed to execute request.")
     }
+
+    pub async fn list_trash(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/trash", self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn restore_user(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/{}/restore", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_profile(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/{}/profile", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_user_by_username(&self, username: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/users/by-username/{}",
+                self.address,
+                urlencoding::encode(username)
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn request_email_change(&self, id: Uuid, email: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/{}/email-change", self.address, id))
+            .json(&serde_json::json!({ "email": email }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn verify_email_change(&self, id: Uuid, token: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!(
+                "http://{}/users/{}/email-change/verify",
+                self.address, id
+            ))
+            .json(&serde_json::json!({ "token": token }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn search_users(&self, query: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/users/search?q={}",
+                self.address,
+                urlencoding::encode(query)
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn change_status(&self, id: Uuid, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .put(format!("http://{}/admin/users/{}/status", self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_users_by_status(&self, status: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/admin/users?status={}",
+                self.address, status
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn patch_profile(&self, id: Uuid, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .patch(format!("http://{}/users/{}/profile", self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_preferences(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/{}/preferences", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn put_preferences(&self, id: Uuid, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .put(format!("http://{}/users/{}/preferences", self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Writes straight to Postgres, bypassing the cache layer.
+    pub async fn uncached_users(&self) -> SeaOrmUserRepository {
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        SeaOrmUserRepository::new(bootstrap_db(&db_config).await.unwrap())
+    }
+
+    pub async fn outbox_payloads(&self, event_type: &str) -> Vec<serde_json::Value> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+        let mut db_config = self.db_config.clone();
+        db_config.database_name = self.db_name.clone();
+        let conn = bootstrap_db(&db_config).await.unwrap();
+
+        common::outbox::Entity::find()
+            .filter(common::outbox::Column::EventType.eq(event_type))
+            .order_by_asc(common::outbox::Column::CreatedAt)
+            .all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|event| event.payload)
+            .collect()
+    }
+
+    pub async fn follow(&self, id: Uuid, follower_id: Uuid) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/{}/follow", self.address, id))
+            .json(&serde_json::json!({ "follower_id": follower_id }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn unfollow(&self, id: Uuid, follower_id: Uuid) -> reqwest::Response {
+        self.api_client
+            .delete(format!(
+                "http://{}/users/{}/follow?follower_id={}",
+                self.address, id, follower_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_followers(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/{}/followers", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_following(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/{}/following", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn erase_user(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/{}/erasure", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_erasure(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .get(format!("http://{}/users/{}/erasure", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn request_export(&self, id: Uuid) -> reqwest::Response {
+        self.api_client
+            .post(format!("http://{}/users/{}/export", self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_export(&self, id: Uuid, export_id: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/users/{}/export/{}",
+                self.address, id, export_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn download_export(&self, id: Uuid, export_id: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "http://{}/users/{}/export/{}/download",
+                self.address, id, export_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub fn trash_purger(&self, retention: chrono::Duration) -> TrashPurger {
+        TrashPurger::new(
+            self.repo_provider.users.clone(),
+            retention,
+            chrono::Duration::hours(1),
+            &self.export_dir,
+            std::time::Duration::from_secs(60),
+        )
+    }
+
+    pub fn export_assembler(&self) -> ExportAssembler {
+        ExportAssembler::new(
+            self.repo_provider.users.clone(),
+            self.repo_provider.follows.clone(),
+            self.repo_provider.exports.clone(),
+            &self.export_dir,
+            self.export_sources.clone(),
+        )
+    }
 }
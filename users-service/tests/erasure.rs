@@ -0,0 +1,150 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use users_service::infrastructure::erasure::ErasureTracker;
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+async fn erasure(response: reqwest::Response) -> serde_json::Value {
+    assert!(response.status().is_success());
+    response.json().await.unwrap()
+}
+
+async fn acknowledge(app: &TestApp, erasure_id: &str, user_id: Uuid, step: &str) {
+    let event = ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: "user_erasure_step_completed".to_string(),
+        payload: serde_json::json!({
+            "erasure_id": erasure_id,
+            "user_id": user_id,
+            "step": step,
+        }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    };
+    ErasureTracker::new(app.repo_provider.erasures.clone())
+        .handle_event(&event)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn erasure_scrubs_the_user_and_asks_other_services() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let follower = create_user(&app).await;
+    assert_eq!(app.follow(user.id, follower.id).await.status(), 200);
+
+    let response = app.erase_user(user.id).await;
+    assert_eq!(response.status(), 202);
+    let body = erasure(response).await;
+    assert_eq!(body["status"], "pending");
+    assert_eq!(body["steps"].as_array().unwrap().len(), 2);
+
+    assert_eq!(app.get_user_by_id(user.id).await.status(), 404);
+    let trash: serde_json::Value = app.list_trash().await.json().await.unwrap();
+    let erased = &trash["data"][0];
+    assert_eq!(erased["id"], user.id.to_string());
+    assert_ne!(erased["username"], user.username);
+    assert_ne!(erased["email"], user.email);
+
+    let following: serde_json::Value = app.list_following(follower.id).await.json().await.unwrap();
+    assert_eq!(following["total"], 0);
+
+    let events = app.outbox_payloads("user_deleted").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], user.id.to_string());
+    assert_eq!(events[0]["erasure_id"], body["id"]);
+
+    // Asking again returns the erasure in progress instead of starting another
+    let again = erasure(app.erase_user(user.id).await).await;
+    assert_eq!(again["id"], body["id"]);
+    assert_eq!(app.outbox_payloads("user_deleted").await.len(), 1);
+}
+
+#[tokio::test]
+async fn erasure_completes_once_every_service_acknowledges() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let body = erasure(app.erase_user(user.id).await).await;
+    let erasure_id = body["id"].as_str().unwrap();
+
+    acknowledge(&app, erasure_id, user.id, "posts").await;
+    let status = erasure(app.get_erasure(user.id).await).await;
+    assert_eq!(status["status"], "pending");
+
+    acknowledge(&app, erasure_id, user.id, "notifications").await;
+    let status = erasure(app.get_erasure(user.id).await).await;
+    assert_eq!(status["status"], "completed");
+    assert!(status["completed_at"].is_string());
+    assert!(
+        status["steps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|step| step["completed_at"].is_string())
+    );
+}
+
+#[tokio::test]
+async fn unacknowledged_erasure_times_out_and_can_be_restarted() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let body = erasure(app.erase_user(user.id).await).await;
+    let erasure_id = body["id"].as_str().unwrap();
+    acknowledge(&app, erasure_id, user.id, "posts").await;
+
+    let later = chrono::Utc::now() + chrono::Duration::days(2);
+    let expired = app
+        .repo_provider
+        .erasures
+        .expire_erasures(later)
+        .await
+        .unwrap();
+    assert_eq!(expired.len(), 1);
+    let status = erasure(app.get_erasure(user.id).await).await;
+    assert_eq!(status["status"], "timed_out");
+
+    let restarted = erasure(app.erase_user(user.id).await).await;
+    assert_eq!(restarted["id"], body["id"]);
+    assert_eq!(restarted["status"], "pending");
+    assert_eq!(app.outbox_payloads("user_deleted").await.len(), 2);
+
+    // The step acknowledged before the timeout stays done
+    acknowledge(&app, erasure_id, user.id, "notifications").await;
+    let status = erasure(app.get_erasure(user.id).await).await;
+    assert_eq!(status["status"], "completed");
+}
+
+#[tokio::test]
+async fn erased_users_cannot_be_restored() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    assert_eq!(app.erase_user(user.id).await.status(), 202);
+
+    assert_eq!(app.restore_user(user.id).await.status(), 409);
+}
+
+#[tokio::test]
+async fn erasing_unknown_user_returns_404() {
+    let app = common::spawn_app().await;
+
+    assert_eq!(app.erase_user(Uuid::new_v4()).await.status(), 404);
+    assert_eq!(app.get_erasure(Uuid::new_v4()).await.status(), 404);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+async fn send_part(app: &TestApp, export_id: &str, user_id: Uuid, part: &str) {
+    send_part_at(
+        app,
+        export_id,
+        user_id,
+        part,
+        &format!("/exports/{}", export_id),
+    )
+    .await;
+}
+
+async fn send_part_at(app: &TestApp, export_id: &str, user_id: Uuid, part: &str, path: &str) {
+    let event = ::common::outbox::OutBoxEvent {
+        id: Uuid::new_v4(),
+        aggregate_type: "user".to_string(),
+        aggregate_id: user_id,
+        event_type: "user_export_part_ready".to_string(),
+        payload: serde_json::json!({
+            "export_id": export_id,
+            "user_id": user_id,
+            "part": part,
+            "path": path,
+        }),
+        created_at: chrono::Utc::now().into(),
+        sent_at: None,
+        attempts: 0,
+        last_error: None,
+        dead_lettered_at: None,
+    };
+    app.export_assembler().handle_event(&event).await.unwrap();
+}
+
+async fn export(response: reqwest::Response) -> serde_json::Value {
+    assert!(response.status().is_success());
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn export_is_assembled_once_every_part_arrives() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let follower = create_user(&app).await;
+    assert_eq!(app.follow(user.id, follower.id).await.status(), 200);
+
+    let response = app.request_export(user.id).await;
+    assert_eq!(response.status(), 202);
+    let requested = export(response).await;
+    assert_eq!(requested["status"], "pending");
+    assert!(requested["download_url"].is_null());
+    let export_id = requested["id"].as_str().unwrap().to_string();
+
+    let events = app.outbox_payloads("user_export_requested").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["export_id"], export_id.as_str());
+
+    send_part(&app, &export_id, user.id, "posts").await;
+    let status = export(app.get_export(user.id, &export_id).await).await;
+    assert_eq!(status["status"], "pending");
+    assert_eq!(status["parts"], serde_json::json!(["posts"]));
+    assert_eq!(app.download_export(user.id, &export_id).await.status(), 409);
+
+    send_part(&app, &export_id, user.id, "notifications").await;
+    let status = export(app.get_export(user.id, &export_id).await).await;
+    assert_eq!(status["status"], "ready");
+    let download_url = status["download_url"].as_str().unwrap();
+    assert_eq!(
+        download_url,
+        format!("/users/{}/export/{}/download", user.id, export_id)
+    );
+
+    let ready = app.outbox_payloads("user_export_ready").await;
+    assert_eq!(ready.len(), 1);
+    assert_eq!(ready[0]["download_path"], download_url);
+
+    let response = app.download_export(user.id, &export_id).await;
+    assert_eq!(response.status(), 200);
+    let archive: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(archive["profile"]["email"], user.email.as_str());
+    assert_eq!(archive["followers"][0]["user_id"], follower.id.to_string());
+    assert_eq!(archive["posts"]["source"], "posts");
+    assert_eq!(archive["notifications"]["source"], "notifications");
+}
+
+#[tokio::test]
+async fn redelivered_parts_do_not_finish_an_export_twice() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let requested = export(app.request_export(user.id).await).await;
+    let export_id = requested["id"].as_str().unwrap();
+
+    // A pending export is reused rather than started again
+    let again = export(app.request_export(user.id).await).await;
+    assert_eq!(again["id"], requested["id"]);
+
+    for _ in 0..2 {
+        send_part(&app, export_id, user.id, "posts").await;
+        send_part(&app, export_id, user.id, "notifications").await;
+    }
+    assert_eq!(app.outbox_payloads("user_export_ready").await.len(), 1);
+
+    // Once ready, a new request starts a fresh export
+    let fresh = export(app.request_export(user.id).await).await;
+    assert_ne!(fresh["id"], requested["id"]);
+}
+
+#[tokio::test]
+async fn exports_missing_parts_past_their_deadline_fail() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let requested = export(app.request_export(user.id).await).await;
+    let export_id = requested["id"].as_str().unwrap();
+    send_part(&app, export_id, user.id, "posts").await;
+
+    let exports = &app.repo_provider.exports;
+    assert!(
+        exports
+            .expire_exports(chrono::Utc::now())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let expired = exports
+        .expire_exports(chrono::Utc::now() + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(expired.len(), 1);
+
+    let status = export(app.get_export(user.id, export_id).await).await;
+    assert_eq!(status["status"], "failed");
+    let failed = app.outbox_payloads("user_export_failed").await;
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0]["user_id"], user.id.to_string());
+    assert_eq!(
+        failed[0]["missing_parts"],
+        serde_json::json!(["notifications"])
+    );
+
+    // A part that turns up late does not revive it
+    send_part(&app, export_id, user.id, "notifications").await;
+    let status = export(app.get_export(user.id, export_id).await).await;
+    assert_eq!(status["status"], "failed");
+    assert!(app.outbox_payloads("user_export_ready").await.is_empty());
+
+    let fresh = export(app.request_export(user.id).await).await;
+    assert_ne!(fresh["id"], requested["id"]);
+    assert_eq!(fresh["status"], "pending");
+}
+
+#[tokio::test]
+async fn erasing_a_user_removes_their_exports() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let requested = export(app.request_export(user.id).await).await;
+    let export_id = requested["id"].as_str().unwrap();
+    send_part(&app, export_id, user.id, "posts").await;
+    send_part(&app, export_id, user.id, "notifications").await;
+    let user_dir = app.export_dir.join(user.id.to_string());
+    assert!(user_dir.exists());
+
+    assert_eq!(app.erase_user(user.id).await.status(), 202);
+
+    assert!(!user_dir.exists());
+    assert_eq!(app.get_export(user.id, export_id).await.status(), 404);
+}
+
+#[tokio::test]
+async fn export_of_unknown_user_returns_404() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    assert_eq!(app.request_export(Uuid::new_v4()).await.status(), 404);
+    assert_eq!(
+        app.get_export(user.id, &Uuid::new_v4().to_string())
+            .await
+            .status(),
+        404
+    );
+}
+
+#[tokio::test]
+async fn parts_that_are_gone_are_skipped() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let requested = export(app.request_export(user.id).await).await;
+    let export_id = requested["id"].as_str().unwrap();
+
+    // The source no longer has it, e.g. because the user was erased meanwhile
+    send_part_at(&app, export_id, user.id, "posts", "/gone").await;
+
+    let status = export(app.get_export(user.id, export_id).await).await;
+    assert_eq!(status["status"], "pending");
+    assert_eq!(status["parts"], serde_json::json!([]));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+async fn ids(response: reqwest::Response) -> (Vec<String>, u64) {
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let ids = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|edge| edge["user"]["id"].as_str().unwrap().to_string())
+        .collect();
+    (ids, body["total"].as_u64().unwrap())
+}
+
+#[tokio::test]
+async fn follow_updates_lists_and_counts() {
+    let app = common::spawn_app().await;
+    let author = create_user(&app).await;
+    let first = create_user(&app).await;
+    let second = create_user(&app).await;
+
+    let response = app.follow(author.id, first.id).await;
+    assert_eq!(response.status(), 200);
+    let profile: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(profile["followers_count"], 1);
+
+    app.follow(author.id, second.id).await;
+    app.follow(second.id, first.id).await;
+
+    let (followers, total) = ids(app.list_followers(author.id).await).await;
+    assert_eq!(total, 2);
+    assert_eq!(followers, vec![second.id.to_string(), first.id.to_string()]);
+
+    let (following, total) = ids(app.list_following(first.id).await).await;
+    assert_eq!(total, 2);
+    assert_eq!(
+        following,
+        vec![second.id.to_string(), author.id.to_string()]
+    );
+
+    let profile: serde_json::Value = app.get_profile(first.id).await.json().await.unwrap();
+    assert_eq!(profile["followers_count"], 0);
+    assert_eq!(profile["following_count"], 2);
+}
+
+#[tokio::test]
+async fn follow_is_idempotent_and_emits_one_event() {
+    let app = common::spawn_app().await;
+    let author = create_user(&app).await;
+    let follower = create_user(&app).await;
+
+    assert_eq!(app.follow(author.id, follower.id).await.status(), 200);
+    let response = app.follow(author.id, follower.id).await;
+    let profile: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(profile["followers_count"], 1);
+
+    let events = app.outbox_payloads("user_followed").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["followee_id"], author.id.to_string());
+    assert_eq!(events[0]["follower_id"], follower.id.to_string());
+    assert_eq!(events[0]["follower_username"], follower.username);
+}
+
+#[tokio::test]
+async fn unfollow_removes_the_follow() {
+    let app = common::spawn_app().await;
+    let author = create_user(&app).await;
+    let follower = create_user(&app).await;
+    app.follow(author.id, follower.id).await;
+
+    let response = app.unfollow(author.id, follower.id).await;
+    assert_eq!(response.status(), 200);
+    let profile: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(profile["followers_count"], 0);
+
+    // Unfollowing again is not an error
+    assert_eq!(app.unfollow(author.id, follower.id).await.status(), 200);
+    assert_eq!(ids(app.list_followers(author.id).await).await.1, 0);
+
+    let events = app.outbox_payloads("user_unfollowed").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["follower_id"], follower.id.to_string());
+    assert_eq!(events[0]["followee_id"], author.id.to_string());
+}
+
+#[tokio::test]
+async fn follow_rejects_self_and_unknown_users() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    assert_eq!(app.follow(user.id, user.id).await.status(), 400);
+    assert_eq!(app.follow(Uuid::new_v4(), user.id).await.status(), 404);
+    assert_eq!(app.follow(user.id, Uuid::new_v4()).await.status(), 404);
+    assert_eq!(app.list_followers(Uuid::new_v4()).await.status(), 404);
+}
+
+#[tokio::test]
+async fn trashed_users_drop_out_of_follow_lists() {
+    let app = common::spawn_app().await;
+    let author = create_user(&app).await;
+    let follower = create_user(&app).await;
+    app.follow(author.id, follower.id).await;
+
+    app.delete_user(follower.id).await;
+    assert_eq!(ids(app.list_followers(author.id).await).await.1, 0);
+    let profile: serde_json::Value = app.get_profile(author.id).await.json().await.unwrap();
+    assert_eq!(profile["followers_count"], 0);
+
+    app.restore_user(follower.id).await;
+    assert_eq!(ids(app.list_followers(author.id).await).await.1, 1);
+}
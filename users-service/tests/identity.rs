@@ -0,0 +1,322 @@
+mod common;
+
+use common::{UserRequest, UserResponse};
+use uuid::Uuid;
+
+fn sample_user() -> UserRequest {
+    let id = Uuid::new_v4();
+    UserRequest {
+        username: format!("user_{}", id),
+        email: format!("{}@example.com", id),
+    }
+}
+
+fn update_body(created: &UserResponse, username: &str, email: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": created.id,
+        "username": username,
+        "email": email,
+        "created_at": created.created_at,
+        "updated_at": chrono::Utc::now(),
+    })
+}
+
+#[tokio::test]
+async fn create_user_normalizes_username_and_email() {
+    let app = common::spawn_app().await;
+    let id = Uuid::new_v4().simple().to_string();
+
+    let response = app
+        .post_user(&UserRequest {
+            // Fullwidth letters fold to ASCII under NFKC
+            username: format!("  Ｍｉｘｅｄ_{}  ", &id[..8]),
+            email: format!("{}@Example.COM", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let created: UserResponse = response.json().await.unwrap();
+    assert_eq!(created.username, format!("mixed_{}", &id[..8]));
+    assert_eq!(created.email, format!("{}@example.com", id));
+}
+
+#[tokio::test]
+async fn create_user_rejects_names_and_emails_differing_only_in_case() {
+    let app = common::spawn_app().await;
+    let user = sample_user();
+    assert_eq!(app.post_user(&user).await.status(), 200);
+
+    let response = app
+        .post_user(&UserRequest {
+            username: user.username.to_uppercase(),
+            email: format!("{}@example.com", Uuid::new_v4()),
+        })
+        .await;
+    assert_eq!(response.status(), 409);
+
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", Uuid::new_v4()),
+            email: user.email.to_uppercase(),
+        })
+        .await;
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn create_user_rejects_reserved_usernames() {
+    let app = common::spawn_app().await;
+
+    for username in ["Admin", "  support ", "erased-someone"] {
+        let response = app
+            .post_user(&UserRequest {
+                username: username.to_string(),
+                email: format!("{}@example.com", Uuid::new_v4()),
+            })
+            .await;
+        assert_eq!(response.status(), 400, "{username}");
+    }
+}
+
+#[tokio::test]
+async fn update_user_cannot_change_email_directly() {
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
+
+    let body = update_body(&created, &created.username, "other@example.com");
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 400);
+
+    let fetched: UserResponse = app.get_user_by_id(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.email, created.email);
+    assert_eq!(fetched.version, 1);
+}
+
+#[tokio::test]
+async fn email_change_applies_once_the_new_address_is_verified() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+    let new_email = format!("{}@example.org", Uuid::new_v4());
+
+    let response = app
+        .request_email_change(created.id, &new_email.to_uppercase())
+        .await;
+    assert_eq!(response.status(), 202);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["new_email"], new_email);
+    assert!(body.get("token").is_none());
+
+    // Nothing changes until the token comes back
+    let fetched: UserResponse = app.get_user_by_id(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.email, created.email);
+
+    let events = app.outbox_payloads("user_email_change_requested").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["new_email"], new_email);
+    let token = events[0]["token"].as_str().unwrap().to_string();
+
+    let response = app.verify_email_change(created.id, "not-the-token").await;
+    assert_eq!(response.status(), 400);
+
+    let response = app.verify_email_change(created.id, &token).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"2\"");
+    let updated: UserResponse = response.json().await.unwrap();
+    assert_eq!(updated.email, new_email);
+
+    let events = app.outbox_payloads("user_updated").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["changes"]["email"]["after"], new_email);
+
+    // The token is spent
+    let response = app.verify_email_change(created.id, &token).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn email_change_to_an_address_in_use_returns_409() {
+    let app = common::spawn_app().await;
+    let first: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+    let second: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+
+    let response = app.request_email_change(first.id, &second.email).await;
+    assert_eq!(response.status(), 409);
+
+    let response = app.request_email_change(first.id, &first.email).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn renamed_user_is_found_through_their_former_username() {
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
+
+    let new_name = format!("renamed_{}", created.id);
+    let body = update_body(&created, &new_name, &created.email);
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let response = app.get_user_by_username(&new_name).await;
+    assert_eq!(response.status(), 200);
+    let found: UserResponse = response.json().await.unwrap();
+    assert_eq!(found.id, created.id);
+
+    let response = app
+        .get_user_by_username(&created.username.to_uppercase())
+        .await;
+    assert_eq!(response.status(), 308);
+    assert_eq!(
+        response.headers()[reqwest::header::LOCATION],
+        format!("/users/by-username/{}", new_name).as_str()
+    );
+
+    let response = app.get_user_by_username("nobody_here").await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn renaming_evicts_the_cached_former_username() {
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
+    let response = app.get_user_by_username(&created.username).await;
+    assert_eq!(response.status(), 200);
+
+    // A stale update evicts the user by id, while their name stays cached
+    let new_name = format!("renamed_{}", created.id);
+    let body = update_body(&created, &new_name, &created.email);
+    let response = app.update_user(created.id, &body, Some("\"7\"")).await;
+    assert_eq!(response.status(), 412);
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let response = app.get_user_by_username(&created.username).await;
+    assert_eq!(response.status(), 308);
+    assert_eq!(
+        response.headers()[reqwest::header::LOCATION],
+        format!("/users/by-username/{}", new_name).as_str()
+    );
+}
+
+#[tokio::test]
+async fn former_usernames_stay_with_their_owner() {
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
+
+    let new_name = format!("renamed_{}", created.id);
+    let body = update_body(&created, &new_name, &created.email);
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    let etag = common::etag(&response);
+
+    let response = app
+        .post_user(&UserRequest {
+            username: created.username.clone(),
+            email: format!("{}@example.com", Uuid::new_v4()),
+        })
+        .await;
+    assert_eq!(response.status(), 409);
+
+    // The owner can take it back, after which it no longer redirects
+    let body = update_body(&created, &created.username, &created.email);
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 200);
+
+    let response = app.get_user_by_username(&created.username).await;
+    assert_eq!(response.status(), 200);
+    let response = app.get_user_by_username(&new_name).await;
+    assert_eq!(response.status(), 308);
+}
+
+/// Rolls the test database back to just before identities were normalized and
+/// returns a connection to it.
+async fn before_normalization(app: &common::TestApp) -> sea_orm::DatabaseConnection {
+    use migration::{Migrator, MigratorTrait};
+
+    let url = users_service::infrastructure::database::build_db_url(&app.db_config)
+        .await
+        .unwrap();
+    let conn = sea_orm::Database::connect(&url).await.unwrap();
+    let later = Migrator::migrations().len() as u32 - 8;
+    Migrator::down(&conn, Some(later)).await.unwrap();
+    conn
+}
+
+async fn insert_legacy_user(
+    conn: &sea_orm::DatabaseConnection,
+    username: &str,
+    email: &str,
+    age_days: i32,
+) -> Uuid {
+    use sea_orm::ConnectionTrait;
+
+    let id = Uuid::new_v4();
+    conn.execute_unprepared(&format!(
+        "INSERT INTO users (id, username, email, created_at, updated_at) \
+         VALUES ('{id}', '{username}', '{email}', \
+         now() - interval '{age_days} days', now())"
+    ))
+    .await
+    .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn normalization_renames_newer_accounts_with_colliding_usernames() {
+    use migration::{Migrator, MigratorTrait};
+
+    let app = common::spawn_app().await;
+    let conn = before_normalization(&app).await;
+    let name = format!("legacy_{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let oldest = insert_legacy_user(&conn, &name, "first@example.com", 2).await;
+    let newer = insert_legacy_user(&conn, &name.to_uppercase(), "second@example.com", 1).await;
+
+    Migrator::up(&conn, None).await.unwrap();
+
+    let response = app.get_user_by_id(oldest).await;
+    let user: UserResponse = response.json().await.unwrap();
+    assert_eq!(user.username, name);
+
+    let response = app.get_user_by_id(newer).await;
+    let user: UserResponse = response.json().await.unwrap();
+    let suffix = &newer.simple().to_string()[..8];
+    assert_eq!(user.username, format!("{}-{}", name, suffix));
+
+    use sea_orm::{ConnectionTrait, Statement};
+    let former = conn
+        .query_one_raw(Statement::from_string(
+            conn.get_database_backend(),
+            format!(
+                "SELECT user_id FROM username_history WHERE username = '{}'",
+                name.to_uppercase()
+            ),
+        ))
+        .await
+        .unwrap()
+        .expect("the old name is kept");
+    assert_eq!(former.try_get::<Uuid>("", "user_id").unwrap(), newer);
+}
+
+#[tokio::test]
+async fn normalization_reports_colliding_emails() {
+    use migration::{Migrator, MigratorTrait};
+
+    let app = common::spawn_app().await;
+    let conn = before_normalization(&app).await;
+    let first = insert_legacy_user(&conn, "legacy_first", "Shared@example.com", 2).await;
+    let second = insert_legacy_user(&conn, "legacy_second", "shared@EXAMPLE.com", 1).await;
+
+    let error = Migrator::up(&conn, None).await.unwrap_err().to_string();
+    assert!(error.contains("shared@example.com"), "{}", error);
+    assert!(
+        error.contains(&format!("{}, {}", first, second)),
+        "{}",
+        error
+    );
+}
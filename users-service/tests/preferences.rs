@@ -0,0 +1,169 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn users_start_with_default_preferences() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app.get_preferences(user.id).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"0\"");
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["locale"], "en");
+    assert_eq!(body["timezone"], "UTC");
+    assert_eq!(body["theme"], "system");
+    for kind in [
+        "post_published",
+        "comment_created",
+        "post_liked",
+        "user_followed",
+    ] {
+        assert_eq!(
+            body["notifications"][kind],
+            serde_json::json!({ "in_app": true, "email": false, "push": false })
+        );
+    }
+}
+
+#[tokio::test]
+async fn put_preferences_stores_document_and_publishes_it() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    // Served from the cache afterwards, so the write has to refresh it
+    app.get_preferences(user.id).await;
+
+    let document = serde_json::json!({
+        "locale": "pt-BR",
+        "timezone": "America/Sao_Paulo",
+        "theme": "dark",
+        "notifications": {
+            "post_liked": { "in_app": false },
+            "user_followed": { "email": true },
+        },
+    });
+    let response = app.put_preferences(user.id, &document).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"1\"");
+
+    let response = app.get_preferences(user.id).await;
+    assert_eq!(common::etag(&response), "\"1\"");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["locale"], "pt-BR");
+    assert_eq!(body["theme"], "dark");
+    assert_eq!(
+        body["notifications"]["post_liked"],
+        serde_json::json!({ "in_app": false, "email": false, "push": false })
+    );
+    assert_eq!(
+        body["notifications"]["user_followed"],
+        serde_json::json!({ "in_app": true, "email": true, "push": false })
+    );
+    assert_eq!(body["notifications"]["comment_created"]["in_app"], true);
+
+    let events = app.outbox_payloads("user_preferences_updated").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], user.id.to_string());
+    assert_eq!(events[0]["version"], 1);
+    assert_eq!(events[0]["preferences"], body);
+
+    // Saving the same document again changes nothing
+    let response = app.put_preferences(user.id, &document).await;
+    assert_eq!(common::etag(&response), "\"1\"");
+    assert_eq!(
+        app.outbox_payloads("user_preferences_updated").await.len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn put_preferences_rejects_documents_failing_the_schema() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let invalid = [
+        (serde_json::json!({ "timezone": "UTC" }), "document"),
+        (
+            serde_json::json!({ "locale": "en", "timezone": "UTC", "font": "serif" }),
+            "document",
+        ),
+        (
+            serde_json::json!({ "locale": "english", "timezone": "UTC" }),
+            "locale",
+        ),
+        (
+            serde_json::json!({ "locale": "en", "timezone": "Not a zone" }),
+            "timezone",
+        ),
+        (
+            serde_json::json!({ "locale": "en", "timezone": "UTC", "theme": "neon" }),
+            "theme",
+        ),
+        (
+            serde_json::json!({
+                "locale": "en",
+                "timezone": "UTC",
+                "notifications": { "user_deleted": { "in_app": false } },
+            }),
+            "notifications",
+        ),
+        (
+            serde_json::json!({
+                "locale": "en",
+                "timezone": "UTC",
+                "notifications": { "post_liked": { "email": "yes" } },
+            }),
+            "notifications.post_liked.email",
+        ),
+    ];
+
+    for (document, field) in invalid {
+        let response = app.put_preferences(user.id, &document).await;
+        assert_eq!(response.status(), 400, "{}", document);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["fields"][field].is_array(), "{} -> {}", document, body);
+    }
+
+    assert!(
+        app.outbox_payloads("user_preferences_updated")
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn preferences_of_unknown_users_are_not_found() {
+    let app = common::spawn_app().await;
+    let id = Uuid::new_v4();
+
+    assert_eq!(app.get_preferences(id).await.status(), 404);
+    let document = serde_json::json!({ "locale": "en", "timezone": "UTC" });
+    assert_eq!(app.put_preferences(id, &document).await.status(), 404);
+}
+
+#[tokio::test]
+async fn deleted_users_preferences_are_not_served() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let document = serde_json::json!({ "locale": "de", "timezone": "Europe/Berlin" });
+    assert_eq!(app.put_preferences(user.id, &document).await.status(), 200);
+    assert_eq!(app.get_preferences(user.id).await.status(), 200);
+
+    assert_eq!(app.delete_user(user.id).await.status(), 200);
+    assert_eq!(app.get_preferences(user.id).await.status(), 404);
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use serde_json::json;
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn patch_profile_emits_user_updated() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app
+        .patch_profile(user.id, &json!({ "display_name": "Ada", "bio": null }))
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let events = app.outbox_payloads("user_updated").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0]["changes"],
+        json!({ "display_name": { "before": null, "after": "Ada" } })
+    );
+}
+
+#[tokio::test]
+async fn patch_profile_sets_only_given_fields() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app
+        .patch_profile(
+            user.id,
+            &json!({
+                "display_name": "Ada Lovelace",
+                "bio": "First programmer.",
+                "social_links": { "github": "https://github.com/ada" },
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"2\"");
+
+    let response = app
+        .patch_profile(user.id, &json!({ "location": "London" }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["display_name"], "Ada Lovelace");
+    assert_eq!(body["bio"], "First programmer.");
+    assert_eq!(body["location"], "London");
+    assert_eq!(body["social_links"]["github"], "https://github.com/ada");
+    assert_eq!(body["email"], user.email);
+    assert_eq!(body["version"], 3);
+}
+
+#[tokio::test]
+async fn patch_profile_clears_fields_set_to_null_or_empty() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    app.patch_profile(
+        user.id,
+        &json!({ "display_name": "Ada", "bio": "Bio", "website": "https://ada.dev" }),
+    )
+    .await;
+
+    let response = app
+        .patch_profile(user.id, &json!({ "display_name": null, "bio": "  " }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["display_name"].is_null());
+    assert!(body["bio"].is_null());
+    assert_eq!(body["website"], "https://ada.dev");
+}
+
+#[tokio::test]
+async fn public_profile_omits_email() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    app.patch_profile(user.id, &json!({ "display_name": "Ada" }))
+        .await;
+
+    let response = app.get_profile(user.id).await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["username"], user.username);
+    assert_eq!(body["display_name"], "Ada");
+    assert!(body.get("email").is_none());
+
+    assert_eq!(app.get_profile(Uuid::new_v4()).await.status(), 404);
+}
+
+#[tokio::test]
+async fn patch_profile_rejects_invalid_links() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app
+        .patch_profile(
+            user.id,
+            &json!({
+                "avatar_url": "javascript:alert(1)",
+                "social_links": { "Bad Label": "https://example.com" },
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["fields"]["avatar_url"].is_array());
+    assert!(body["fields"]["social_links"].is_array());
+
+    let too_many: serde_json::Map<_, _> = (0..11)
+        .map(|i| (format!("site{}", i), json!("https://example.com")))
+        .collect();
+    let response = app
+        .patch_profile(user.id, &json!({ "social_links": too_many }))
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let profile: serde_json::Value = app.get_profile(user.id).await.json().await.unwrap();
+    assert!(profile["avatar_url"].is_null());
+    assert_eq!(profile["social_links"], json!({}));
+}
+
+#[tokio::test]
+async fn full_update_keeps_profile_fields() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let response = app.patch_profile(user.id, &json!({ "bio": "Kept" })).await;
+    let etag = common::etag(&response);
+
+    let response = app
+        .update_user(
+            user.id,
+            &json!({
+                "id": user.id,
+                "username": "renamed_user",
+                "email": user.email,
+                "created_at": user.created_at,
+                "updated_at": chrono::Utc::now(),
+            }),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let profile: serde_json::Value = app.get_profile(user.id).await.json().await.unwrap();
+    assert_eq!(profile["username"], "renamed_user");
+    assert_eq!(profile["bio"], "Kept");
+}
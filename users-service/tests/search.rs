@@ -0,0 +1,115 @@
+mod common;
+
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp, username: &str) -> UserResponse {
+    let response = app
+        .post_user(&UserRequest {
+            username: username.to_string(),
+            email: format!("{}@example.com", Uuid::new_v4()),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+async fn usernames(response: reqwest::Response) -> (Vec<String>, u64) {
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let names = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["username"].as_str().unwrap().to_string())
+        .collect();
+    (names, body["total"].as_u64().unwrap())
+}
+
+#[tokio::test]
+async fn search_matches_username_and_display_name_prefixes() {
+    let app = common::spawn_app().await;
+    create_user(&app, "alice").await;
+    create_user(&app, "alicia").await;
+    let writer = create_user(&app, "writer42").await;
+    create_user(&app, "bob").await;
+    let response = app
+        .patch_profile(
+            writer.id,
+            &serde_json::json!({ "display_name": "Alina Writes" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let (mut names, total) = usernames(app.search_users("ALI").await).await;
+    names.sort();
+    assert_eq!(names, ["alice", "alicia", "writer42"]);
+    assert_eq!(total, 3);
+}
+
+#[tokio::test]
+async fn search_matches_underscores_literally() {
+    let app = common::spawn_app().await;
+    create_user(&app, "x_y").await;
+    create_user(&app, "xzy_long").await;
+
+    let (names, _) = usernames(app.search_users("x_").await).await;
+    assert_eq!(names, ["x_y"]);
+}
+
+#[tokio::test]
+async fn search_ranks_exact_matches_then_by_followers() {
+    let app = common::spawn_app().await;
+    let sam = create_user(&app, "sam").await;
+    let popular = create_user(&app, "sam_popular").await;
+    let quiet = create_user(&app, "sam_quiet").await;
+    let fan = create_user(&app, "fan").await;
+    let other_fan = create_user(&app, "other_fan").await;
+
+    for follower in [fan.id, other_fan.id] {
+        assert_eq!(app.follow(popular.id, follower).await.status(), 200);
+    }
+    assert_eq!(app.follow(quiet.id, fan.id).await.status(), 200);
+
+    let response = app.search_users("sam").await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    let data = body["data"].as_array().unwrap();
+    let ranked: Vec<&str> = data
+        .iter()
+        .map(|user| user["username"].as_str().unwrap())
+        .collect();
+    assert_eq!(ranked, ["sam", "sam_popular", "sam_quiet"]);
+    assert_eq!(data[0]["id"], sam.id.to_string());
+    assert_eq!(data[1]["followers_count"], 2);
+    assert!(data[0].get("email").is_none());
+}
+
+#[tokio::test]
+async fn search_finds_similar_usernames() {
+    let app = common::spawn_app().await;
+    create_user(&app, "jonathan").await;
+    create_user(&app, "margaret").await;
+
+    let (names, _) = usernames(app.search_users("jonathon").await).await;
+    assert_eq!(names, ["jonathan"]);
+}
+
+#[tokio::test]
+async fn search_skips_trashed_users() {
+    let app = common::spawn_app().await;
+    let gone = create_user(&app, "carol").await;
+    create_user(&app, "caroline").await;
+    assert_eq!(app.delete_user(gone.id).await.status(), 200);
+
+    let (names, total) = usernames(app.search_users("carol").await).await;
+    assert_eq!(names, ["caroline"]);
+    assert_eq!(total, 1);
+}
+
+#[tokio::test]
+async fn search_rejects_empty_and_overlong_queries() {
+    let app = common::spawn_app().await;
+
+    assert_eq!(app.search_users("   ").await.status(), 400);
+    assert_eq!(app.search_users(&"a".repeat(65)).await.status(), 400);
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{TestApp, UserRequest, UserResponse};
+use uuid::Uuid;
+
+async fn create_user(app: &TestApp) -> UserResponse {
+    let id = Uuid::new_v4();
+    let response = app
+        .post_user(&UserRequest {
+            username: format!("user_{}", id),
+            email: format!("{}@example.com", id),
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn suspending_a_user_records_reason_and_expiry() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let expires_at = Utc::now() + Duration::days(7);
+
+    let response = app
+        .change_status(
+            user.id,
+            &serde_json::json!({
+                "status": "suspended",
+                "reason": "Spam",
+                "expires_at": expires_at,
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"2\"");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "suspended");
+    assert_eq!(body["status_reason"], "Spam");
+
+    let events = app.outbox_payloads("user_status_changed").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], user.id.to_string());
+    assert_eq!(events[0]["status"], "suspended");
+    assert_eq!(events[0]["previous_status"], "active");
+    assert_eq!(events[0]["reason"], "Spam");
+    assert_eq!(events[0]["lapsed"], false);
+
+    // Setting the same status again changes nothing
+    let response = app
+        .change_status(
+            user.id,
+            &serde_json::json!({
+                "status": "suspended",
+                "reason": "Spam",
+                "expires_at": expires_at,
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(app.outbox_payloads("user_status_changed").await.len(), 1);
+}
+
+#[tokio::test]
+async fn change_status_rejects_inconsistent_changes() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let invalid = [
+        serde_json::json!({ "status": "suspended" }),
+        serde_json::json!({ "status": "banned", "reason": "   " }),
+        serde_json::json!({
+            "status": "banned",
+            "reason": "Abuse",
+            "expires_at": Utc::now() + Duration::days(1),
+        }),
+        serde_json::json!({
+            "status": "suspended",
+            "reason": "Spam",
+            "expires_at": Utc::now() - Duration::days(1),
+        }),
+    ];
+    for body in invalid {
+        let response = app.change_status(user.id, &body).await;
+        assert_eq!(response.status(), 400, "{body}");
+    }
+
+    let response = app
+        .change_status(
+            Uuid::new_v4(),
+            &serde_json::json!({ "status": "deactivated" }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn inactive_users_cannot_write() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    let other = create_user(&app).await;
+
+    let response = app
+        .change_status(
+            user.id,
+            &serde_json::json!({ "status": "banned", "reason": "Abuse" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let response = app
+        .patch_profile(user.id, &serde_json::json!({ "bio": "hello" }))
+        .await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(app.follow(other.id, user.id).await.status(), 403);
+
+    // Others can still follow them, and they can be reinstated
+    assert_eq!(app.follow(user.id, other.id).await.status(), 200);
+    let response = app
+        .change_status(user.id, &serde_json::json!({ "status": "active" }))
+        .await;
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "active");
+    assert!(body.get("status_reason").is_none());
+
+    let response = app
+        .patch_profile(user.id, &serde_json::json!({ "bio": "hello" }))
+        .await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn lapsed_suspensions_are_lifted() {
+    let app = common::spawn_app().await;
+    let lapsing = create_user(&app).await;
+    let indefinite = create_user(&app).await;
+
+    let response = app
+        .change_status(
+            lapsing.id,
+            &serde_json::json!({
+                "status": "suspended",
+                "reason": "Cooling off",
+                "expires_at": Utc::now() + Duration::hours(1),
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app
+        .change_status(
+            indefinite.id,
+            &serde_json::json!({ "status": "suspended", "reason": "Under review" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let users = &app.repo_provider.users;
+    assert!(
+        users
+            .reactivate_expired(Utc::now())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let lifted = users
+        .reactivate_expired(Utc::now() + Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(lifted.len(), 1);
+    assert_eq!(lifted[0].id, lapsing.id);
+
+    let fetched: UserResponse = app.get_user_by_id(lapsing.id).await.json().await.unwrap();
+    assert_eq!(fetched.version, 3);
+
+    let events = app.outbox_payloads("user_status_changed").await;
+    let lapsed: Vec<_> = events.iter().filter(|e| e["lapsed"] == true).collect();
+    assert_eq!(lapsed.len(), 1);
+    assert_eq!(lapsed[0]["id"], lapsing.id.to_string());
+    assert_eq!(lapsed[0]["status"], "active");
+    assert_eq!(lapsed[0]["previous_status"], "suspended");
+
+    let response = app.list_users_by_status("suspended").await;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["id"], indefinite.id.to_string());
+}
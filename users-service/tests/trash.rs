@@ -0,0 +1,139 @@
+mod common;
+
+use common::{ListUsersResponse, UserRequest, UserResponse};
+use uuid::Uuid;
+
+fn sample_user() -> UserRequest {
+    let id = Uuid::new_v4();
+    UserRequest {
+        username: format!("user_{}", id),
+        email: format!("{}@example.com", id),
+    }
+}
+
+async fn create_user(app: &common::TestApp) -> UserResponse {
+    let response = app.post_user(&sample_user()).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn deleted_user_moves_to_trash() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    let response = app.delete_user(user.id).await;
+    assert_eq!(response.status(), 200);
+
+    assert_eq!(app.get_user_by_id(user.id).await.status(), 404);
+
+    let listed: ListUsersResponse = app.list_users().await.json().await.unwrap();
+    assert!(listed.data.iter().all(|u| u.id != user.id));
+
+    let trash: ListUsersResponse = app.list_trash().await.json().await.unwrap();
+    assert_eq!(trash.total, 1);
+    assert_eq!(trash.data[0].id, user.id);
+    assert!(trash.data[0].deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn deleting_trashed_user_returns_404() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    assert_eq!(app.delete_user(user.id).await.status(), 200);
+    assert_eq!(app.delete_user(user.id).await.status(), 404);
+}
+
+#[tokio::test]
+async fn restore_brings_user_back() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    app.delete_user(user.id).await;
+
+    let response = app.restore_user(user.id).await;
+    assert_eq!(response.status(), 200);
+    let etag = common::etag(&response);
+    let restored: UserResponse = response.json().await.unwrap();
+    assert_eq!(restored.id, user.id);
+    assert_eq!(restored.version, user.version + 2);
+    assert_eq!(etag, format!("\"{}\"", restored.version));
+
+    let fetched: UserResponse = app.get_user_by_id(user.id).await.json().await.unwrap();
+    assert_eq!(fetched.username, user.username);
+    assert!(fetched.deleted_at.is_none());
+
+    let trash: ListUsersResponse = app.list_trash().await.json().await.unwrap();
+    assert_eq!(trash.total, 0);
+}
+
+#[tokio::test]
+async fn restore_of_live_user_returns_404() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+
+    assert_eq!(app.restore_user(user.id).await.status(), 404);
+    assert_eq!(app.restore_user(Uuid::new_v4()).await.status(), 404);
+}
+
+#[tokio::test]
+async fn purge_removes_only_users_trashed_before_cutoff() {
+    let app = common::spawn_app().await;
+    let trashed = create_user(&app).await;
+    let live = create_user(&app).await;
+    app.delete_user(trashed.id).await;
+
+    let users = &app.repo_provider.users;
+    let deadline = chrono::Utc::now() + chrono::Duration::hours(1);
+
+    let purged = users
+        .purge_deleted_users(chrono::Utc::now() - chrono::Duration::days(1), deadline)
+        .await
+        .unwrap();
+    assert!(purged.is_empty());
+
+    let purged = users
+        .purge_deleted_users(chrono::Utc::now() + chrono::Duration::seconds(1), deadline)
+        .await
+        .unwrap();
+    assert_eq!(purged, vec![trashed.id]);
+
+    // Already being erased, so the next run leaves the user alone
+    let purged = users
+        .purge_deleted_users(chrono::Utc::now() + chrono::Duration::seconds(1), deadline)
+        .await
+        .unwrap();
+    assert!(purged.is_empty());
+
+    assert_eq!(app.restore_user(trashed.id).await.status(), 409);
+    assert_eq!(app.get_user_by_id(live.id).await.status(), 200);
+}
+
+#[tokio::test]
+async fn purge_erases_trashed_users_in_the_other_services() {
+    let app = common::spawn_app().await;
+    let user = create_user(&app).await;
+    app.delete_user(user.id).await;
+
+    // A plain deletion leaves the erasure to the purge
+    let events = app.outbox_payloads("user_deleted").await;
+    assert_eq!(events.len(), 1);
+    assert!(events[0].get("erasure_id").is_none());
+
+    let purged = app
+        .trash_purger(chrono::Duration::zero())
+        .purge()
+        .await
+        .unwrap();
+    assert_eq!(purged, vec![user.id]);
+
+    let response = app.get_erasure(user.id).await;
+    assert_eq!(response.status(), 200);
+    let erasure: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(erasure["status"], "pending");
+
+    let events = app.outbox_payloads("user_deleted").await;
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1]["id"], user.id.to_string());
+    assert_eq!(events[1]["erasure_id"], erasure["id"]);
+}
@@ -46,6 +46,32 @@ async fn create_user_returns_422_for_missing_fields() {
     assert_eq!(response.status(), 422);
 }
 
+#[tokio::test]
+async fn create_user_returns_field_errors_for_invalid_data() {
+    let app = common::spawn_app().await;
+
+    let response = app
+        .post_user(&UserRequest {
+            username: "ab".to_string(),
+            email: "not-an-email".to_string(),
+        })
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body["fields"]["username"][0],
+        "Username must be between 3-64 characters"
+    );
+    assert_eq!(
+        body["fields"]["email"][0],
+        "Email must be a valid email address"
+    );
+
+    let listed: serde_json::Value = app.list_users().await.json().await.unwrap();
+    assert_eq!(listed["total"], 0);
+}
+
 #[tokio::test]
 async fn get_user_returns_404_for_nonexistent_user() {
     let app = common::spawn_app().await;
@@ -74,27 +100,272 @@ async fn list_users_returns_all_created_users() {
     assert!(data.len() >= 3);
 }
 
+async fn listed_users(app: &common::TestApp) -> Vec<serde_json::Value> {
+    let response = app.list_users().await;
+    assert_eq!(response.status(), 200);
+    let listed: serde_json::Value = response.json().await.unwrap();
+    listed["data"].as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn list_users_serves_cached_pages_until_users_change() {
+    use users_service::domain::{profile::ProfileUpdate, repository::UserRepository};
+
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let first: UserResponse = response.json().await.unwrap();
+    assert_eq!(listed_users(&app).await.len(), 1);
+
+    // Changed behind the cache's back, so only a cached page still shows the old name
+    let update = ProfileUpdate {
+        display_name: Some(Some("Hidden".to_string())),
+        ..Default::default()
+    };
+    app.uncached_users()
+        .await
+        .update_profile(first.id, update)
+        .await
+        .unwrap();
+    let listed = listed_users(&app).await;
+    assert_eq!(listed[0]["display_name"], serde_json::Value::Null);
+
+    let response = app
+        .patch_profile(first.id, &serde_json::json!({ "display_name": "Visible" }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let listed = listed_users(&app).await;
+    assert_eq!(listed[0]["display_name"], "Visible");
+
+    let response = app.post_user(&sample_user()).await;
+    let second: UserResponse = response.json().await.unwrap();
+    assert_eq!(listed_users(&app).await.len(), 2);
+
+    assert_eq!(app.delete_user(second.id).await.status(), 200);
+    let listed = listed_users(&app).await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["id"], first.id.to_string());
+}
+
+#[tokio::test]
+async fn list_pages_follow_writes_made_by_other_instances() {
+    use std::{sync::Arc, time::Duration};
+
+    use ::common::{
+        cache::{LocalCache, TieredCache},
+        pagination::Pagination,
+    };
+    use users_service::{
+        domain::{profile::ProfileUpdate, repository::UserRepository},
+        infrastructure::database::CachedUserRepository,
+    };
+
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let created: UserResponse = response.json().await.unwrap();
+
+    // Two instances with their own L1 in front of one shared L2
+    let ttl = Duration::from_secs(60);
+    let shared = Arc::new(LocalCache::with_ttl(ttl));
+    let first = CachedUserRepository::new(
+        Arc::new(app.uncached_users().await),
+        Arc::new(TieredCache::new(LocalCache::with_ttl(ttl), ttl).add_l2(shared.clone())),
+        ttl,
+    );
+    let second = CachedUserRepository::new(
+        Arc::new(app.uncached_users().await),
+        Arc::new(TieredCache::new(LocalCache::with_ttl(ttl), ttl).add_l2(shared)),
+        ttl,
+    );
+
+    let pagination = Pagination {
+        page: 1,
+        page_size: 10,
+    };
+    let (users, _) = first.list_users(&pagination).await.unwrap();
+    assert_eq!(users[0].display_name, None);
+
+    let update = ProfileUpdate {
+        display_name: Some(Some("Renamed".to_string())),
+        ..Default::default()
+    };
+    second.update_profile(created.id, update).await.unwrap();
+
+    let (users, _) = first.list_users(&pagination).await.unwrap();
+    assert_eq!(users[0].display_name.as_deref(), Some("Renamed"));
+}
+
 #[tokio::test]
 async fn update_user_persists_changes() {
     let app = common::spawn_app().await;
     let user = sample_user();
 
-    let created: UserResponse = app.post_user(&user).await.json().await.unwrap();
+    let response = app.post_user(&user).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
 
     let update_body = serde_json::json!({
         "id": created.id,
         "username": "updated_name",
-        "email": "updated@example.com",
+        "email": created.email,
         "created_at": created.created_at,
         "updated_at": chrono::Utc::now(),
     });
 
-    let response = app.update_user(created.id, &update_body).await;
+    let response = app.update_user(created.id, &update_body, Some(&etag)).await;
     assert_eq!(response.status(), 200);
+    assert_eq!(common::etag(&response), "\"2\"");
 
-    let updated: UserResponse = app.get_user_by_id(created.id).await.json().await.unwrap();
+    let response = app.get_user_by_id(created.id).await;
+    assert_eq!(common::etag(&response), "\"2\"");
+
+    let updated: UserResponse = response.json().await.unwrap();
     assert_eq!(updated.username, "updated_name");
-    assert_eq!(updated.email, "updated@example.com");
+    assert_eq!(updated.email, created.email);
+    assert_eq!(updated.version, 2);
+}
+
+#[tokio::test]
+async fn update_user_emits_user_updated_with_changed_fields() {
+    let app = common::spawn_app().await;
+    let response = app.post_user(&sample_user()).await;
+    let etag = common::etag(&response);
+    let created: UserResponse = response.json().await.unwrap();
+
+    let response = app
+        .update_user(
+            created.id,
+            &update_body(&created, "renamed_user"),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let events = app.outbox_payloads("user_updated").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], created.id.to_string());
+    assert_eq!(events[0]["username"], "renamed_user");
+    assert_eq!(events[0]["version"], 2);
+    assert_eq!(
+        events[0]["changes"],
+        serde_json::json!({
+            "username": { "before": created.username, "after": "renamed_user" },
+        })
+    );
+
+    // Saving the same values again changes nothing worth announcing
+    let response = app
+        .update_user(
+            created.id,
+            &update_body(&created, "renamed_user"),
+            Some("\"2\""),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(app.outbox_payloads("user_updated").await.len(), 1);
+}
+
+#[tokio::test]
+async fn delete_user_emits_user_deleted() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+
+    assert_eq!(app.delete_user(created.id).await.status(), 200);
+
+    let events = app.outbox_payloads("user_deleted").await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["id"], created.id.to_string());
+    assert_eq!(events[0]["username"], created.username);
+    assert_eq!(events[0]["email"], created.email);
+    assert!(events[0]["deleted_at"].is_string());
+}
+
+fn update_body(created: &UserResponse, username: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": created.id,
+        "username": username,
+        "email": created.email,
+        "created_at": created.created_at,
+        "updated_at": chrono::Utc::now(),
+    })
+}
+
+#[tokio::test]
+async fn update_user_without_if_match_returns_428() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+
+    let response = app
+        .update_user(created.id, &update_body(&created, "no_precondition"), None)
+        .await;
+    assert_eq!(response.status(), 428);
+}
+
+#[tokio::test]
+async fn update_user_rejects_invalid_email() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_user_by_id(created.id).await);
+
+    let mut body = update_body(&created, "valid_name");
+    body["email"] = serde_json::json!("nope");
+    let response = app.update_user(created.id, &body, Some(&etag)).await;
+    assert_eq!(response.status(), 400);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["fields"]["email"].is_array());
+    assert!(body["fields"].get("username").is_none());
+}
+
+#[tokio::test]
+async fn update_user_with_stale_etag_returns_412() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_user_by_id(created.id).await);
+
+    let first = app
+        .update_user(
+            created.id,
+            &update_body(&created, "first_editor"),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(first.status(), 200);
+
+    let second = app
+        .update_user(
+            created.id,
+            &update_body(&created, "second_editor"),
+            Some(&etag),
+        )
+        .await;
+    assert_eq!(second.status(), 412);
+
+    let fetched: UserResponse = app.get_user_by_id(created.id).await.json().await.unwrap();
+    assert_eq!(fetched.username, "first_editor");
+}
+
+#[tokio::test]
+async fn renaming_user_evicts_old_username_from_cache() {
+    let app = common::spawn_app().await;
+    let created: UserResponse = app.post_user(&sample_user()).await.json().await.unwrap();
+    let etag = common::etag(&app.get_user_by_id(created.id).await);
+
+    let new_name = format!("renamed_{}", created.id);
+    let response = app
+        .update_user(created.id, &update_body(&created, &new_name), Some(&etag))
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let users = &app.repo_provider.users;
+    assert!(
+        users
+            .get_user_by_name(created.username.clone())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let renamed = users.get_user_by_name(new_name).await.unwrap().unwrap();
+    assert_eq!(renamed.version, 2);
 }
 
 #[tokio::test]